@@ -0,0 +1,121 @@
+//! Generate the perfect hash tables of the contig names of the bundled builds.
+
+use std::{collections::HashSet, env, fmt::Write as _, fs, path::Path};
+
+#[allow(dead_code)]
+#[path = "src/phf.rs"]
+mod phf;
+
+/// The bundled assembly reports and the names of the generated tables.
+const BUNDLED: [(&str, &str); 2] = [
+    (
+        "src/data/GCF_000001405.25_GRCh37.p13_assembly_report.tsv",
+        "GRCH37_P13",
+    ),
+    (
+        "src/data/GCF_000001405.39_GRCh38.p13_assembly_report.tsv",
+        "GRCH38_P13",
+    ),
+];
+
+fn main() {
+    let mut out = String::new();
+    for (path, table) in BUNDLED {
+        println!("cargo:rerun-if-changed={path}");
+        let report = fs::read_to_string(path).expect("Bundled assembly report");
+        let (seeds, entries) = generate(&contig_names(&report));
+        writeln!(
+            out,
+            "pub(crate) static {table}: PerfectIndex = PerfectIndex {{\n    \
+                seeds: &{seeds:?},\n    \
+                entries: &{entries:?},\n\
+            }};"
+        )
+        .expect("Writing to a string");
+    }
+    println!("cargo:rerun-if-changed=src/phf.rs");
+
+    let path = Path::new(&env::var("OUT_DIR").expect("Cargo sets OUT_DIR")).join("bundled.rs");
+    fs::write(path, out).expect("Writing the generated tables");
+}
+
+/// Get the names of the contigs of the `report`, in the order of the bundled build.
+///
+/// The names mirror the assembly report parser: the sequence name,
+/// followed by the GenBank, RefSeq, and UCSC-style names, unless `na`.
+/// A repeated name is kept once, as by `Contig::from_names`,
+/// hence the name positions match `Contig::names`.
+fn contig_names(report: &str) -> Vec<Vec<&str>> {
+    let mut contigs: Vec<Vec<&str>> = report
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let fields: Vec<_> = line.split('\t').collect();
+            let mut names = vec![fields[0]];
+            for &i in &[4, 6, 9] {
+                match fields.get(i).copied() {
+                    Some(name) if name != "na" && !names.contains(&name) => names.push(name),
+                    _ => {}
+                }
+            }
+            names
+        })
+        .collect();
+    // The bundled builds are sorted by the main name.
+    contigs.sort_by(|l, r| l[0].cmp(r[0]));
+    contigs
+}
+
+/// Generate the bucket seeds and the slot entries of the names of the `contigs`.
+///
+/// A name shared by several contigs points to the first contig, as in the other indexes.
+fn generate(contigs: &[Vec<&str>]) -> (Vec<u32>, Vec<(u32, u32)>) {
+    let mut keys: Vec<(&str, (u32, u32))> = vec![];
+    let mut seen = HashSet::new();
+    for (i, names) in contigs.iter().enumerate() {
+        for (j, &name) in names.iter().enumerate() {
+            if seen.insert(name) {
+                keys.push((name, (i as u32, j as u32)));
+            }
+        }
+    }
+
+    let n_slots = keys.len() as u64;
+    let n_buckets = keys.len().div_ceil(4).max(1);
+    let mut buckets = vec![vec![]; n_buckets];
+    for key in &keys {
+        buckets[(phf::hash(key.0, 0) % n_buckets as u64) as usize].push(*key);
+    }
+    // Place the largest buckets first, while most slots are free.
+    let mut order: Vec<_> = (0..n_buckets).collect();
+    order.sort_by_key(|&b| std::cmp::Reverse(buckets[b].len()));
+
+    let mut seeds = vec![0; n_buckets];
+    let mut slots: Vec<Option<(u32, u32)>> = vec![None; keys.len()];
+    for b in order {
+        if buckets[b].is_empty() {
+            continue;
+        }
+        let seed = (1..)
+            .find(|&seed| {
+                let mut taken: Vec<usize> = vec![];
+                buckets[b].iter().all(|&(name, _)| {
+                    let slot = (phf::hash(name, seed) % n_slots) as usize;
+                    let free = slots[slot].is_none() && !taken.contains(&slot);
+                    taken.push(slot);
+                    free
+                })
+            })
+            .expect("Perfect hash seed");
+        seeds[b] = seed;
+        for &(name, entry) in &buckets[b] {
+            slots[(phf::hash(name, seed) % n_slots) as usize] = Some(entry);
+        }
+    }
+
+    let entries = slots
+        .into_iter()
+        .map(|slot| slot.expect("One slot per name"))
+        .collect();
+    (seeds, entries)
+}
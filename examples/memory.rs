@@ -0,0 +1,62 @@
+//! Measure the heap memory used by a highly fragmented genome build.
+//!
+//! Run with:
+//!
+//! ```shell
+//! cargo run --release --example memory
+//! ```
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use dabuild::{GenomeBuild, GenomeBuildIdentifier, IndexKind};
+
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const N_SCAFFOLDS: usize = 300_000;
+
+fn main() {
+    for index in [IndexKind::Hash, IndexKind::Sorted] {
+        let before = ALLOCATED.load(Ordering::Relaxed);
+
+        let mut builder = GenomeBuild::builder()
+            .id(GenomeBuildIdentifier::from_str("fragmented").expect("Infallible"))
+            .index(index);
+        for i in 0..N_SCAFFOLDS {
+            builder = builder
+                .contig(i, 1_000u32)
+                .alias(i, format!("JAB{i:08}.1"))
+                .alias(i, format!("scaffold_{i}"));
+        }
+        let build = builder.build().expect("Valid build");
+
+        let after = ALLOCATED.load(Ordering::Relaxed);
+        let total = after - before;
+        println!(
+            "{} contigs with {index:?} index use {:.1} MiB of heap ({} bytes per contig)",
+            build.contigs().count(),
+            total as f64 / (1024. * 1024.),
+            total / N_SCAFFOLDS,
+        );
+    }
+}
@@ -0,0 +1,50 @@
+/*
+ * C bindings of dabuild, available with the `ffi` feature.
+ *
+ * See the documentation of the `dabuild::ffi` module for more info.
+ */
+#ifndef DABUILD_H
+#define DABUILD_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* An opaque genome build handle. */
+typedef struct DabuildGenomeBuild DabuildGenomeBuild;
+
+/* Get the bundled builds. Release with `dabuild_genome_build_free`. */
+DabuildGenomeBuild *dabuild_get_grch37_p13(void);
+DabuildGenomeBuild *dabuild_get_grch38_p13(void);
+
+/* Parse an assembly report. `patch` can be NULL. Returns NULL on failure. */
+DabuildGenomeBuild *dabuild_parse_assembly_report(const char *path,
+                                                  const char *major_assembly,
+                                                  const char *patch);
+
+/* Release the genome build. NULL is ignored. */
+void dabuild_genome_build_free(DabuildGenomeBuild *build);
+
+/* Get the number of contigs. */
+size_t dabuild_genome_build_contig_count(const DabuildGenomeBuild *build);
+
+/* Store the length of a contig into `length`. Returns 0 on success, -1 for unknown contig. */
+int dabuild_contig_length(const DabuildGenomeBuild *build, const char *name, uint64_t *length);
+
+/*
+ * Write the main name of a contig into `buf`, like `snprintf`.
+ * Returns the main name length or -1 for unknown contig.
+ */
+ptrdiff_t dabuild_contig_name(const DabuildGenomeBuild *build,
+                              const char *name,
+                              char *buf,
+                              size_t buf_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* DABUILD_H */
@@ -0,0 +1,155 @@
+//! # Annotation audit
+//!
+//! The module checks the contig usage of a GTF or GFF3 annotation against a genome build,
+//! the usual sanity check before building an annotation index.
+//! See [`audit_annotation`].
+
+use std::{error::Error, io::BufRead, str::FromStr};
+
+use num_traits::Zero;
+
+use crate::{decompress::decompressed, GenomeBuild};
+
+/// A feature that exceeds the bounds of its contig.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OutOfBounds<C> {
+    /// The 0-based index of the feature line.
+    pub line: usize,
+    /// The main name of the contig.
+    pub contig: String,
+    /// The 1-based start coordinate (included).
+    pub start: C,
+    /// The 1-based end coordinate (included).
+    pub end: C,
+}
+
+/// The contig usage of an annotation, see [`audit_annotation`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnnotationAudit<C> {
+    features: usize,
+    referenced: Vec<String>,
+    missing: Vec<String>,
+    out_of_bounds: Vec<OutOfBounds<C>>,
+}
+
+impl<C> AnnotationAudit<C> {
+    /// Get the number of the feature lines.
+    pub fn features(&self) -> usize {
+        self.features
+    }
+
+    /// Get the main names of the build contigs referenced by the annotation,
+    /// in the order of the first reference.
+    pub fn referenced(&self) -> &[String] {
+        &self.referenced
+    }
+
+    /// Get the sequence names of the annotation that are absent from the build,
+    /// in the order of the first reference.
+    pub fn missing(&self) -> &[String] {
+        &self.missing
+    }
+
+    /// Get the features that start before the contig start or end after the contig end.
+    pub fn out_of_bounds(&self) -> &[OutOfBounds<C>] {
+        &self.out_of_bounds
+    }
+
+    /// Test if all features are on the contigs of the build and within their bounds.
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.out_of_bounds.is_empty()
+    }
+}
+
+/// Scan a GTF or GFF3 annotation and check its contig usage against the `build`.
+///
+/// The sequence names (the 1st column) are looked up in the build by any of the contig names.
+/// The features are checked using the 1-based start and end (the 4th and the 5th column).
+/// The comment lines (`#`) and empty lines are skipped,
+/// and the scan stops at the `##FASTA` directive of GFF3.
+/// A gzip-compressed annotation is decompressed if the `bgzf` feature is enabled.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::GenomeBuild;
+/// use dabuild::annotation::audit_annotation;
+/// use dabuild::builds::get_grch38_p13;
+///
+/// let build: GenomeBuild<u32> = get_grch38_p13();
+/// let gtf = "#!genome-build GRCh38.p13\n\
+///     chr1\tHAVANA\tgene\t11869\t14409\t.\t+\t.\tgene_id \"ENSG00000290825\";\n\
+///     chrM\tEnsembl\tgene\t16000\t17000\t.\t+\t.\tgene_id \"ENSG00000000000\";\n\
+///     chrEBV\tEnsembl\tgene\t1\t100\t.\t+\t.\tgene_id \"ENSG00000000001\";\n";
+///
+/// let audit = audit_annotation(&build, gtf.as_bytes()).expect("Valid GTF");
+///
+/// assert_eq!(audit.features(), 3);
+/// assert_eq!(audit.referenced(), ["1", "MT"]);
+/// assert_eq!(audit.missing(), ["chrEBV"]);
+/// assert_eq!(audit.out_of_bounds()[0].contig, "MT");
+/// assert!(!audit.is_clean());
+/// ```
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`BufRead`]
+/// * Missing or unparsable start or end coordinate
+pub fn audit_annotation<C, R>(
+    build: &GenomeBuild<C>,
+    read: R,
+) -> Result<AnnotationAudit<C>, Box<dyn Error>>
+where
+    C: Copy + FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    let mut audit = AnnotationAudit {
+        features: 0,
+        referenced: vec![],
+        missing: vec![],
+        out_of_bounds: vec![],
+    };
+
+    for (i, line) in decompressed(read)?.lines().enumerate() {
+        let line = line?;
+        if line.starts_with("##FASTA") {
+            break;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<_> = line.splitn(6, '\t').collect();
+        let (Some(start), Some(end)) = (
+            fields.get(3).and_then(|start| start.parse::<C>().ok()),
+            fields.get(4).and_then(|end| end.parse::<C>().ok()),
+        ) else {
+            return Err(format!("Missing or invalid start or end in line #{i} {line}").into());
+        };
+        audit.features += 1;
+
+        let seqid = fields[0];
+        match build.contig_by_name(seqid) {
+            Some(contig) => {
+                if !audit.referenced.iter().any(|name| name == contig.name()) {
+                    audit.referenced.push(contig.name().to_string());
+                }
+                if start <= C::zero() || end > *contig.length() {
+                    audit.out_of_bounds.push(OutOfBounds {
+                        line: i,
+                        contig: contig.name().to_string(),
+                        start,
+                        end,
+                    });
+                }
+            }
+            None => {
+                if !audit.missing.iter().any(|name| name == seqid) {
+                    audit.missing.push(seqid.to_string());
+                }
+            }
+        }
+    }
+
+    Ok(audit)
+}
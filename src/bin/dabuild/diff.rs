@@ -0,0 +1,99 @@
+//! Compare the contigs of two genome builds.
+
+use std::{
+    collections::HashSet,
+    error::Error,
+    io::{self, BufWriter, Write},
+    process::ExitCode,
+};
+
+use dabuild::Contig;
+
+use crate::source::{load, Format};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The old build, a bundled build or a path to a file.
+    old: String,
+
+    /// The new build, a bundled build or a path to a file.
+    new: String,
+
+    /// The format of the build files [default: guessed from the file extension].
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+}
+
+/// Describe the changes of a contig that is in both builds, if any.
+fn changes(old: &Contig<u64>, new: &Contig<u64>) -> Vec<String> {
+    let mut changes = vec![];
+    if old.name() != new.name() {
+        changes.push(format!("name {} -> {}", old.name(), new.name()));
+    }
+    if old.length() != new.length() {
+        changes.push(format!("length {} -> {}", old.length(), new.length()));
+    }
+    if old.role() != new.role() {
+        let role = |contig: &Contig<u64>| contig.role().map_or("unknown", |role| role.as_str());
+        changes.push(format!("role {} -> {}", role(old), role(new)));
+    }
+    let (old_names, new_names): (Vec<_>, Vec<_>) =
+        (old.alt_names().collect(), new.alt_names().collect());
+    if old_names != new_names {
+        changes.push(format!(
+            "alt names {} -> {}",
+            old_names.join(","),
+            new_names.join(",")
+        ));
+    }
+    changes
+}
+
+/// Print the removed, added, and changed contigs, one tab-separated line per contig.
+///
+/// The old contigs are paired with the new contigs by the main name of the old contig,
+/// which can match any name of the new contig.
+/// Fails if the builds differ, like `diff`.
+pub fn run(args: Args) -> Result<ExitCode, Box<dyn Error>> {
+    let old = load(&args.old, args.format)?;
+    let new = load(&args.new, args.format)?;
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    let (mut removed, mut added, mut changed) = (0usize, 0usize, 0usize);
+    let mut paired = HashSet::new();
+    for contig in old.contigs() {
+        match new.contig_by_name(contig.name()) {
+            Some(other) => {
+                paired.insert(other.name());
+                let changes = changes(contig, other);
+                if !changes.is_empty() {
+                    changed += 1;
+                    writeln!(out, "changed\t{}\t{}", contig.name(), changes.join("; "))?;
+                }
+            }
+            None => {
+                removed += 1;
+                writeln!(
+                    out,
+                    "removed\t{}\tlength {}",
+                    contig.name(),
+                    contig.length()
+                )?;
+            }
+        }
+    }
+    for contig in new.contigs() {
+        if !paired.contains(contig.name()) {
+            added += 1;
+            writeln!(out, "added\t{}\tlength {}", contig.name(), contig.length())?;
+        }
+    }
+    out.flush()?;
+
+    eprintln!("{removed} removed, {added} added, {changed} changed");
+    Ok(if removed + added + changed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
@@ -0,0 +1,39 @@
+//! Print the digests of a genome build.
+
+use std::{error::Error, process::ExitCode};
+
+use crate::source::SourceArgs;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    source: SourceArgs,
+}
+
+/// Print the build digests and the known MD5 digests of the contig sequences,
+/// one tab-separated line per digest.
+pub fn run(args: Args) -> Result<ExitCode, Box<dyn Error>> {
+    let build = args.source.load()?;
+
+    let digests = build.seqcol_digests();
+    println!("fingerprint\t{}", digests.fingerprint);
+    println!("names\t{}", digests.names);
+    println!("lengths\t{}", digests.lengths);
+    println!(
+        "sorted_name_length_pairs\t{}",
+        digests.sorted_name_length_pairs
+    );
+
+    let mut known = 0usize;
+    for contig in build.contigs() {
+        if let Some(md5) = contig.md5() {
+            known += 1;
+            println!("md5\t{}\t{md5}", contig.name());
+        }
+    }
+    if known == 0 {
+        eprintln!("No MD5 digests of the contig sequences are known");
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,100 @@
+//! Write a genome build in another format.
+
+use std::{
+    error::Error,
+    io::{self, BufWriter, Write},
+    process::ExitCode,
+};
+
+use clap::ValueEnum;
+use dabuild::{
+    builds::write_assembly_report,
+    formats::{write_chrom_sizes, write_dict, write_fai_stub, write_json, write_vcf_header},
+    NameStyle,
+};
+
+use crate::source::SourceArgs;
+
+/// The output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Target {
+    /// Tab-separated contig names and lengths.
+    #[value(name = "chrom.sizes")]
+    ChromSizes,
+    /// Sequence dictionary.
+    Dict,
+    /// FASTA index without sequence offsets.
+    FaiStub,
+    /// VCF header with `##contig` lines.
+    VcfHeader,
+    /// JSON object with the build identifier and the contigs.
+    Json,
+    /// Genome Reference Consortium assembly report.
+    AssemblyReport,
+}
+
+/// The contig naming style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Style {
+    /// The main name (e.g. `1`).
+    Main,
+    /// GenBank accession (e.g. `CM000663.2`).
+    Genbank,
+    /// RefSeq accession (e.g. `NC_000001.11`).
+    Refseq,
+    /// UCSC-style name (e.g. `chr1`).
+    Ucsc,
+}
+
+impl From<Style> for NameStyle {
+    fn from(style: Style) -> Self {
+        match style {
+            Style::Main => NameStyle::Main,
+            Style::Genbank => NameStyle::GenBank,
+            Style::Refseq => NameStyle::RefSeq,
+            Style::Ucsc => NameStyle::Ucsc,
+        }
+    }
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    source: SourceArgs,
+
+    /// The output format.
+    #[arg(long, value_enum)]
+    to: Target,
+
+    /// The contig naming style.
+    /// The contigs without a name in the style keep their main name.
+    #[arg(long, value_enum, default_value = "main")]
+    name_style: Style,
+}
+
+/// Write the build to the standard output.
+pub fn run(args: Args) -> Result<ExitCode, Box<dyn Error>> {
+    let build = args.source.load()?;
+    let style = NameStyle::from(args.name_style);
+
+    let missing = build
+        .contigs()
+        .filter(|contig| contig.name_in(style).is_none())
+        .count();
+    if missing > 0 {
+        eprintln!("{missing} contig(s) have no {style} name, using the main name");
+    }
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    match args.to {
+        Target::ChromSizes => write_chrom_sizes(&build, style, &mut out)?,
+        Target::Dict => write_dict(&build, style, &mut out)?,
+        Target::FaiStub => write_fai_stub(&build, style, &mut out)?,
+        Target::VcfHeader => write_vcf_header(&build, style, &mut out)?,
+        Target::Json => write_json(&build, style, &mut out)?,
+        Target::AssemblyReport => write_assembly_report(&build, style, &mut out)?,
+    }
+    out.flush()?;
+
+    Ok(ExitCode::SUCCESS)
+}
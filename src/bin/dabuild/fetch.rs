@@ -0,0 +1,82 @@
+//! Download an assembly report from NCBI into a local cache.
+
+use std::{
+    error::Error,
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
+    process::ExitCode,
+    time::Duration,
+};
+
+use dabuild::fetch::{BuildCache, DownloadObserver, Downloader};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The GenBank or RefSeq assembly accession (e.g. `GCF_000001405.40`).
+    accession: String,
+
+    /// The cache directory [default: `$XDG_CACHE_HOME/dabuild` or `~/.cache/dabuild`].
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+
+    /// Download the report even if it is in the cache.
+    #[arg(long, conflicts_with = "offline")]
+    force: bool,
+
+    /// Never download, fail if the report is not in the cache.
+    #[arg(long)]
+    offline: bool,
+
+    /// The number of retries of a failed download.
+    #[arg(long, default_value_t = 3)]
+    retries: u32,
+}
+
+/// Reports the download progress on the standard error.
+struct Progress;
+
+impl DownloadObserver for Progress {
+    fn on_progress(&self, url: &str, downloaded: u64, total: Option<u64>) {
+        let name = url.rsplit('/').find(|part| !part.is_empty()).unwrap_or(url);
+        let mut stderr = io::stderr().lock();
+        let _ = match total {
+            Some(total) => write!(stderr, "\r{name}: {downloaded}/{total} bytes"),
+            None => write!(stderr, "\r{name}: {downloaded} bytes"),
+        };
+        if total == Some(downloaded) {
+            let _ = writeln!(stderr);
+        }
+    }
+
+    fn on_retry(&self, url: &str, retry: u32, delay: Duration, error: &dyn Error) {
+        eprintln!(
+            "\nDownload of {url} failed: {error}, retry {retry} in {}s",
+            delay.as_secs_f32()
+        );
+    }
+}
+
+/// Print the path of the cached report, downloading the report if it is not in the cache.
+pub fn run(args: Args) -> Result<ExitCode, Box<dyn Error>> {
+    let cache_dir = args
+        .cache_dir
+        .or_else(BuildCache::default_dir)
+        .ok_or("Cannot find the cache directory, use `--cache-dir`")?;
+    let mut downloader = Downloader::new().retries(args.retries);
+    if io::stderr().is_terminal() {
+        downloader = downloader.observer(Progress);
+    }
+    let cache = BuildCache::new(cache_dir)
+        .offline(args.offline)
+        .downloader(downloader.clone());
+
+    let path = if args.force {
+        let report = downloader.download_assembly_report(&args.accession)?;
+        cache.store(&args.accession, &report)?
+    } else {
+        cache.fetch(&args.accession)?
+    };
+
+    println!("{}", path.display());
+    Ok(ExitCode::SUCCESS)
+}
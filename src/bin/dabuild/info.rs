@@ -0,0 +1,37 @@
+//! Summarize a genome build.
+
+use std::{error::Error, process::ExitCode};
+
+use dabuild::format_bp;
+
+use crate::source::SourceArgs;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    source: SourceArgs,
+}
+
+pub fn run(args: Args) -> Result<ExitCode, Box<dyn Error>> {
+    let build = args.source.load()?;
+
+    let total: u64 = build.contigs().map(|contig| contig.length()).sum();
+    let roles = build.counts_by_role();
+
+    println!("{:<16}{}", "Build", build.id());
+    println!("{:<16}{}", "Contigs", build.contigs().count());
+    println!("{:<16}{total} bp ({})", "Total length", format_bp(total));
+    println!("Roles");
+    for (role, count) in roles
+        .iter()
+        .filter_map(|(role, count)| role.map(|role| (role, count)))
+    {
+        println!("  {:<24}{count}", role.as_str());
+    }
+    // The contigs with unknown role go last.
+    if let Some(count) = roles.get(&None) {
+        println!("  {:<24}{count}", "unknown");
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,40 @@
+//! Resolve contig names against a genome build.
+
+use std::{error::Error, process::ExitCode};
+
+use crate::source::SourceArgs;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    source: SourceArgs,
+
+    /// The contig names of any style (e.g. `1`, `chr1`, `NC_000001.11`).
+    #[arg(required = true)]
+    names: Vec<String>,
+}
+
+/// Print the query, the main name, the alternative names, and the length of each contig.
+///
+/// Fails if any of the names is unknown.
+pub fn run(args: Args) -> Result<ExitCode, Box<dyn Error>> {
+    let build = args.source.load()?;
+
+    let mut code = ExitCode::SUCCESS;
+    for name in &args.names {
+        match build.contig_by_name(name) {
+            Some(contig) => println!(
+                "{name}\t{}\t{}\t{}",
+                contig.name(),
+                contig.alt_names().collect::<Vec<_>>().join(","),
+                contig.length()
+            ),
+            None => {
+                eprintln!("Unknown contig {name:?}");
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    Ok(code)
+}
@@ -0,0 +1,79 @@
+//! The `dabuild` command-line tool for inspecting genome build metadata.
+//!
+//! Build with the `cli` feature:
+//!
+//! ```shell
+//! cargo install dabuild --features cli
+//! ```
+
+use std::{error::Error, process::ExitCode};
+
+use clap::{Parser, Subcommand};
+
+mod diff;
+mod digest;
+mod export;
+mod fetch;
+mod info;
+mod lookup;
+mod rename;
+mod sniff;
+mod source;
+mod validate;
+mod windows;
+
+#[derive(Parser)]
+#[command(name = "dabuild", version, about = "Inspect genome build metadata")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compare the contigs of two genome builds.
+    Diff(diff::Args),
+    /// Print the digests of a genome build.
+    Digest(digest::Args),
+    /// Write a genome build in another format.
+    Export(export::Args),
+    /// Download an assembly report from NCBI into a local cache.
+    Fetch(fetch::Args),
+    /// Summarize a genome build.
+    Info(info::Args),
+    /// Resolve contig names against a genome build.
+    Lookup(lookup::Args),
+    /// Rewrite the contig names of BED, VCF, SAM, or GTF records.
+    Rename(rename::Args),
+    /// Identify the bundled build that matches the contigs of a file header.
+    Sniff(sniff::Args),
+    /// Check BED or VCF records against a genome build.
+    Validate(validate::Args),
+    /// Tile a genome build with windows.
+    Windows(windows::Args),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result: Result<ExitCode, Box<dyn Error>> = match cli.command {
+        Command::Diff(args) => diff::run(args),
+        Command::Digest(args) => digest::run(args),
+        Command::Export(args) => export::run(args),
+        Command::Fetch(args) => fetch::run(args),
+        Command::Info(args) => info::run(args),
+        Command::Lookup(args) => lookup::run(args),
+        Command::Rename(args) => rename::run(args),
+        Command::Sniff(args) => sniff::run(args),
+        Command::Validate(args) => validate::run(args),
+        Command::Windows(args) => windows::run(args),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Rewrite the contig names of BED, VCF, SAM, or GTF records.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::ValueEnum;
+use dabuild::{GenomeBuild, NameStyle};
+
+use crate::{export::Style, source::SourceArgs};
+
+/// The format of the records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RecordFormat {
+    /// BED intervals.
+    Bed,
+    /// VCF header and records.
+    Vcf,
+    /// SAM header and alignments.
+    Sam,
+    /// GTF or GFF features.
+    Gtf,
+}
+
+impl RecordFormat {
+    /// Guess the format from the file extension.
+    fn guess(path: &Path) -> Option<RecordFormat> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("bed") => Some(RecordFormat::Bed),
+            Some("vcf") => Some(RecordFormat::Vcf),
+            Some("sam") => Some(RecordFormat::Sam),
+            Some("gtf" | "gff" | "gff3") => Some(RecordFormat::Gtf),
+            _ => None,
+        }
+    }
+}
+
+/// The handling of the contigs that are not in the build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OnUnknown {
+    /// Stop with an error.
+    Error,
+    /// Keep the name and report it to the standard error.
+    Warn,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    source: SourceArgs,
+
+    /// The target contig naming style.
+    #[arg(long, value_enum)]
+    to: Style,
+
+    /// The records to rename [default: the standard input].
+    input: Option<PathBuf>,
+
+    /// The format of the records [default: guessed from the file extension].
+    #[arg(long = "records", value_enum)]
+    record_format: Option<RecordFormat>,
+
+    /// The handling of the contigs that are not in the build.
+    #[arg(long, value_enum, default_value = "error")]
+    on_unknown: OnUnknown,
+}
+
+/// Resolve the contig names of any style to the names of the target style.
+struct Renamer<'a> {
+    build: &'a GenomeBuild<u64>,
+    style: NameStyle,
+    on_unknown: OnUnknown,
+    /// The names that were resolved so far, or `None` for the unknown names.
+    cache: HashMap<String, Option<String>>,
+}
+
+impl Renamer<'_> {
+    fn rename<'n>(&'n mut self, name: &'n str) -> Result<&'n str, String> {
+        if !self.cache.contains_key(name) {
+            let renamed = self.build.contig_by_name(name).map(|contig| {
+                contig
+                    .name_in(self.style)
+                    .unwrap_or(contig.name())
+                    .to_string()
+            });
+            if renamed.is_none() {
+                match self.on_unknown {
+                    OnUnknown::Error => return Err(format!("Unknown contig {name:?}")),
+                    OnUnknown::Warn => eprintln!("Unknown contig {name:?}, keeping the name"),
+                }
+            }
+            self.cache.insert(name.to_string(), renamed);
+        }
+
+        Ok(self.cache[name].as_deref().unwrap_or(name))
+    }
+
+    /// Rename the tab-separated fields of the `line` at the `columns`.
+    fn rename_columns(&mut self, line: &str, columns: &[usize]) -> Result<String, String> {
+        let mut fields: Vec<String> = line.split('\t').map(String::from).collect();
+        for &i in columns {
+            if let Some(field) = fields.get_mut(i) {
+                // `=` and `*` are the placeholders of SAM mate reference and of unmapped reads.
+                if field != "=" && field != "*" {
+                    *field = self.rename(field)?.to_string();
+                }
+            }
+        }
+        Ok(fields.join("\t"))
+    }
+
+    fn rename_line(&mut self, line: &str, format: RecordFormat) -> Result<String, String> {
+        match format {
+            RecordFormat::Bed
+                if line.starts_with('#')
+                    || line.starts_with("track")
+                    || line.starts_with("browser") =>
+            {
+                Ok(line.to_string())
+            }
+            RecordFormat::Gtf if line.starts_with('#') => Ok(line.to_string()),
+            RecordFormat::Vcf if line.starts_with("##contig=<ID=") => {
+                let rest = &line["##contig=<ID=".len()..];
+                let end = rest.find([',', '>']).unwrap_or(rest.len());
+                Ok(format!(
+                    "##contig=<ID={}{}",
+                    self.rename(&rest[..end])?,
+                    &rest[end..]
+                ))
+            }
+            RecordFormat::Vcf if line.starts_with('#') => Ok(line.to_string()),
+            RecordFormat::Sam if line.starts_with("@SQ\t") => {
+                let fields: Result<Vec<_>, _> = line
+                    .split('\t')
+                    .map(|field| match field.strip_prefix("SN:") {
+                        Some(name) => self.rename(name).map(|name| format!("SN:{name}")),
+                        None => Ok(field.to_string()),
+                    })
+                    .collect();
+                Ok(fields?.join("\t"))
+            }
+            RecordFormat::Sam if line.starts_with('@') => Ok(line.to_string()),
+            RecordFormat::Sam => self.rename_columns(line, &[2, 6]),
+            RecordFormat::Bed | RecordFormat::Vcf | RecordFormat::Gtf => {
+                self.rename_columns(line, &[0])
+            }
+        }
+    }
+}
+
+/// Open the records of the `input` file or of the standard input if `None`,
+/// guessing the format from the file extension if `None`.
+pub fn open_records(
+    input: Option<&Path>,
+    format: Option<RecordFormat>,
+) -> Result<(RecordFormat, Box<dyn BufRead>), Box<dyn Error>> {
+    let format = format
+        .or_else(|| input.and_then(RecordFormat::guess))
+        .ok_or("Cannot guess the record format, use `--records`")?;
+    let read: Box<dyn BufRead> = match input {
+        Some(path) => Box::new(BufReader::new(
+            File::open(path).map_err(|e| format!("Cannot open {}: {e}", path.display()))?,
+        )),
+        None => Box::new(io::stdin().lock()),
+    };
+    Ok((format, read))
+}
+
+/// Write the renamed records to the standard output.
+///
+/// Fails on the first unknown contig, unless the unknown contigs are allowed.
+pub fn run(args: Args) -> Result<ExitCode, Box<dyn Error>> {
+    let build = args.source.load()?;
+
+    let (format, read) = open_records(args.input.as_deref(), args.record_format)?;
+
+    let mut renamer = Renamer {
+        build: &build,
+        style: NameStyle::from(args.to),
+        on_unknown: args.on_unknown,
+        cache: HashMap::new(),
+    };
+    let mut out = BufWriter::new(io::stdout().lock());
+    for line in read.lines() {
+        let line = line?;
+        if line.is_empty() {
+            writeln!(out)?;
+            continue;
+        }
+        writeln!(out, "{}", renamer.rename_line(&line, format)?)?;
+    }
+    out.flush()?;
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,95 @@
+//! Identify the bundled build that matches the contigs of a file header.
+
+use std::{error::Error, path::PathBuf, process::ExitCode};
+
+use dabuild::{builds::KnownBuild, GenomeBuild};
+
+use crate::source::{load_file, Format};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// A BAM, VCF, `.dict`, `.fai`, or `chrom.sizes` file.
+    path: PathBuf,
+
+    /// The format of the file [default: guessed from the file extension].
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+}
+
+/// The agreement between the contigs of a file and a bundled build.
+struct Agreement {
+    known: KnownBuild,
+    /// The contigs with a known name and the same length.
+    matched: usize,
+    /// The contigs with a known name and a different length.
+    mismatched: usize,
+    /// The contigs with an unknown name.
+    unknown: usize,
+}
+
+impl Agreement {
+    fn new(known: KnownBuild, query: &GenomeBuild<u64>) -> Agreement {
+        let build: GenomeBuild<u64> = known.load();
+        let mut agreement = Agreement {
+            known,
+            matched: 0,
+            mismatched: 0,
+            unknown: 0,
+        };
+        for contig in query.contigs() {
+            match build.contig_by_name(contig.name()) {
+                Some(c) if c.length() == contig.length() => agreement.matched += 1,
+                Some(_) => agreement.mismatched += 1,
+                None => agreement.unknown += 1,
+            }
+        }
+        agreement
+    }
+
+    /// Get the percentage of the matched contigs.
+    fn score(&self) -> f64 {
+        let total = self.matched + self.mismatched + self.unknown;
+        100. * self.matched as f64 / total as f64
+    }
+}
+
+/// Print the agreement of the file contigs with each bundled build, the best match first.
+///
+/// Fails if no bundled build matches any contig.
+pub fn run(args: Args) -> Result<ExitCode, Box<dyn Error>> {
+    let query = load_file(&args.path, args.format)?;
+
+    let mut agreements: Vec<_> = KnownBuild::ALL
+        .into_iter()
+        .map(|known| Agreement::new(known, &query))
+        .collect();
+    agreements.sort_by(|l, r| r.score().total_cmp(&l.score()));
+
+    println!("{:<16}{}", "File", args.path.display());
+    println!("{:<16}{}", "Contigs", query.contigs().count());
+    println!(
+        "{:<16}{:>8}{:>12}{:>12}{:>8}",
+        "Build", "Matched", "Mismatched", "Unknown", "Score"
+    );
+    for a in &agreements {
+        println!(
+            "{:<16}{:>8}{:>12}{:>12}{:>7.1}%",
+            a.known.name(),
+            a.matched,
+            a.mismatched,
+            a.unknown,
+            a.score()
+        );
+    }
+
+    match agreements.first().filter(|a| a.matched > 0) {
+        Some(best) => {
+            println!("{:<16}{}", "Best match", best.known.name());
+            Ok(ExitCode::SUCCESS)
+        }
+        None => {
+            eprintln!("No bundled build matches the contigs");
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Load the genome build from a bundled build or from a file.
+
+use std::{error::Error, fs::File, io::BufReader, path::Path, str::FromStr};
+
+use clap::ValueEnum;
+use dabuild::{
+    builds::{parse_assembly_report, KnownBuild},
+    formats::{parse_bam_header, parse_chrom_sizes, parse_dict, parse_fai, parse_vcf_header},
+    GenomeBuild, GenomeBuildIdentifier,
+};
+
+/// The format of a genome build file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Genome Reference Consortium assembly report.
+    AssemblyReport,
+    /// FASTA index.
+    Fai,
+    /// Sequence dictionary or SAM header.
+    Dict,
+    /// Tab-separated contig names and lengths.
+    ChromSizes,
+    /// VCF header, optionally compressed.
+    Vcf,
+    /// BAM header.
+    Bam,
+}
+
+impl Format {
+    /// Guess the format from the file extension, using the assembly report by default.
+    pub fn guess(path: &Path) -> Format {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let name = name.strip_suffix(".gz").unwrap_or(name);
+        match name.rsplit_once('.').map(|(_, ext)| ext) {
+            Some("fai") => Format::Fai,
+            Some("dict" | "sam") => Format::Dict,
+            Some("sizes") => Format::ChromSizes,
+            Some("vcf") => Format::Vcf,
+            Some("bam") => Format::Bam,
+            _ => Format::AssemblyReport,
+        }
+    }
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SourceArgs {
+    /// A bundled build (e.g. `GRCh38.p13`) or a path to an assembly report,
+    /// `.fai`, `.dict`, `chrom.sizes`, VCF, or BAM file.
+    #[arg(short, long)]
+    pub build: String,
+
+    /// The format of the build file [default: guessed from the file extension].
+    #[arg(long, value_enum)]
+    pub format: Option<Format>,
+}
+
+impl SourceArgs {
+    pub fn load(&self) -> Result<GenomeBuild<u64>, Box<dyn Error>> {
+        load(&self.build, self.format)
+    }
+}
+
+/// Load a bundled `build` or the build from a file in the `format`.
+pub fn load(build: &str, format: Option<Format>) -> Result<GenomeBuild<u64>, Box<dyn Error>> {
+    if let Ok(known) = KnownBuild::from_str(build) {
+        return Ok(known.load());
+    }
+
+    load_file(Path::new(build), format)
+}
+
+/// Load the build from a file in the `format`, guessing the format if `None`.
+///
+/// The file name is used as the build identifier.
+/// Gzip and BGZF-compressed files are decompressed by the parsers, regardless of the name.
+pub fn load_file(path: &Path, format: Option<Format>) -> Result<GenomeBuild<u64>, Box<dyn Error>> {
+    let id = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let id = GenomeBuildIdentifier::from_str(id)?;
+    let file = File::open(path).map_err(|e| format!("Cannot open {}: {e}", path.display()))?;
+
+    let read = BufReader::new(file);
+
+    match format.unwrap_or_else(|| Format::guess(path)) {
+        Format::AssemblyReport => parse_assembly_report(id, read),
+        Format::Fai => parse_fai(id, read),
+        Format::Dict => parse_dict(id, read),
+        Format::ChromSizes => parse_chrom_sizes(id, read),
+        Format::Vcf => parse_vcf_header(id, read),
+        Format::Bam => parse_bam_header(id, read),
+    }
+}
@@ -0,0 +1,148 @@
+//! Check BED or VCF records against a genome build.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    io::{self, BufRead, BufWriter, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use crate::{
+    rename::{open_records, RecordFormat},
+    source::SourceArgs,
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    source: SourceArgs,
+
+    /// The records to validate [default: the standard input].
+    input: Option<PathBuf>,
+
+    /// The format of the records [default: guessed from the file extension].
+    #[arg(long = "records", value_enum)]
+    record_format: Option<RecordFormat>,
+}
+
+/// A record that violates the build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Violation {
+    /// The record cannot be parsed.
+    InvalidRecord,
+    /// The contig is not in the build.
+    UnknownContig,
+    /// The record extends beyond the contig.
+    OutOfBounds,
+    /// The record precedes the previous record in the build order.
+    Unsorted,
+}
+
+impl Violation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Violation::InvalidRecord => "invalid-record",
+            Violation::UnknownContig => "unknown-contig",
+            Violation::OutOfBounds => "out-of-bounds",
+            Violation::Unsorted => "unsorted",
+        }
+    }
+}
+
+/// Get the contig name and the 0-based half-open coordinates of a record,
+/// or `None` if the record cannot be parsed.
+fn parse_record(line: &str, format: RecordFormat) -> Option<(&str, u64, u64)> {
+    let fields: Vec<_> = line.split('\t').collect();
+    match format {
+        RecordFormat::Bed => {
+            let start = fields.get(1)?.parse().ok()?;
+            let end = fields.get(2)?.parse().ok()?;
+            (start <= end).then_some((fields[0], start, end))
+        }
+        RecordFormat::Vcf => {
+            let pos: u64 = fields.get(1)?.parse().ok()?;
+            let reference = fields.get(3)?;
+            // Position `0` denotes a telomere.
+            let start = pos.saturating_sub(1);
+            Some((fields[0], start, start + reference.len() as u64))
+        }
+        RecordFormat::Sam | RecordFormat::Gtf => None,
+    }
+}
+
+/// Print a tab-separated report with one line per violation.
+///
+/// Fails if any record violates the build.
+pub fn run(args: Args) -> Result<ExitCode, Box<dyn Error>> {
+    let build = args.source.load()?;
+
+    let (format, read) = open_records(args.input.as_deref(), args.record_format)?;
+    if matches!(format, RecordFormat::Sam | RecordFormat::Gtf) {
+        return Err("Only BED and VCF records can be validated".into());
+    }
+
+    // The build order and the length of the contigs by all their names.
+    let contigs: HashMap<&str, (usize, u64)> = build
+        .contigs()
+        .enumerate()
+        .flat_map(|(i, contig)| {
+            std::iter::once(contig.name())
+                .chain(contig.alt_names())
+                .map(move |name| (name, (i, *contig.length())))
+        })
+        .collect();
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    writeln!(out, "#line\tviolation\tcontig\tdetail")?;
+    let (mut records, mut violations) = (0usize, 0usize);
+    let mut previous: Option<(usize, u64)> = None;
+    for (i, line) in read.lines().enumerate() {
+        let line = line?;
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("track")
+            || line.starts_with("browser")
+        {
+            continue;
+        }
+        records += 1;
+
+        let mut report = |violation: Violation, contig: &str, detail: String| {
+            violations += 1;
+            writeln!(out, "{}\t{}\t{contig}\t{detail}", i + 1, violation.as_str())
+        };
+
+        let Some((name, start, end)) = parse_record(&line, format) else {
+            report(Violation::InvalidRecord, ".", line.clone())?;
+            continue;
+        };
+        let Some(&(index, length)) = contigs.get(name) else {
+            report(Violation::UnknownContig, name, String::new())?;
+            continue;
+        };
+        if end > length {
+            report(
+                Violation::OutOfBounds,
+                name,
+                format!("end {end} exceeds the contig length {length}"),
+            )?;
+        }
+        if previous.is_some_and(|previous| (index, start) < previous) {
+            report(
+                Violation::Unsorted,
+                name,
+                format!("start {start} precedes the previous record"),
+            )?;
+        }
+        previous = Some((index, start));
+    }
+    out.flush()?;
+
+    eprintln!("{violations} violation(s) in {records} record(s)");
+    Ok(if violations == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
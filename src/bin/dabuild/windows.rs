@@ -0,0 +1,63 @@
+//! Tile a genome build with windows.
+
+use std::{
+    error::Error,
+    io::{self, BufWriter, Write},
+    process::ExitCode,
+};
+
+use dabuild::{NameStyle, SequenceRole};
+
+use crate::{export::Style, source::SourceArgs};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    source: SourceArgs,
+
+    /// The window size in bases.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    size: u64,
+
+    /// The distance between the window starts in bases [default: the window size].
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    step: Option<u64>,
+
+    /// Only tile the assembled molecules (e.g. `1`, `X`, `MT`).
+    #[arg(long)]
+    primary_only: bool,
+
+    /// The contig naming style.
+    /// The contigs without a name in the style keep their main name.
+    #[arg(long, value_enum, default_value = "main")]
+    name_style: Style,
+}
+
+/// Write the windows as BED records to the standard output.
+pub fn run(args: Args) -> Result<ExitCode, Box<dyn Error>> {
+    let mut build = args.source.load()?;
+    if args.primary_only {
+        build = build.retain(|contig| contig.role() == Some(SequenceRole::AssembledMolecule));
+        if build.contigs().next().is_none() {
+            return Err(
+                "No assembled molecules in the build, are the sequence roles known?".into(),
+            );
+        }
+    }
+    let style = NameStyle::from(args.name_style);
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    for window in build.windows(args.size, args.step.unwrap_or(args.size)) {
+        let contig = window.contig();
+        writeln!(
+            out,
+            "{}\t{}\t{}",
+            contig.name_in(style).unwrap_or(contig.name()),
+            window.start(),
+            window.end()
+        )?;
+    }
+    out.flush()?;
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,159 @@
+//! # Genome browser links
+//!
+//! The module includes [`Browser`], the genome browsers that show a region of a well-known
+//! assembly, see [`GenomicRegion::browser_url`].
+
+use alloc::{format, string::String};
+use core::{fmt::Display, ops::Add, str::FromStr};
+
+use num_traits::One;
+
+use crate::{karyotype::strip_chr, GenomeBuildIdentifier, GenomicRegion, NameStyle};
+
+/// A genome browser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Browser {
+    /// The UCSC Genome Browser, with the UCSC-style names, e.g. `chr1`.
+    Ucsc,
+    /// The Ensembl genome browser, with the Ensembl names, e.g. `1` or `MT`.
+    Ensembl,
+    /// The IGV web app, with the UCSC-style names, e.g. `chr1`.
+    Igv,
+}
+
+impl Browser {
+    /// All browsers.
+    pub const ALL: [Browser; 3] = [Browser::Ucsc, Browser::Ensembl, Browser::Igv];
+
+    /// Get the browser name, e.g. `ucsc`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Browser::Ucsc => "ucsc",
+            Browser::Ensembl => "ensembl",
+            Browser::Igv => "igv",
+        }
+    }
+}
+
+/// Parse the browser name, e.g. `ucsc`, ignoring the case.
+impl FromStr for Browser {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Browser::ALL
+            .into_iter()
+            .find(|browser| browser.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown browser {s:?}"))
+    }
+}
+
+impl core::fmt::Display for Browser {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The major assemblies shown by the browsers: the NCBI name, the UCSC database,
+/// the Ensembl site, and the Ensembl species.
+const ASSEMBLIES: &[(&str, &str, &str, &str)] = &[
+    ("GRCh38", "hg38", "https://www.ensembl.org", "Homo_sapiens"),
+    (
+        "GRCh37",
+        "hg19",
+        "https://grch37.ensembl.org",
+        "Homo_sapiens",
+    ),
+    ("GRCm39", "mm39", "https://www.ensembl.org", "Mus_musculus"),
+    (
+        "GRCm38",
+        "mm10",
+        "https://nov2020.archive.ensembl.org",
+        "Mus_musculus",
+    ),
+];
+
+impl<C> GenomicRegion<C> {
+    /// Get the URL of the `browser` showing the region of the assembly `id`.
+    ///
+    /// The assembly is recognized by its NCBI name (e.g. `GRCh38`) or by its UCSC database
+    /// (e.g. `hg38`), and the contig is named in the style of the browser.
+    /// The URL includes the 1-based closed coordinates of the region.
+    ///
+    /// Returns `None` if the browser does not show the assembly.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{Browser, GenomeBuild, GenomicRegion};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let mt = build.contig_by_name("MT").expect("Known contig");
+    /// let region = GenomicRegion::new(mt, 1_000, 2_000).expect("Valid region");
+    ///
+    /// assert_eq!(
+    ///     region.browser_url(Browser::Ucsc, build.id()).as_deref(),
+    ///     Some("https://genome.ucsc.edu/cgi-bin/hgTracks?db=hg38&position=chrM:1001-2000"),
+    /// );
+    /// assert_eq!(
+    ///     region.browser_url(Browser::Ensembl, build.id()).as_deref(),
+    ///     Some("https://www.ensembl.org/Homo_sapiens/Location/View?r=MT:1001-2000"),
+    /// );
+    /// assert_eq!(
+    ///     region.browser_url(Browser::Igv, build.id()).as_deref(),
+    ///     Some("https://igv.org/app/?genome=hg38&locus=chrM:1001-2000"),
+    /// );
+    /// ```
+    pub fn browser_url(&self, browser: Browser, id: &GenomeBuildIdentifier) -> Option<String>
+    where
+        C: Copy + PartialOrd + One + Add<Output = C> + Display,
+    {
+        let &(_, ucsc, ensembl, species) = ASSEMBLIES.iter().find(|(ncbi, ucsc, _, _)| {
+            id.major_assembly().eq_ignore_ascii_case(ncbi)
+                || id.major_assembly().eq_ignore_ascii_case(ucsc)
+        })?;
+
+        let contig = self.contig();
+        // Show a base for an empty region.
+        let start = *self.start() + C::one();
+        let end = if *self.end() < start {
+            start
+        } else {
+            *self.end()
+        };
+        Some(match browser {
+            Browser::Ucsc => format!(
+                "https://genome.ucsc.edu/cgi-bin/hgTracks?db={ucsc}&position={}:{start}-{end}",
+                encoded(contig.name_in(NameStyle::Ucsc).unwrap_or(contig.name()))
+            ),
+            Browser::Ensembl => {
+                let name = match strip_chr(contig.name()) {
+                    "M" => "MT",
+                    name => name,
+                };
+                format!(
+                    "{ensembl}/{species}/Location/View?r={}:{start}-{end}",
+                    encoded(name)
+                )
+            }
+            Browser::Igv => format!(
+                "https://igv.org/app/?genome={ucsc}&locus={}:{start}-{end}",
+                encoded(contig.name_in(NameStyle::Ucsc).unwrap_or(contig.name()))
+            ),
+        })
+    }
+}
+
+/// Percent-encode the characters of the contig `name` that are not allowed in a URL query,
+/// e.g. `*` and `:` of `HLA-A*01:01:01:01`.
+fn encoded(name: &str) -> String {
+    let mut encoded = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
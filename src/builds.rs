@@ -9,6 +9,11 @@
 //! * *GRCh37.p13*: [`get_grch37_p13`]
 //! * *GRCh38.p13*: [`get_grch38_p13`]
 //!
+//! or chosen at runtime using [`KnownBuild`].
+//! A small synthetic build for unit tests is available from [`toy`].
+//! With the `std` feature, [`cached`] loads each bundled build once per process
+//! and shares it between the callers.
+//!
 //! ### Example
 //!
 //! Load *GRCh38.p13* (*Homo sapiens*):
@@ -20,6 +25,13 @@
 //! let build: GenomeBuild<u32> = get_grch38_p13();
 //! ```
 //!
+//! ### Contig order
+//!
+//! The contigs of the bundled builds are sorted by the main name,
+//! see [`GenomeBuild::order`]. The order is guaranteed to be stable across the crate versions,
+//! hence it can be used to sort the outputs reproducibly.
+//! A change of the order is considered a breaking change.
+//!
 //! ## Load from an assembly report
 //!
 //! A genome build can be loaded from the Genome Reference Consortium assembly report
@@ -42,18 +54,241 @@
 //! assert_eq!(build.id().major_assembly(), "GRCm39");
 //! ```
 //!
+//! Use [`parse_assembly_report_bytes`] to load an assembly report that is already in memory,
+//! e.g. in environments without file access, such as WebAssembly or `no_std`.
+//!
+//! Use [`ParseOptions`] to keep only the contigs of some sequence roles or of a minimum length,
+//! e.g. the assembled molecules, without allocating the other contigs.
+//! Use [`parse_assembly_report_records`] to scan the contig lines of an in-memory report
+//! without allocating the contigs at all.
+//!
+//! ## Download from NCBI
+//!
+//! With the `fetch` feature, `fetch_assembly_report` downloads the assembly report
+//! of a GenBank or RefSeq accession (e.g. `GCF_000001405.40`) and parses it into a build,
+//! and `list_patches` lists the available releases of an assembly (e.g. `GRCh38`).
+//! Use `fetch_by_ucsc_name` to download the assembly of a UCSC database (e.g. `hg38`).
+//!
+//! ## Async
+//!
+//! With the `tokio` feature, `parse_assembly_report_async` (or `parse_assembly_report_async_with`
+//! with [`ParseOptions`]) reads the report from an async reader, and `fetch_assembly_report_async` (along with the `fetch` feature)
+//! downloads the report on the blocking thread pool of the runtime.
 
-use std::{error::Error, io::BufRead, str::FromStr};
+use alloc::{
+    borrow::Cow, boxed::Box, collections::BTreeSet, format, string::String, sync::Arc, vec,
+    vec::Vec,
+};
+use core::{error::Error, hash::Hash, str::FromStr};
+#[cfg(feature = "std")]
+use std::{
+    fmt::Display,
+    io::{self, BufRead, Write},
+};
 
 use num_traits::Zero;
+use smallvec::SmallVec;
 
-use super::{Contig, GenomeBuild, GenomeBuildIdentifier};
+use super::{
+    BuildMetadata, Contig, GenomeBuild, GenomeBuildIdentifier, SequenceRole, SharedGenomeBuild,
+};
+#[cfg(feature = "std")]
+use crate::{formats::styled_name, NameStyle};
+use crate::{karyotype::strip_chr, phf::PerfectIndex};
 
 #[allow(non_upper_case_globals)]
 const GRCh37_p13: &[u8] = include_bytes!("data/GCF_000001405.25_GRCh37.p13_assembly_report.tsv");
 #[allow(non_upper_case_globals)]
 const GRCh38_p13: &[u8] = include_bytes!("data/GCF_000001405.39_GRCh38.p13_assembly_report.tsv");
 
+/// The first and the last GenBank accession of the patches of *GRCh38*
+/// introduced by the patch releases `p1` to `p13`.
+const GRCH38_PATCH_RELEASES: [(&str, &str); 13] = [
+    ("KN196472.1", "KN196487.1"),
+    ("KN538360.1", "KN538374.1"),
+    ("KQ031383.1", "KQ031390.1"),
+    ("KQ090013.1", "KQ090028.1"),
+    ("KQ458382.1", "KQ458388.1"),
+    ("KQ759759.1", "KQ759762.1"),
+    ("KQ983255.1", "KQ983258.1"),
+    ("KV575243.1", "KV575260.1"),
+    ("KV766192.1", "KV766199.1"),
+    ("KV880763.1", "KV880768.1"),
+    ("KZ208904.1", "KZ208924.1"),
+    ("KZ559100.1", "KZ559116.1"),
+    ("ML143341.1", "ML143385.1"),
+];
+
+/// Get the number of the patch release of *GRCh38* that introduced the `contig`,
+/// `0` for the contigs of the initial release, or `None` for an unknown patch.
+fn grch38_introduced_in<C>(contig: &Contig<C>) -> Option<u8> {
+    match contig.role() {
+        Some(SequenceRole::FixPatch | SequenceRole::NovelPatch) => {
+            contig.names().find_map(|name| {
+                (1..)
+                    .zip(GRCH38_PATCH_RELEASES)
+                    .find(|&(_, (first, last))| {
+                        name.len() == first.len() && first <= name && name <= last
+                    })
+                    .map(|(patch, _)| patch)
+            })
+        }
+        _ => Some(0),
+    }
+}
+
+/// The genome builds bundled with the crate.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::GenomeBuild;
+/// use dabuild::builds::KnownBuild;
+///
+/// let known: KnownBuild = "GRCh38.p13".parse().expect("Bundled build");
+/// let build: GenomeBuild<u32> = known.load();
+///
+/// assert_eq!(build.id().major_assembly(), "GRCh38");
+///
+/// // The bundled builds can also be found by either assembly accession.
+/// assert_eq!(KnownBuild::from_accession("GCA_000001405.28"), Some(known));
+/// assert_eq!(KnownBuild::from_accession("GCF_000001405.39"), Some(known));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KnownBuild {
+    /// *GRCh37.p13* (*Homo sapiens*).
+    GRCh37p13,
+    /// *GRCh38.p13* (*Homo sapiens*).
+    GRCh38p13,
+}
+
+impl KnownBuild {
+    /// All bundled genome builds.
+    pub const ALL: [KnownBuild; 2] = [KnownBuild::GRCh37p13, KnownBuild::GRCh38p13];
+
+    /// Get the build name, e.g. `GRCh38.p13`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            KnownBuild::GRCh37p13 => "GRCh37.p13",
+            KnownBuild::GRCh38p13 => "GRCh38.p13",
+        }
+    }
+
+    /// Get the GenBank assembly accession, e.g. `GCA_000001405.28`.
+    pub fn genbank_accession(&self) -> &'static str {
+        match self {
+            KnownBuild::GRCh37p13 => "GCA_000001405.14",
+            KnownBuild::GRCh38p13 => "GCA_000001405.28",
+        }
+    }
+
+    /// Get the RefSeq assembly accession, e.g. `GCF_000001405.39`.
+    pub fn refseq_accession(&self) -> &'static str {
+        match self {
+            KnownBuild::GRCh37p13 => "GCF_000001405.25",
+            KnownBuild::GRCh38p13 => "GCF_000001405.39",
+        }
+    }
+
+    /// Find the bundled build by its GenBank or RefSeq assembly accession.
+    pub fn from_accession(accession: &str) -> Option<KnownBuild> {
+        KnownBuild::ALL.into_iter().find(|known| {
+            known.genbank_accession() == accession || known.refseq_accession() == accession
+        })
+    }
+
+    /// Get the length of the contig with the GenBank or RefSeq `accession` in the build,
+    /// e.g. `248956422` for `NC_000001.11` in *GRCh38.p13*,
+    /// or `None` if the accession is not in the build.
+    ///
+    /// The assembly report is scanned without loading the build.
+    pub fn accession_length(&self, accession: &str) -> Option<u64> {
+        self.records()
+            .find(|record| {
+                record.genbank.as_deref() == Some(accession)
+                    || record.refseq.as_deref() == Some(accession)
+            })
+            .map(|record| record.length)
+    }
+
+    /// Get the contig records of the bundled assembly report.
+    pub(crate) fn records(&self) -> impl Iterator<Item = ReportRecord<'static, u64>> {
+        let report = match self {
+            KnownBuild::GRCh37p13 => GRCh37_p13,
+            KnownBuild::GRCh38p13 => GRCh38_p13,
+        };
+        let report = core::str::from_utf8(report).expect("Builtin report is UTF-8");
+        parse_assembly_report_records(report).filter_map(Result::ok)
+    }
+
+    /// Load the genome build.
+    ///
+    /// ## Panics
+    ///
+    /// If the builtin assembly report cannot be parsed (should not happen).
+    pub fn load<C>(&self) -> GenomeBuild<C>
+    where
+        C: FromStr + Zero + PartialOrd,
+    {
+        match self {
+            KnownBuild::GRCh37p13 => get_grch37_p13(),
+            KnownBuild::GRCh38p13 => get_grch38_p13(),
+        }
+    }
+
+    /// Load the genome build into a handle that can be shared between threads.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::thread;
+    ///
+    /// use dabuild::SharedGenomeBuild;
+    /// use dabuild::builds::KnownBuild;
+    ///
+    /// let build: SharedGenomeBuild<u32> = KnownBuild::GRCh38p13.load_shared();
+    ///
+    /// let workers: Vec<_> = ["chr1", "chrX"]
+    ///     .into_iter()
+    ///     .map(|name| {
+    ///         let build = build.clone();
+    ///         thread::spawn(move || build.contig_by_name(name).map(|c| *c.length()))
+    ///     })
+    ///     .collect();
+    /// let lengths: Vec<_> = workers.into_iter().map(|w| w.join().unwrap()).collect();
+    ///
+    /// assert_eq!(lengths, [Some(248_956_422), Some(156_040_895)]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// If the builtin assembly report cannot be parsed (should not happen).
+    pub fn load_shared<C>(&self) -> SharedGenomeBuild<C>
+    where
+        C: FromStr + Zero + PartialOrd,
+    {
+        Arc::new(self.load())
+    }
+}
+
+/// Parse the build name, e.g. `GRCh38.p13`, ignoring the case.
+impl FromStr for KnownBuild {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        KnownBuild::ALL
+            .into_iter()
+            .find(|known| known.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown bundled build {s:?}"))
+    }
+}
+
+impl core::fmt::Display for KnownBuild {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 /// Get the *GRCh37.p13* build.
 ///
 /// ## Panics
@@ -64,7 +299,8 @@ where
     C: FromStr + Zero + PartialOrd,
 {
     let id = GenomeBuildIdentifier::from(("GRCh37", "p13"));
-    parse_assembly_report(id, GRCh37_p13).expect("Reading builtin GRCh37.p13 assembly report")
+    load_bundled(id, GRCh37_p13, &bundled::GRCH37_P13, |_| None)
+        .expect("Reading builtin GRCh37.p13 assembly report")
 }
 
 /// Get the *GRCh38.p13* build.
@@ -77,7 +313,158 @@ where
     C: FromStr + Zero + PartialOrd,
 {
     let id = GenomeBuildIdentifier::from(("GRCh38", "p13"));
-    parse_assembly_report(id, GRCh38_p13).expect("Reading builtin GRCh38.p13 assembly report")
+    load_bundled(id, GRCh38_p13, &bundled::GRCH38_P13, grch38_introduced_in)
+        .expect("Reading builtin GRCh38.p13 assembly report")
+}
+
+/// The assembly report of the toy build, see [`toy`].
+const TOY_REPORT: &str = "# Assembly name:  Toy.p1\n\
+# Description:    Synthetic build for unit tests\n\
+# Assembly level: Chromosome\n\
+# Genome representation: full\n\
+# Sequence-Name\tSequence-Role\tAssigned-Molecule\tAssigned-Molecule-Location/Type\tGenBank-Accn\tRelationship\tRefSeq-Accn\tAssembly-Unit\tSequence-Length\tUCSC-style-name\n\
+1\tassembled-molecule\t1\tChromosome\tCM900001.1\t=\tNC_900001.1\tPrimary Assembly\t1000\tchr1\n\
+2\tassembled-molecule\t2\tChromosome\tCM900002.1\t=\tNC_900002.1\tPrimary Assembly\t800\tchr2\n\
+X\tassembled-molecule\tX\tChromosome\tCM900003.1\t=\tNC_900003.1\tPrimary Assembly\t600\tchrX\n\
+Y\tassembled-molecule\tY\tChromosome\tCM900004.1\t=\tNC_900004.1\tPrimary Assembly\t400\tchrY\n\
+MT\tassembled-molecule\tMT\tMitochondrion\tCM900005.1\t=\tNC_900005.1\tnon-nuclear\t165\tchrM\n\
+TOY1_RANDOM_CTG1\tunlocalized-scaffold\t1\tChromosome\tKI900001.1\t=\tNT_900001.1\tPrimary Assembly\t120\tchr1_KI900001v1_random\n\
+TOYUN_RANDOM_CTG1\tunplaced-scaffold\tna\tna\tKI900002.1\t=\tNT_900002.1\tPrimary Assembly\t50\tchrUn_KI900002v1\n\
+TOY2_1_CTG1\talt-scaffold\t2\tChromosome\tKI900003.1\t=\tNT_900003.1\tALT_REF_LOCI_1\t300\tchr2_KI900003v1_alt\n\
+TOY1_FIX_CTG1\tfix-patch\t1\tChromosome\tKN900001.1\t=\tNW_900001.1\tPATCHES\t200\tchr1_KN900001v1_fix\n\
+TOYX_NOVEL_CTG1\tnovel-patch\tX\tChromosome\tKN900002.1\t=\tNW_900002.1\tPATCHES\t250\tchrX_KN900002v1_alt\n";
+
+/// Get a small synthetic build for unit tests.
+///
+/// The build includes a contig of each [`SequenceRole`],
+/// named in each [`NameStyle`](crate::NameStyle), and it loads much faster than the bundled builds.
+/// The contigs are sorted by the main name, as in the bundled builds:
+///
+/// | Name | Role | GenBank | RefSeq | UCSC | Length |
+/// |------|------|---------|--------|------|-------:|
+/// | `1` | assembled-molecule | `CM900001.1` | `NC_900001.1` | `chr1` | 1000 |
+/// | `2` | assembled-molecule | `CM900002.1` | `NC_900002.1` | `chr2` | 800 |
+/// | `MT` | assembled-molecule | `CM900005.1` | `NC_900005.1` | `chrM` | 165 |
+/// | `TOY1_FIX_CTG1` | fix-patch | `KN900001.1` | `NW_900001.1` | `chr1_KN900001v1_fix` | 200 |
+/// | `TOY1_RANDOM_CTG1` | unlocalized-scaffold | `KI900001.1` | `NT_900001.1` | `chr1_KI900001v1_random` | 120 |
+/// | `TOY2_1_CTG1` | alt-scaffold | `KI900003.1` | `NT_900003.1` | `chr2_KI900003v1_alt` | 300 |
+/// | `TOYUN_RANDOM_CTG1` | unplaced-scaffold | `KI900002.1` | `NT_900002.1` | `chrUn_KI900002v1` | 50 |
+/// | `TOYX_NOVEL_CTG1` | novel-patch | `KN900002.1` | `NW_900002.1` | `chrX_KN900002v1_alt` | 250 |
+/// | `X` | assembled-molecule | `CM900003.1` | `NC_900003.1` | `chrX` | 600 |
+/// | `Y` | assembled-molecule | `CM900004.1` | `NC_900004.1` | `chrY` | 400 |
+///
+/// The accessions are made up, hence they do not clash with the accessions of real assemblies.
+/// The build has the identifier *Toy.p1*, the *Chromosome* assembly level,
+/// and the *full* genome representation.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::{GenomeBuild, SequenceRole};
+/// use dabuild::builds::toy;
+///
+/// let build: GenomeBuild<u32> = toy();
+///
+/// assert_eq!(build.contigs().count(), 10);
+/// assert_eq!(build.contig_by_name("NC_900001.1").map(|c| c.name()), Some("1"));
+/// assert_eq!(build.counts_by_role()[&Some(SequenceRole::NovelPatch)], 1);
+/// ```
+///
+/// ## Panics
+///
+/// If the builtin assembly report cannot be parsed (should not happen).
+pub fn toy<C>() -> GenomeBuild<C>
+where
+    C: FromStr + Zero + PartialOrd,
+{
+    let (contigs, metadata) =
+        read_assembly_report_lines(TOY_REPORT.lines().map(Ok), &ParseOptions::default())
+            .expect("Reading builtin toy assembly report");
+    let id = GenomeBuildIdentifier::from(("Toy", "p1"));
+    GenomeBuild::new(id, contigs).with_metadata(metadata)
+}
+
+/// Get the shared handle of a bundled build from a process-wide cache.
+///
+/// Each bundled build is loaded at most once per coordinate type `C`,
+/// and the subsequent calls, from any thread, return a handle of the same build.
+/// Hence, the cache replaces the ad-hoc statics holding a loaded build.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use dabuild::SharedGenomeBuild;
+/// use dabuild::builds::{cached, KnownBuild};
+///
+/// let build: SharedGenomeBuild<u32> = cached(KnownBuild::GRCh38p13);
+/// let again = std::thread::spawn(|| cached::<u32>(KnownBuild::GRCh38p13))
+///     .join()
+///     .unwrap();
+///
+/// assert!(Arc::ptr_eq(&build, &again));
+/// ```
+///
+/// ## Panics
+///
+/// If the builtin assembly report cannot be parsed (should not happen).
+#[cfg(feature = "std")]
+pub fn cached<C>(known: KnownBuild) -> SharedGenomeBuild<C>
+where
+    C: FromStr + Zero + PartialOrd + Send + Sync + 'static,
+{
+    use std::{
+        any::{Any, TypeId},
+        collections::HashMap,
+        sync::{Mutex, OnceLock, PoisonError},
+    };
+
+    type Cache = HashMap<(KnownBuild, TypeId), Box<dyn Any + Send + Sync>>;
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+
+    // The lock is held while loading, hence a build is never loaded twice.
+    // A panic while loading leaves no entry behind, hence the poisoning can be ignored.
+    let mut cache = CACHE
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    cache
+        .entry((known, TypeId::of::<C>()))
+        .or_insert_with(|| Box::new(known.load_shared::<C>()))
+        .downcast_ref::<SharedGenomeBuild<C>>()
+        .expect("Cached build should match its coordinate type")
+        .clone()
+}
+
+/// The perfect hash indexes of the bundled builds, generated by the build script.
+mod bundled {
+    use crate::phf::PerfectIndex;
+
+    include!(concat!(env!("OUT_DIR"), "/bundled.rs"));
+}
+
+/// Load a bundled build from the `report` with the perfect hash `index` of its contig names.
+fn load_bundled<C>(
+    id: GenomeBuildIdentifier,
+    report: &[u8],
+    index: &'static PerfectIndex,
+    introduced_in: fn(&Contig<C>) -> Option<u8>,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+{
+    let report = core::str::from_utf8(report)?;
+    let (contigs, metadata) =
+        read_assembly_report_lines(report.lines().map(Ok), &ParseOptions::default())?;
+    let contigs = contigs
+        .into_iter()
+        .map(|contig| match introduced_in(&contig) {
+            Some(patch) => contig.with_introduced_in(patch),
+            None => contig,
+        })
+        .collect();
+    Ok(GenomeBuild::bundled(id, contigs, index).with_metadata(metadata))
 }
 
 /// Parse an assembly report into a [`GenomeBuild`].
@@ -97,14 +484,18 @@ where
 /// * Sequence-Length
 /// * UCSC-style-name
 ///
+/// A gzip-compressed report is decompressed if the `bgzf` feature is enabled.
+///
 /// ## Errors
 ///
 /// The parsing can fail from several reasons:
 ///
-/// * I/O error of the underlying [`BufRead`]
+/// * I/O or decompression error of the underlying [`BufRead`](std::io::BufRead)
 /// * Missing column `0` (`Sequence-Name`)
+/// * Unknown value of column `1` (`Sequence-Role`), other than `na`
 /// * Missing/unparsable column `8` (`Sequence-Length`)
 /// * Sequence length being negative (should not really happen)
+#[cfg(feature = "std")]
 pub fn parse_assembly_report<C, R>(
     id: GenomeBuildIdentifier,
     read: R,
@@ -113,65 +504,1034 @@ where
     C: FromStr + Zero + PartialOrd,
     R: BufRead,
 {
-    let mut contigs = vec![];
+    parse_assembly_report_with(id, read, &ParseOptions::default())
+}
 
-    for (i, line) in read.lines().enumerate() {
-        // Bail in case of I/O errors.
-        let line = line?;
+/// Parse the assembly report into a [`GenomeBuild`] with the parse `options`.
+///
+/// See [`parse_assembly_report`] for the expected format.
+///
+/// ## Example
+///
+/// Keep the 22 assembled molecules of *GRCm39*:
+///
+/// ```rust
+/// use std::{fs::File, io::BufReader, str::FromStr};
+/// use dabuild::{GenomeBuild, GenomeBuildIdentifier, SequenceRole};
+/// use dabuild::builds::{parse_assembly_report_with, ParseOptions};
+///
+/// let path = "data/GCF_000001635.27_GRCm39_assembly_report.txt";
+/// let build: GenomeBuild<u32> = parse_assembly_report_with(
+///     GenomeBuildIdentifier::from_str("GRCm39").expect("Infallible"),
+///     BufReader::new(File::open(path).expect("File not found")),
+///     &ParseOptions::new().roles(&[SequenceRole::AssembledMolecule]),
+/// ).expect("No I/O or format issues");
+///
+/// assert_eq!(build.contigs().count(), 22);
+/// ```
+///
+/// ## Errors
+///
+/// Same as [`parse_assembly_report`].
+#[cfg(feature = "std")]
+pub fn parse_assembly_report_with<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+    options: &ParseOptions,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    parse_assembly_report_lines(
+        id,
+        crate::decompress::decompressed(read)?
+            .lines()
+            .map(|line| line.map_err(Into::into)),
+        options,
+    )
+}
+
+#[cfg(feature = "std")]
+impl<C> GenomeBuild<C>
+where
+    C: FromStr + Zero + PartialOrd + Hash,
+{
+    /// Re-parse the assembly `report` of the build and replace the contigs and the metadata
+    /// if anything changed, keeping the build identifier.
+    ///
+    /// Returns `true` if the build changed. The build is left as is if nothing changed,
+    /// hence the handles of the unchanged build, such as the [`ContigKey`](crate::ContigKey)s,
+    /// stay valid.
+    ///
+    /// The re-parsed contigs are ordered (see [`GenomeBuild::ordering`]) and indexed
+    /// as the contigs of the build, and keep their patch releases (see [`Contig::introduced_in`]).
+    /// The new patches of *GRCh38* get the patch release of the bundled builds.
+    /// The build changed if the metadata or the [`GenomeBuild::fingerprint`] differ,
+    /// hence any field of the contigs, such as the source index or the report names.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::{fs::File, io::BufReader, str::FromStr};
+    /// use dabuild::{GenomeBuild, GenomeBuildIdentifier};
+    /// use dabuild::builds::parse_assembly_report;
+    ///
+    /// let path = "data/GCF_000001635.27_GRCm39_assembly_report.txt";
+    /// let open = || BufReader::new(File::open(path).expect("File not found"));
+    /// let mut build: GenomeBuild<u32> = parse_assembly_report(
+    ///     GenomeBuildIdentifier::from_str("GRCm39").expect("Infallible"),
+    ///     open(),
+    /// ).expect("No I/O or format issues");
+    ///
+    /// assert!(!build.refresh_from(open()).expect("No I/O or format issues"));
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Same as [`parse_assembly_report`]. The build is left as is on error.
+    pub fn refresh_from<R>(&mut self, report: R) -> Result<bool, Box<dyn Error>>
+    where
+        R: BufRead,
+    {
+        self.refresh_from_with(report, &ParseOptions::default())
+    }
 
-        if line.starts_with("#") {
-            continue;
+    /// Re-parse the assembly `report` of the build with the parse `options`,
+    /// see [`GenomeBuild::refresh_from`].
+    ///
+    /// ## Errors
+    ///
+    /// Same as [`parse_assembly_report_with`]. The build is left as is on error.
+    pub fn refresh_from_with<R>(
+        &mut self,
+        report: R,
+        options: &ParseOptions,
+    ) -> Result<bool, Box<dyn Error>>
+    where
+        R: BufRead,
+    {
+        let lines = crate::decompress::decompressed(report)?
+            .lines()
+            .map(|line| line.map_err(Into::into));
+        let (mut contigs, metadata) = read_assembly_report_lines(lines, options)?;
+        options.finish_contigs(&mut contigs);
+        Ok(self.refresh_with(contigs, metadata))
+    }
+
+    /// Replace the contigs and the metadata of the build with the `contigs` and the `metadata`
+    /// if anything changed, see [`GenomeBuild::refresh_from`].
+    ///
+    /// Returns `true` if the build changed.
+    pub(crate) fn refresh_with(
+        &mut self,
+        contigs: Vec<Contig<C>>,
+        metadata: BuildMetadata,
+    ) -> bool {
+        // The patch releases of *GRCh38* are known, hence the new patches get theirs too.
+        let grch38 = self.id().major_assembly() == "GRCh38";
+        let contigs = contigs.into_iter().map(|contig| {
+            let patch = grch38
+                .then(|| grch38_introduced_in(&contig))
+                .flatten()
+                .or_else(|| {
+                    self.contig_by_name(contig.name())
+                        .filter(|known| known.name() == contig.name())
+                        .and_then(Contig::introduced_in)
+                });
+            match patch {
+                Some(patch) => contig.with_introduced_in(patch),
+                None => contig,
+            }
+        });
+        let fresh = GenomeBuild::assemble(
+            self.id().clone(),
+            contigs,
+            self.ordering(),
+            self.index_kind(),
+        )
+        .with_metadata(metadata);
+
+        let changed =
+            fresh.fingerprint() != self.fingerprint() || fresh.metadata() != self.metadata();
+        if changed {
+            *self = fresh;
         }
-        let fields: Vec<_> = line.split("\t").collect();
+        changed
+    }
+}
 
-        // Disabling the lint to emphasize accessing the columns with indices.
-        #[allow(clippy::get_first)]
-        let name = if let Some(&name) = fields.get(0) {
-            name
+/// Write the genome build as an assembly report.
+///
+/// The `Sequence-Name` column includes the main contig name,
+/// and the accession columns include the GenBank, RefSeq, and UCSC-style names of the contig,
+/// the names read from a report (see [`Contig::genbank_accession`]) if known.
+/// The `style` picks the name of the `UCSC-style-name` column of the contigs without
+/// a UCSC-style name, unless the name is already in another column.
+/// The unknown values, such as the assigned molecule or the assembly unit, are written as `na`.
+/// Hence, the report can be read back by [`parse_assembly_report`] in any `style`.
+/// The header is written by [`write_assembly_report_header`].
+///
+/// ## Example
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use dabuild::{GenomeBuild, GenomeBuildIdentifier, NameStyle};
+/// use dabuild::builds::{get_grch38_p13, parse_assembly_report, write_assembly_report};
+///
+/// let build: GenomeBuild<u32> = get_grch38_p13();
+/// let mut report = vec![];
+/// write_assembly_report(&build, NameStyle::Ucsc, &mut report).expect("No I/O issues");
+///
+/// let parsed: GenomeBuild<u32> = parse_assembly_report(
+///     GenomeBuildIdentifier::from(("GRCh38", "p13")),
+///     report.as_slice(),
+/// ).expect("Valid report");
+/// assert_eq!(parsed, build);
+/// ```
+///
+/// ## Errors
+///
+/// I/O error of the underlying [`Write`].
+#[cfg(feature = "std")]
+pub fn write_assembly_report<C, W>(
+    build: &GenomeBuild<C>,
+    style: NameStyle,
+    mut write: W,
+) -> io::Result<()>
+where
+    C: Display,
+    W: Write,
+{
+    write_assembly_report_header(build, &mut write)?;
+    writeln!(
+        write,
+        "# Sequence-Name\tSequence-Role\tAssigned-Molecule\tAssigned-Molecule-Location/Type\t\
+        GenBank-Accn\tRelationship\tRefSeq-Accn\tAssembly-Unit\tSequence-Length\tUCSC-style-name"
+    )?;
+
+    for contig in build.contigs() {
+        let role = contig.role().map_or("na", |role| role.as_str());
+        let [gen_bank, refseq, ucsc] = contig.report_columns();
+        let relationship = if gen_bank.is_some() && refseq.is_some() {
+            "="
         } else {
-            return Err(format!("Missing column #0 (`Sequence-Name`) in line #{i} {line}").into());
+            "na"
         };
-        let mut alt_names = vec![];
+        let ucsc = ucsc.or_else(|| {
+            let styled = styled_name(contig, style);
+            let written = [Some(contig.name()), gen_bank, refseq];
+            Some(styled).filter(|styled| !written.contains(&Some(styled)))
+        });
+        writeln!(
+            write,
+            "{}\t{role}\tna\tna\t{}\t{relationship}\t{}\tna\t{}\t{}",
+            contig.name(),
+            gen_bank.unwrap_or("na"),
+            refseq.unwrap_or("na"),
+            contig.length(),
+            ucsc.unwrap_or("na"),
+        )?;
+    }
+    Ok(())
+}
 
-        // Accessions:
-        // GenBank, column #4
-        if let Some(&gen_bank) = fields.get(4) {
-            if gen_bank != "na" {
-                alt_names.push(gen_bank);
+/// Write the `#` header block of an assembly report, synthesized from the build identifier
+/// and the [`BuildMetadata`], e.g. for a build loaded from a FASTA index.
+///
+/// The header includes the keys of the NCBI reports in the NCBI order,
+/// with an empty value if unknown, hence the strict header parsers find all keys.
+/// The release type is `patch` if the identifier has a patch, and `major` otherwise.
+/// The block ends with an empty `#` line.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use dabuild::{AssemblyLevel, BuildMetadata, GenomeBuild, GenomeBuildIdentifier};
+/// use dabuild::builds::write_assembly_report_header;
+/// use dabuild::formats::parse_chrom_sizes;
+///
+/// let build: GenomeBuild<u32> = parse_chrom_sizes(
+///     GenomeBuildIdentifier::from_str("custom").expect("Infallible"),
+///     "chr1\t1000\n".as_bytes(),
+/// ).expect("Valid chrom.sizes");
+/// let metadata = BuildMetadata::new()
+///     .with_organism("Homo sapiens (human)")
+///     .with_taxid(9606)
+///     .with_assembly_level(AssemblyLevel::Chromosome);
+///
+/// let mut header = vec![];
+/// write_assembly_report_header(&build.with_metadata(metadata), &mut header)
+///     .expect("No I/O issues");
+/// let header = String::from_utf8(header).expect("UTF-8");
+///
+/// assert!(header.starts_with("# Assembly name:  custom\n# Organism name:  Homo sapiens (human)\n"));
+/// assert!(header.contains("# Taxid:          9606\n"));
+/// assert!(header.contains("# Release type:   major\n"));
+/// assert!(header.contains("# RefSeq assembly accession: \n"));
+/// ```
+///
+/// ## Errors
+///
+/// I/O error of the underlying [`Write`].
+#[cfg(feature = "std")]
+pub fn write_assembly_report_header<C, W>(build: &GenomeBuild<C>, mut write: W) -> io::Result<()>
+where
+    W: Write,
+{
+    let metadata = build.metadata();
+    let taxid = metadata.taxid().map(|taxid| taxid.to_string());
+    let level = metadata.assembly_level().map(|level| level.to_string());
+    let representation = metadata
+        .genome_representation()
+        .map(|representation| representation.to_string());
+    let release = if build.id().patch().is_some() {
+        "patch"
+    } else {
+        "major"
+    };
+    let name = build.id().to_string();
+
+    for (key, value) in [
+        ("Assembly name:  ", Some(name.as_str())),
+        ("Organism name:  ", metadata.organism()),
+        ("Taxid:          ", taxid.as_deref()),
+        ("BioSample:      ", metadata.biosample()),
+        ("BioProject:     ", metadata.bioproject()),
+        ("Submitter:      ", metadata.submitter()),
+        ("Date:           ", metadata.date()),
+        ("Release type:   ", Some(release)),
+        ("Assembly level: ", level.as_deref()),
+        ("Genome representation: ", representation.as_deref()),
+        ("GenBank assembly accession: ", metadata.genbank_accession()),
+        ("RefSeq assembly accession: ", metadata.refseq_accession()),
+    ] {
+        writeln!(write, "# {key}{}", value.unwrap_or_default())?;
+    }
+    writeln!(write, "#")
+}
+
+/// Download the assembly report of the `accession` (e.g. `GCF_000001405.40`) from NCBI
+/// and parse it into a [`GenomeBuild`].
+///
+/// The build identifier is derived from the assembly name, e.g. `GRCh38` and `p14`
+/// for `GRCh38.p14`. The function blocks until the report is downloaded.
+/// See the [`crate::fetch`] module for more info.
+///
+/// ## Errors
+///
+/// * Invalid or unknown accession
+/// * Network error
+/// * Invalid assembly report, see [`parse_assembly_report`]
+#[cfg(feature = "fetch")]
+pub fn fetch_assembly_report<C>(accession: &str) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+{
+    let (id, report) = crate::fetch::Downloader::new().download_identified_report(accession)?;
+    parse_assembly_report_bytes(id, report.as_bytes())
+}
+
+/// Download the assembly report of the UCSC database `name` (e.g. `hg38`) from NCBI
+/// and parse it into a [`GenomeBuild`].
+///
+/// The name is resolved to the assembly accession using [`crate::fetch::resolve_ucsc_name`].
+///
+/// ## Errors
+///
+/// * Unknown database
+/// * The errors of [`fetch_assembly_report`]
+#[cfg(feature = "fetch")]
+pub fn fetch_by_ucsc_name<C>(name: &str) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+{
+    fetch_assembly_report(&crate::fetch::resolve_ucsc_name(name)?)
+}
+
+/// List the releases of the `assembly` available at NCBI, such as the patches, the oldest first.
+///
+/// The `assembly` is the name of a well-known assembly (`GRCh37`, `GRCh38`, `GRCm38`, `GRCm39`),
+/// or an accession without the version (e.g. `GCF_000001405`).
+/// The releases of a well-known assembly are limited to those of the same major assembly,
+/// e.g. the `GRCh38` patches exclude the `GRCh37` releases.
+///
+/// For instance, to use the latest patch:
+///
+/// ```no_run
+/// use dabuild::GenomeBuild;
+/// use dabuild::builds::{fetch_assembly_report, list_patches};
+///
+/// let patches = list_patches("GRCh38").expect("Available NCBI");
+/// let latest = patches.last().expect("At least one release");
+/// let build: GenomeBuild<u32> = fetch_assembly_report(latest.accession()).expect("Valid report");
+/// ```
+///
+/// ## Errors
+///
+/// * Unknown assembly name or invalid accession
+/// * Network error
+#[cfg(feature = "fetch")]
+pub fn list_patches(assembly: &str) -> Result<Vec<crate::fetch::AssemblyRelease>, Box<dyn Error>> {
+    crate::fetch::list_releases(assembly)
+}
+
+/// Download the assembly report of the `accession` from NCBI
+/// and parse it into a [`GenomeBuild`] without blocking the async runtime.
+///
+/// The download runs on the blocking thread pool of the current [`tokio`] runtime.
+/// See [`fetch_assembly_report`] for more info.
+///
+/// ## Errors
+///
+/// Same as [`fetch_assembly_report`].
+///
+/// ## Panics
+///
+/// If called outside of a [`tokio`] runtime.
+#[cfg(all(feature = "fetch", feature = "tokio"))]
+pub async fn fetch_assembly_report_async<C>(
+    accession: &str,
+) -> Result<GenomeBuild<C>, Box<dyn Error + Send + Sync>>
+where
+    C: FromStr + Zero + PartialOrd,
+{
+    let accession = String::from(accession);
+    let (id, report) = tokio::task::spawn_blocking(move || {
+        crate::fetch::Downloader::new()
+            .download_identified_report(&accession)
+            .map_err(|e| e.to_string())
+    })
+    .await??;
+    parse_assembly_report_bytes(id, report.as_bytes()).map_err(|e| e.to_string().into())
+}
+
+/// Parse an assembly report from an async reader into a [`GenomeBuild`].
+///
+/// See [`parse_assembly_report`] for the expected format.
+/// The lines are parsed as they are read. A compressed report is read whole,
+/// and then decompressed and parsed.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use dabuild::{GenomeBuild, GenomeBuildIdentifier};
+/// use dabuild::builds::parse_assembly_report_async;
+///
+/// let report = std::fs::read("data/GCF_000001635.27_GRCm39_assembly_report.txt")
+///     .expect("File not found");
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().expect("Runtime");
+/// let build: GenomeBuild<u32> = runtime.block_on(parse_assembly_report_async(
+///     GenomeBuildIdentifier::from_str("GRCm39").expect("Infallible"),
+///     report.as_slice(),
+/// )).expect("No format issues");
+///
+/// assert_eq!(build.contigs().count(), 61);
+/// ```
+///
+/// ## Errors
+///
+/// Same as [`parse_assembly_report`].
+#[cfg(feature = "tokio")]
+pub async fn parse_assembly_report_async<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+) -> Result<GenomeBuild<C>, Box<dyn Error + Send + Sync>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: tokio::io::AsyncBufRead + Unpin,
+{
+    parse_assembly_report_async_with(id, read, &ParseOptions::default()).await
+}
+
+/// Parse an assembly report from an async reader into a [`GenomeBuild`]
+/// with the parse `options`.
+///
+/// See [`parse_assembly_report_async`].
+///
+/// ## Errors
+///
+/// Same as [`parse_assembly_report_with`].
+#[cfg(feature = "tokio")]
+pub async fn parse_assembly_report_async_with<C, R>(
+    id: GenomeBuildIdentifier,
+    mut read: R,
+    options: &ParseOptions,
+) -> Result<GenomeBuild<C>, Box<dyn Error + Send + Sync>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: tokio::io::AsyncBufRead + Unpin,
+{
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+    let mut reader = ReportReader::new(options);
+    if read
+        .fill_buf()
+        .await?
+        .starts_with(&crate::decompress::GZIP_MAGIC)
+    {
+        // The compressed report is decompressed by the sync reader of the sync parser.
+        let mut report = vec![];
+        read.read_to_end(&mut report).await?;
+        for line in crate::decompress::decompressed(report.as_slice())?.lines() {
+            reader.read_line(&line?)?;
+        }
+    } else {
+        // The report is parsed line by line, as the lines are read.
+        let mut lines = read.lines();
+        while let Some(line) = lines.next_line().await? {
+            reader.read_line(&line)?;
+        }
+    }
+
+    let (mut contigs, metadata) = reader.finish();
+    options.finish_contigs(&mut contigs);
+    Ok(GenomeBuild::new(id, contigs).with_metadata(metadata))
+}
+
+/// Parse an assembly report stored in a byte slice into a [`GenomeBuild`].
+///
+/// The function does not need the `std` feature.
+/// See [`parse_assembly_report`] for the expected format.
+///
+/// ## Errors
+///
+/// Same as [`parse_assembly_report`], except for I/O errors.
+/// Additionally, the parsing fails if the bytes are not valid UTF-8.
+pub fn parse_assembly_report_bytes<C>(
+    id: GenomeBuildIdentifier,
+    bytes: &[u8],
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+{
+    parse_assembly_report_bytes_with(id, bytes, &ParseOptions::default())
+}
+
+/// Parse an assembly report stored in a byte slice into a [`GenomeBuild`]
+/// with the parse `options`.
+///
+/// ## Errors
+///
+/// Same as [`parse_assembly_report_bytes`].
+pub fn parse_assembly_report_bytes_with<C>(
+    id: GenomeBuildIdentifier,
+    bytes: &[u8],
+    options: &ParseOptions,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+{
+    let report = core::str::from_utf8(bytes)?;
+    parse_assembly_report_lines(id, report.lines().map(Ok), options)
+}
+
+/// The options of parsing the contigs, shared by the parsers of the assembly reports
+/// and of the other formats, such as [`crate::formats::parse_fai_with`].
+///
+/// By default, all contigs are kept, the malformed contig lines fail the parsing,
+/// and the number of the contigs is not limited.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use dabuild::{GenomeBuild, GenomeBuildIdentifier};
+/// use dabuild::builds::ParseOptions;
+/// use dabuild::formats::parse_chrom_sizes_with;
+///
+/// let sizes = "1\t248956422\nchrUn_KI270302v1\t2274\nbroken line\nMT\t16569\n";
+/// let options = ParseOptions::new().lenient().min_length(10_000).chr_aliases();
+/// let build: GenomeBuild<u32> = parse_chrom_sizes_with(
+///     GenomeBuildIdentifier::from_str("hg38").expect("Infallible"),
+///     sizes.as_bytes(),
+///     &options,
+/// ).expect("Lenient parsing");
+///
+/// let names: Vec<_> = build.contigs().map(|c| c.name()).collect();
+/// assert_eq!(names, ["1", "MT"]);
+/// assert!(build.contig_by_name("chrM").is_some());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    roles: Option<Vec<SequenceRole>>,
+    min_length: Option<u64>,
+    lenient: bool,
+    chr_aliases: bool,
+    max_contigs: Option<usize>,
+}
+
+impl ParseOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only the contigs with one of the `roles`.
+    ///
+    /// The contigs with an unknown role (`na`) are dropped.
+    pub fn roles(mut self, roles: &[SequenceRole]) -> Self {
+        self.roles = Some(roles.to_vec());
+        self
+    }
+
+    /// Keep only the contigs that are at least `min_length` bases long,
+    /// e.g. to drop the scaffolds under 1 kb.
+    pub fn min_length(mut self, min_length: u64) -> Self {
+        self.min_length = Some(min_length);
+        self
+    }
+
+    /// Skip the malformed contig lines, e.g. with a missing or an unparsable length,
+    /// instead of failing.
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
+    /// Add the alias with or without the `chr` prefix to the chromosomes,
+    /// e.g. `chr1` to `1` and `MT` to `chrM`, unless the alias names another contig.
+    pub fn chr_aliases(mut self) -> Self {
+        self.chr_aliases = true;
+        self
+    }
+
+    /// Fail if more than `max_contigs` contigs are kept, e.g. to bound the memory
+    /// used by an untrusted input.
+    pub fn max_contigs(mut self, max_contigs: usize) -> Self {
+        self.max_contigs = Some(max_contigs);
+        self
+    }
+
+    /// Test if the malformed contig lines are skipped.
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Test if a contig with the `role` and the `length` text should be kept,
+    /// keeping the lengths that are not `u64` integers.
+    #[cfg(feature = "std")]
+    pub(crate) fn keeps_contig(&self, role: Option<SequenceRole>, length: &str) -> bool {
+        self.keeps_role(role)
+            && length
+                .parse()
+                .map_or(true, |length| self.keeps_length(length))
+    }
+
+    /// Check that keeping `count` contigs is within the limit of [`ParseOptions::max_contigs`].
+    pub(crate) fn check_count(&self, count: usize) -> Result<(), String> {
+        match self.max_contigs {
+            Some(max_contigs) if count > max_contigs => {
+                Err(format!("More than {max_contigs} contigs"))
             }
-        };
-        // RefSeq, column #6
-        if let Some(&refseq) = fields.get(6) {
-            if refseq != "na" {
-                alt_names.push(refseq);
+            _ => Ok(()),
+        }
+    }
+
+    /// Apply the options to the parsed `build`, adding the aliases of [`ParseOptions::chr_aliases`].
+    #[cfg(feature = "std")]
+    pub(crate) fn finish<C>(&self, mut build: GenomeBuild<C>) -> GenomeBuild<C>
+    where
+        C: Clone,
+    {
+        if !self.chr_aliases {
+            return build;
+        }
+        let aliases: Vec<_> = build
+            .contigs()
+            .filter_map(|contig| Some((String::from(contig.name()), chr_alias(contig.name())?)))
+            .filter(|(_, alias)| build.contig_by_name(alias).is_none())
+            .collect();
+        for (name, alias) in aliases {
+            // The alias names no other contig, hence it is added.
+            let _ = build.add_alias(&name, &alias);
+        }
+        build
+    }
+
+    /// Apply the options to the parsed `contigs`, adding the aliases of [`ParseOptions::chr_aliases`].
+    fn finish_contigs<C>(&self, contigs: &mut [Contig<C>]) {
+        if !self.chr_aliases {
+            return;
+        }
+        let mut names: BTreeSet<String> = contigs
+            .iter()
+            .flat_map(|contig| contig.names().map(String::from))
+            .collect();
+        for contig in contigs {
+            if let Some(alias) = chr_alias(contig.name()).filter(|alias| !names.contains(alias)) {
+                contig.push_alt_name(&alias);
+                names.insert(alias);
             }
-        };
-        // UCSC, column #9
-        if let Some(&ucsc) = fields.get(9) {
-            if ucsc != "na" {
-                alt_names.push(ucsc);
+        }
+    }
+
+    /// Test if the contigs are filtered by length, see [`ParseOptions::min_length`].
+    pub(crate) fn filters_length(&self) -> bool {
+        self.min_length.is_some()
+    }
+
+    /// Test if a contig with the `length` should be kept.
+    pub fn keeps_length(&self, length: u64) -> bool {
+        self.min_length
+            .is_none_or(|min_length| length >= min_length)
+    }
+
+    /// Test if a contig with the `role` should be kept.
+    pub fn keeps_role(&self, role: Option<SequenceRole>) -> bool {
+        match (&self.roles, role) {
+            (None, _) => true,
+            (Some(roles), Some(role)) => roles.contains(&role),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Get the name of the chromosome `name` with or without the `chr` prefix,
+/// e.g. `1` for `chr1` and `chrM` for `MT`, or `None` if the name is not a chromosome.
+fn chr_alias(name: &str) -> Option<String> {
+    let stripped = strip_chr(name);
+    let is_chromosome = stripped.bytes().all(|b| b.is_ascii_digit())
+        || ["X", "Y", "Z", "W", "M", "MT"].contains(&stripped);
+    if stripped.is_empty() || !is_chromosome {
+        return None;
+    }
+    Some(match (stripped.len() < name.len(), stripped) {
+        (true, "M") => String::from("MT"),
+        (true, _) => String::from(stripped),
+        (false, "MT" | "M") => String::from("chrM"),
+        (false, _) => format!("chr{stripped}"),
+    })
+}
+
+fn parse_assembly_report_lines<C, I, S>(
+    id: GenomeBuildIdentifier,
+    lines: I,
+    options: &ParseOptions,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    I: Iterator<Item = Result<S, Box<dyn Error>>>,
+    S: AsRef<str>,
+{
+    let (mut contigs, metadata) = read_assembly_report_lines(lines, options)?;
+    options.finish_contigs(&mut contigs);
+    Ok(GenomeBuild::new(id, contigs).with_metadata(metadata))
+}
+
+/// The contigs and the header metadata of an assembly report.
+type ReportContents<C> = (Vec<Contig<C>>, BuildMetadata);
+
+/// Read the contigs and the header metadata from the lines of an assembly report.
+fn read_assembly_report_lines<C, I, S>(
+    lines: I,
+    options: &ParseOptions,
+) -> Result<ReportContents<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    I: Iterator<Item = Result<S, Box<dyn Error>>>,
+    S: AsRef<str>,
+{
+    let mut reader = ReportReader::new(options);
+    for line in lines {
+        // Bail in case of I/O errors.
+        reader
+            .read_line(line?.as_ref())
+            .map_err(|e| -> Box<dyn Error> { e })?;
+    }
+
+    Ok(reader.finish())
+}
+
+/// Reads the contigs and the header metadata of an assembly report line by line,
+/// e.g. from the lines of a sync or of an async reader.
+struct ReportReader<'a, C> {
+    options: &'a ParseOptions,
+    contigs: Vec<Contig<C>>,
+    metadata: BuildMetadata,
+    line: usize,
+}
+
+impl<'a, C> ReportReader<'a, C>
+where
+    C: FromStr + Zero + PartialOrd,
+{
+    fn new(options: &'a ParseOptions) -> Self {
+        ReportReader {
+            options,
+            contigs: vec![],
+            metadata: BuildMetadata::new(),
+            line: 0,
+        }
+    }
+
+    /// Read the next `line` of the report.
+    fn read_line(&mut self, line: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let i = self.line;
+        self.line += 1;
+        if line.starts_with('#') {
+            self.metadata.read_header_line(line);
+            return Ok(());
+        }
+        match parse_assembly_report_line(i, line, self.options) {
+            Ok(Some(contig)) => {
+                self.contigs.push(contig);
+                self.options.check_count(self.contigs.len())?;
             }
-        };
+            Ok(None) => {}
+            Err(_) if self.options.is_lenient() => {}
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> ReportContents<C> {
+        (self.contigs, self.metadata)
+    }
+}
+
+/// Parse the `i`th line of an assembly report into a contig,
+/// or `None` if the line is a header line or the contig is filtered out by the `options`.
+fn parse_assembly_report_line<C>(
+    i: usize,
+    line: &str,
+    options: &ParseOptions,
+) -> Result<Option<Contig<C>>, Box<dyn Error + Send + Sync>>
+where
+    C: FromStr + Zero + PartialOrd,
+{
+    match parse_report_record(i, line, options)? {
+        Some(record) => record
+            .into_contig()
+            .map(Some)
+            .ok_or_else(|| "Cannot parse contig".into()),
+        None => Ok(None),
+    }
+}
+
+/// A contig line of an assembly report with the names borrowed from the report.
+///
+/// See [`parse_assembly_report_records`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReportRecord<'a, C> {
+    /// The 0-based index of the line in the report.
+    pub line: usize,
+    /// `Sequence-Name`, column #0.
+    pub name: Cow<'a, str>,
+    /// `Sequence-Role`, column #1, or `None` if `na`.
+    pub role: Option<SequenceRole>,
+    /// `GenBank-Accn`, column #4, or `None` if `na`.
+    pub genbank: Option<Cow<'a, str>>,
+    /// `RefSeq-Accn`, column #6, or `None` if `na`.
+    pub refseq: Option<Cow<'a, str>>,
+    /// `Sequence-Length`, column #8.
+    pub length: C,
+    /// `UCSC-style-name`, column #9, or `None` if `na`.
+    pub ucsc: Option<Cow<'a, str>>,
+}
+
+impl<C> ReportRecord<'_, C> {
+    /// Get an iterator with the GenBank, RefSeq, and UCSC-style names, if present.
+    pub fn alt_names(&self) -> impl Iterator<Item = &str> {
+        [&self.genbank, &self.refseq, &self.ucsc]
+            .into_iter()
+            .flatten()
+            .map(|name| name.as_ref())
+    }
+
+    /// Get a record that owns the names.
+    pub fn into_owned(self) -> ReportRecord<'static, C> {
+        let owned = |name: Cow<'_, str>| Cow::Owned(name.into_owned());
+        ReportRecord {
+            line: self.line,
+            name: owned(self.name),
+            role: self.role,
+            genbank: self.genbank.map(owned),
+            refseq: self.refseq.map(owned),
+            length: self.length,
+            ucsc: self.ucsc.map(owned),
+        }
+    }
+
+    /// Convert the record into a contig, with the role and the source index of the record,
+    /// and with the accessions of the record, e.g. [`Contig::genbank_accession`].
+    ///
+    /// Returns `None` if the length is negative.
+    pub fn into_contig(self) -> Option<Contig<C>>
+    where
+        C: Zero + PartialOrd,
+    {
+        let Self {
+            line,
+            name,
+            role,
+            genbank,
+            refseq,
+            length,
+            ucsc,
+        } = self;
+        let alt_names: SmallVec<[&str; 3]> = [&genbank, &refseq, &ucsc]
+            .into_iter()
+            .flatten()
+            .map(|name| name.as_ref())
+            .collect();
+        let contig = Contig::from_names(&name, &alt_names, length)?
+            .with_source_index(line)
+            .with_report_names([genbank.as_deref(), refseq.as_deref(), ucsc.as_deref()])?;
+        Some(match role {
+            Some(role) => contig.with_role(role),
+            None => contig,
+        })
+    }
+}
+
+/// Parse the contig lines of an assembly report stored in memory,
+/// borrowing the names from the `report`.
+///
+/// The header lines are skipped, and the records are yielded in the order of the report.
+/// Unlike [`parse_assembly_report_bytes`], no contigs or builds are allocated,
+/// hence the records can be scanned or filtered cheaply, e.g. in large, scaffold-heavy assemblies.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::builds::{parse_assembly_report_records, ReportRecord};
+///
+/// let report = include_str!("../data/GCF_000001635.27_GRCm39_assembly_report.txt");
+/// let records: Vec<ReportRecord<'_, u32>> = parse_assembly_report_records(report)
+///     .collect::<Result<_, _>>()
+///     .expect("Valid report");
+///
+/// let y = records.iter().find(|record| record.name == "Y").expect("Y chromosome");
+/// assert_eq!(y.refseq.as_deref(), Some("NC_000087.8"));
+/// assert_eq!(y.length, 91_455_967);
+///
+/// let contig = y.clone().into_contig().expect("Non-negative length");
+/// assert_eq!(contig.name(), "Y");
+/// ```
+///
+/// ## Errors
+///
+/// The same line errors as [`parse_assembly_report_bytes`].
+pub fn parse_assembly_report_records<C>(
+    report: &str,
+) -> impl Iterator<Item = Result<ReportRecord<'_, C>, Box<dyn Error>>>
+where
+    C: FromStr,
+{
+    let options = ParseOptions::default();
+    report.lines().enumerate().filter_map(move |(i, line)| {
+        parse_report_record(i, line, &options)
+            .map_err(|e| -> Box<dyn Error> { e })
+            .transpose()
+    })
+}
+
+/// Parse the `i`th line of an assembly report into a record,
+/// or `None` if the line is a header line or the role is filtered out by the `options`.
+fn parse_report_record<'a, C>(
+    i: usize,
+    line: &'a str,
+    options: &ParseOptions,
+) -> Result<Option<ReportRecord<'a, C>>, Box<dyn Error + Send + Sync>>
+where
+    C: FromStr,
+{
+    if line.starts_with("#") {
+        return Ok(None);
+    }
+    // The columns, without allocating.
+    let mut fields = [None; 10];
+    for (field, value) in fields.iter_mut().zip(line.split('\t')) {
+        *field = Some(value);
+    }
+    let accession = |value: Option<&'a str>| value.filter(|&value| value != "na").map(Cow::from);
+
+    let name = if let Some(name) = fields[0] {
+        name
+    } else {
+        return Err(format!("Missing column #0 (`Sequence-Name`) in line #{i} {line}").into());
+    };
+
+    // Role, column #1
+    let role = match fields[1] {
+        Some("na") | None => None,
+        Some(role) => match role.parse::<SequenceRole>() {
+            Ok(role) => Some(role),
+            Err(e) => return Err(format!("{e} in line #{i} {line}").into()),
+        },
+    };
+    if !options.keeps_role(role) {
+        return Ok(None);
+    }
+
+    // Length
+    let Some(text) = fields[8] else {
+        return Err(format!("Missing column #8 (`Sequence-Length`) in line #{i} {line}").into());
+    };
+    let Ok(length) = text.parse() else {
+        return Err(format!(
+            "Cannot parse field #8 {text:?} into contig length in line #{i} {line}"
+        )
+        .into());
+    };
+    // `C` has no conversion to `u64`, hence the length is parsed again only to be filtered.
+    if options.filters_length()
+        && text
+            .parse()
+            .is_ok_and(|length| !options.keeps_length(length))
+    {
+        return Ok(None);
+    }
 
-        // Length
-        let length = if let Some(&l) = fields.get(8) {
-            match l.parse() {
-                Ok(length) => length,
-                Err(_) => {
-                    return Err(format!("Cannot parse field #8 {l:?} into contig length").into())
+    Ok(Some(ReportRecord {
+        line: i,
+        name: Cow::from(name),
+        role,
+        // Accessions: GenBank, column #4, RefSeq, column #6, and UCSC, column #9
+        genbank: accession(fields[4]),
+        refseq: accession(fields[6]),
+        length,
+        ucsc: accession(fields[9]),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{get_grch37_p13, get_grch38_p13};
+    use crate::GenomeBuild;
+
+    #[test]
+    fn test_bundled_perfect_index() {
+        for bundled in [get_grch37_p13::<u32>(), get_grch38_p13()] {
+            let rebuilt = GenomeBuild::new(bundled.id().clone(), bundled.contigs().cloned());
+            assert_eq!(bundled, rebuilt);
+
+            for contig in bundled.contigs() {
+                for name in core::iter::once(contig.name()).chain(contig.alt_names()) {
+                    assert_eq!(
+                        bundled.contig_by_name(name).map(|c| c.name()),
+                        rebuilt.contig_by_name(name).map(|c| c.name()),
+                    );
                 }
             }
-        } else {
-            return Err(
-                format!("Missing column #8 (`Sequence-Length`) in line #{i} {line}").into(),
-            );
-        };
+            for name in ["", "chr", "chr1_", "NC_000001", "1 ", "chrUn_KI270302v1"] {
+                assert_eq!(
+                    bundled.contig_by_name(name).is_some(),
+                    rebuilt.contig_by_name(name).is_some(),
+                );
+            }
 
-        match Contig::new(name, &alt_names, length) {
-            Some(contig) => contigs.push(contig),
-            None => return Err("Cannot parse contig".into()),
-        };
+            let subset = bundled.subset(["chr2", "1"]).unwrap();
+            let names: Vec<_> = subset.contigs().map(|c| c.name()).collect();
+            assert_eq!(names, ["1", "2"]);
+            assert_eq!(subset.contig_by_name("chr1").map(|c| c.name()), Some("1"));
+            assert!(subset.contig_by_name("chr3").is_none());
+        }
     }
-
-    Ok(GenomeBuild::new(id, contigs))
 }
@@ -0,0 +1,482 @@
+//! # Sequence name consistency
+//!
+//! The module includes [`SequenceCheck`], the comparison of the sequence names (and lengths)
+//! of a file, such as a FASTA file or a tabix index, with the contigs of a genome build.
+//! See [`GenomeBuild::check_sequences`] and [`GenomeBuild::check_names`].
+//! The naming style of a set of names is detected by [`GenomeBuild::detect_name_style`],
+//! and the renames between two styles are planned by [`GenomeBuild::plan_rename`].
+
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{GenomeBuild, NameStyle};
+
+/// A sequence whose length differs from the length of its contig.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LengthMismatch<C> {
+    /// The sequence name, as used in the file.
+    pub name: String,
+    /// The contig length.
+    pub expected: C,
+    /// The sequence length.
+    pub found: C,
+}
+
+/// The comparison of the sequences of a file with the contigs of a genome build,
+/// see [`GenomeBuild::check_sequences`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SequenceCheck<C> {
+    /// The pairs of the sequence name and the main name of its contig.
+    matched: Vec<(String, String)>,
+    /// The contig names in all styles, in the order of `matched`.
+    styled: Vec<[Option<String>; 4]>,
+    /// The styles of the contig names used by all matched sequences.
+    styles: Vec<NameStyle>,
+    unknown: Vec<String>,
+    missing: Vec<String>,
+    length_mismatches: Vec<LengthMismatch<C>>,
+}
+
+impl<C> SequenceCheck<C> {
+    /// Get the pairs of the sequence name and the main name of the contig of the sequence.
+    pub fn matched(&self) -> &[(String, String)] {
+        &self.matched
+    }
+
+    /// Get the naming styles that are followed by all matched sequence names,
+    /// e.g. [`NameStyle::Ucsc`] for `chr1`, `chr2`, ...
+    ///
+    /// The styles are empty if the file mixes the styles.
+    pub fn styles(&self) -> &[NameStyle] {
+        &self.styles
+    }
+
+    /// Get the sequence names that are not in the build.
+    pub fn unknown(&self) -> &[String] {
+        &self.unknown
+    }
+
+    /// Get the main names of the contigs that are not in the file.
+    pub fn missing(&self) -> &[String] {
+        &self.missing
+    }
+
+    /// Get the sequences whose length differs from the contig length.
+    pub fn length_mismatches(&self) -> &[LengthMismatch<C>] {
+        &self.length_mismatches
+    }
+
+    /// Test if all sequences are in the build and have the contig length.
+    ///
+    /// The contigs missing from the file and the naming styles are not considered.
+    pub fn is_consistent(&self) -> bool {
+        self.unknown.is_empty() && self.length_mismatches.is_empty()
+    }
+
+    /// Get the renames of the sequence names to the contig names in the `style`,
+    /// e.g. `1` to `chr1` for [`NameStyle::Ucsc`].
+    ///
+    /// The sequences already named in the `style`
+    /// and the sequences whose contig has no name in the `style` are left out.
+    pub fn rename_map(&self, style: NameStyle) -> Vec<(String, String)> {
+        let s = NameStyle::ALL
+            .iter()
+            .position(|&s| s == style)
+            .expect("All styles");
+        self.matched
+            .iter()
+            .zip(&self.styled)
+            .filter_map(|((name, _), styled)| {
+                let renamed = styled[s].as_ref()?;
+                (renamed != name).then(|| (name.clone(), renamed.clone()))
+            })
+            .collect()
+    }
+
+    /// Get the renames of the main contig names to the sequence names,
+    /// e.g. to query a file named in another style with the names of the build.
+    ///
+    /// The contigs whose main name is the sequence name are left out.
+    pub fn query_map(&self) -> Vec<(String, String)> {
+        self.matched
+            .iter()
+            .filter(|(name, main)| name != main)
+            .map(|(name, main)| (main.clone(), name.clone()))
+            .collect()
+    }
+}
+
+/// The naming style detected in a set of names, see [`GenomeBuild::detect_name_style`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleDetection {
+    style: Option<NameStyle>,
+    counts: [usize; 4],
+    total: usize,
+    common_prefix: String,
+}
+
+impl StyleDetection {
+    /// Get the style followed by most names, or `None` if no name follows any style.
+    ///
+    /// A tie between the [`NameStyle::Main`] style and another style goes to the other style,
+    /// e.g. to [`NameStyle::Ucsc`] for a build with the UCSC-style main names.
+    pub fn style(&self) -> Option<NameStyle> {
+        self.style
+    }
+
+    /// Get the fraction of the names that follow the detected style, between `0` and `1`.
+    pub fn confidence(&self) -> f64 {
+        match self.style {
+            Some(style) if self.total > 0 => self.count(style) as f64 / self.total as f64,
+            _ => 0.,
+        }
+    }
+
+    /// Get the number of the names that follow the `style`.
+    pub fn count(&self, style: NameStyle) -> usize {
+        self.counts[style_index(style)]
+    }
+
+    /// Get the number of the names.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Get the longest common prefix of the names, e.g. `chr` for `chr1`, `chr2`, ...
+    pub fn common_prefix(&self) -> &str {
+        &self.common_prefix
+    }
+}
+
+/// The renames of the contig names from one naming style to another,
+/// see [`GenomeBuild::plan_rename`].
+///
+/// The plan is checked when made, hence no two names are renamed to the same name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamePlan {
+    from: NameStyle,
+    to: NameStyle,
+    /// The pairs of the source and the target name of all contigs named in the `from` style.
+    mapping: BTreeMap<String, String>,
+    /// The source names of the contigs without a name in the `to` style.
+    kept: Vec<String>,
+}
+
+impl RenamePlan {
+    /// Get the style of the source names.
+    pub fn from(&self) -> NameStyle {
+        self.from
+    }
+
+    /// Get the style of the target names.
+    pub fn to(&self) -> NameStyle {
+        self.to
+    }
+
+    /// Get the target name of the source `name`,
+    /// or `None` if the `name` is not a contig name in the source style.
+    ///
+    /// The names in [`RenamePlan::kept`] are their own targets.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.mapping.get(name).map(String::as_str)
+    }
+
+    /// Get the pairs of the source and the target name of the names that change,
+    /// ordered by the source name.
+    pub fn renames(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.mapping
+            .iter()
+            .filter(|(source, target)| source != target)
+            .map(|(source, target)| (source.as_str(), target.as_str()))
+    }
+
+    /// Get the source names of the contigs that have no name in the target style,
+    /// and keep their name.
+    pub fn kept(&self) -> &[String] {
+        &self.kept
+    }
+
+    /// Get the number of the source names.
+    pub fn len(&self) -> usize {
+        self.mapping.len()
+    }
+
+    /// Test if the plan has no source names.
+    pub fn is_empty(&self) -> bool {
+        self.mapping.is_empty()
+    }
+}
+
+fn style_index(style: NameStyle) -> usize {
+    NameStyle::ALL
+        .iter()
+        .position(|&s| s == style)
+        .expect("All styles")
+}
+
+impl<C> GenomeBuild<C> {
+    /// Detect the naming style of user-supplied `names`, e.g. to decide on renaming them.
+    ///
+    /// A name of a contig of the build follows the styles in which it names the contig,
+    /// hence `1` follows the [`NameStyle::Main`] style (the Ensembl style of the human builds).
+    /// The names absent from the build are recognized by their shape,
+    /// e.g. `NC_000001.11` follows the [`NameStyle::RefSeq`] style,
+    /// and they never follow the [`NameStyle::Main`] style.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{GenomeBuild, NameStyle};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    ///
+    /// let detection = build.detect_name_style(["chr1", "chr2", "chrX", "chrZ", "MT"]);
+    /// assert_eq!(detection.style(), Some(NameStyle::Ucsc));
+    /// assert_eq!(detection.count(NameStyle::Ucsc), 4);
+    /// assert_eq!(detection.confidence(), 0.8);
+    /// assert_eq!(detection.common_prefix(), "");
+    ///
+    /// let detection = build.detect_name_style(["1", "22", "X"]);
+    /// assert_eq!(detection.style(), Some(NameStyle::Main));
+    /// ```
+    pub fn detect_name_style<I, S>(&self, names: I) -> StyleDetection
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut counts = [0; 4];
+        let mut total = 0;
+        let mut common_prefix: Option<String> = None;
+
+        for name in names {
+            let name = name.as_ref();
+            total += 1;
+            for (count, style) in counts.iter_mut().zip(NameStyle::ALL) {
+                let follows = match self.contig_by_name(name) {
+                    Some(contig) => contig.name_in(style) == Some(name),
+                    None => style != NameStyle::Main && style.matches(name),
+                };
+                *count += usize::from(follows);
+            }
+            match &mut common_prefix {
+                Some(prefix) => {
+                    let len = prefix
+                        .char_indices()
+                        .zip(name.chars())
+                        .find(|&((_, l), r)| l != r)
+                        .map_or(prefix.len().min(name.len()), |((i, _), _)| i);
+                    prefix.truncate(len);
+                }
+                None => common_prefix = Some(name.to_string()),
+            }
+        }
+
+        // The specific styles come first to win the ties with the main style.
+        let mut style = None;
+        let mut best = 0;
+        for candidate in [
+            NameStyle::Ucsc,
+            NameStyle::RefSeq,
+            NameStyle::GenBank,
+            NameStyle::Main,
+        ] {
+            if counts[style_index(candidate)] > best {
+                best = counts[style_index(candidate)];
+                style = Some(candidate);
+            }
+        }
+
+        StyleDetection {
+            style,
+            counts,
+            total,
+            common_prefix: common_prefix.unwrap_or_default(),
+        }
+    }
+
+    /// Plan the renames of the contig names in the `from` style to the names in the `to` style,
+    /// e.g. before rewriting the contig names of a file.
+    ///
+    /// The contigs without a name in the `from` style are left out,
+    /// and the contigs without a name in the `to` style keep their name.
+    ///
+    /// ## Errors
+    ///
+    /// If two source names would end up with the same name, e.g. if both `M` and `MT`
+    /// would be renamed to `chrM`. The error lists all such collisions.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{GenomeBuild, NameStyle};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let plan = build.plan_rename(NameStyle::Main, NameStyle::Ucsc).unwrap();
+    ///
+    /// assert_eq!(plan.get("1"), Some("chr1"));
+    /// assert_eq!(plan.get("MT"), Some("chrM"));
+    /// assert_eq!(plan.get("chr1"), None);
+    /// assert_eq!(plan.len(), build.contigs().count());
+    /// ```
+    pub fn plan_rename(&self, from: NameStyle, to: NameStyle) -> Result<RenamePlan, String> {
+        let mut mapping = BTreeMap::new();
+        let mut kept = vec![];
+        // The source names by the target name.
+        let mut sources: BTreeMap<&str, &str> = BTreeMap::new();
+        let mut collisions = vec![];
+
+        for contig in self.contigs() {
+            let Some(source) = contig.name_in(from) else {
+                continue;
+            };
+            let target = match contig.name_in(to) {
+                Some(target) => target,
+                None => {
+                    kept.push(source.to_string());
+                    source
+                }
+            };
+            match sources.get(target) {
+                Some(&other) => collisions.push(format!(
+                    "{other:?} and {source:?} would both be renamed to {target:?}"
+                )),
+                None => {
+                    sources.insert(target, source);
+                    mapping.insert(source.to_string(), target.to_string());
+                }
+            }
+        }
+
+        if collisions.is_empty() {
+            Ok(RenamePlan {
+                from,
+                to,
+                mapping,
+                kept,
+            })
+        } else {
+            Err(format!(
+                "Cannot rename {from} to {to} names: {}",
+                collisions.join(", ")
+            ))
+        }
+    }
+
+    /// Compare the sequence names of a file, e.g. of a tabix index, with the contigs of the build.
+    ///
+    /// See [`GenomeBuild::check_sequences`] for more info.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let check = build.check_names(["chr1", "chr2", "chrX"]);
+    ///
+    /// assert!(check.is_consistent());
+    /// // Query the indexed file with the main names of the build.
+    /// let query = check.query_map();
+    /// assert_eq!(query[2], ("X".to_string(), "chrX".to_string()));
+    /// ```
+    pub fn check_names<I, S>(&self, names: I) -> SequenceCheck<C>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        C: Copy + PartialEq,
+    {
+        self.check_sequences(names.into_iter().map(|name| (name, None)))
+    }
+
+    /// Compare the sequences of a file with the contigs of the build.
+    ///
+    /// The `sequences` are the pairs of the sequence name and, if known, the sequence length,
+    /// e.g. from the FASTA headers or from a tabix index.
+    /// The names are looked up in the build by any of the contig names.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{GenomeBuild, NameStyle};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let check = build.check_sequences([
+    ///     ("1", Some(248_956_422)),
+    ///     ("2", Some(242_193_528)),
+    ///     ("HLA-A*01:01:01:01", None),
+    /// ]);
+    ///
+    /// assert!(!check.is_consistent());
+    /// assert_eq!(check.unknown(), ["HLA-A*01:01:01:01"]);
+    /// assert_eq!(check.length_mismatches()[0].found, 242_193_528);
+    /// assert_eq!(check.styles(), [NameStyle::Main]);
+    /// assert_eq!(
+    ///     check.rename_map(NameStyle::Ucsc),
+    ///     [("1".to_string(), "chr1".to_string()), ("2".to_string(), "chr2".to_string())],
+    /// );
+    /// ```
+    pub fn check_sequences<I, S>(&self, sequences: I) -> SequenceCheck<C>
+    where
+        I: IntoIterator<Item = (S, Option<C>)>,
+        S: AsRef<str>,
+        C: Copy + PartialEq,
+    {
+        let mut check = SequenceCheck {
+            matched: vec![],
+            styled: vec![],
+            styles: NameStyle::ALL.to_vec(),
+            unknown: vec![],
+            missing: vec![],
+            length_mismatches: vec![],
+        };
+        let contigs: Vec<_> = self.contigs().collect();
+        let mut seen = vec![false; contigs.len()];
+
+        for (name, length) in sequences {
+            let name = name.as_ref();
+            let Some(i) = self.contig_index_by_name(name) else {
+                check.unknown.push(name.to_string());
+                continue;
+            };
+            let contig = contigs[i];
+            seen[i] = true;
+
+            if let Some(length) = length.filter(|length| length != contig.length()) {
+                check.length_mismatches.push(LengthMismatch {
+                    name: name.to_string(),
+                    expected: *contig.length(),
+                    found: length,
+                });
+            }
+            check
+                .styles
+                .retain(|&style| contig.name_in(style) == Some(name));
+            check
+                .styled
+                .push(NameStyle::ALL.map(|style| contig.name_in(style).map(String::from)));
+            check
+                .matched
+                .push((name.to_string(), contig.name().to_string()));
+        }
+
+        check.missing = contigs
+            .iter()
+            .zip(seen)
+            .filter(|&(_, seen)| !seen)
+            .map(|(contig, _)| contig.name().to_string())
+            .collect();
+        if check.matched.is_empty() {
+            check.styles.clear();
+        }
+        check
+    }
+}
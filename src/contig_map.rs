@@ -0,0 +1,136 @@
+//! # Per-contig values
+//!
+//! The module includes [`ContigMap`], a dense container with a value per contig
+//! of a genome build, such as a coverage or a count, see [`GenomeBuild::contig_map`].
+
+use alloc::vec::Vec;
+use core::{
+    hash::Hash,
+    ops::{Index, IndexMut},
+};
+
+use crate::{ContigKey, GenomeBuild};
+
+/// A value per contig of a genome build, stored densely in the order of [`GenomeBuild::contigs`]
+/// and indexed by [`ContigKey`].
+///
+/// The map does not borrow the build, hence it can be updated while the build is in use.
+/// Indexing with the key of another build panics, see [`ContigMap::get`] for the checked access.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContigMap<T> {
+    build: u64,
+    values: Vec<T>,
+}
+
+impl<T> ContigMap<T> {
+    /// Get the number of the values, one per contig.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Test if the build has no contigs.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Get the value of the contig `key`, or `None` if the key belongs to another build.
+    pub fn get(&self, key: ContigKey) -> Option<&T> {
+        if key.build() == self.build {
+            self.values.get(key.index())
+        } else {
+            None
+        }
+    }
+
+    /// Get the mutable value of the contig `key`, or `None` if the key belongs to another build.
+    pub fn get_mut(&mut self, key: ContigKey) -> Option<&mut T> {
+        if key.build() == self.build {
+            self.values.get_mut(key.index())
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the keys and the values in the order of [`GenomeBuild::contigs`].
+    pub fn iter(&self) -> impl Iterator<Item = (ContigKey, &T)> {
+        let build = self.build;
+        self.values
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (ContigKey::new(build, i as u32), value))
+    }
+
+    /// Iterate over the keys and the mutable values in the order of [`GenomeBuild::contigs`].
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (ContigKey, &mut T)> {
+        let build = self.build;
+        self.values
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, value)| (ContigKey::new(build, i as u32), value))
+    }
+
+    /// Get the values in the order of [`GenomeBuild::contigs`].
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Get the values in the order of [`GenomeBuild::contigs`], dropping the keys.
+    pub fn into_values(self) -> Vec<T> {
+        self.values
+    }
+}
+
+impl<T> Index<ContigKey> for ContigMap<T> {
+    type Output = T;
+
+    fn index(&self, key: ContigKey) -> &T {
+        self.get(key)
+            .expect("The contig key should belong to the build of the map")
+    }
+}
+
+impl<T> IndexMut<ContigKey> for ContigMap<T> {
+    fn index_mut(&mut self, key: ContigKey) -> &mut T {
+        self.get_mut(key)
+            .expect("The contig key should belong to the build of the map")
+    }
+}
+
+impl<C> GenomeBuild<C> {
+    /// Get a vector with the default value per contig, in the order of [`GenomeBuild::contigs`],
+    /// hence indexed by [`ContigKey::index`] or [`GenomeBuild::contig_index_by_name`].
+    pub fn make_vec<T: Default>(&self) -> Vec<T> {
+        self.contigs().map(|_| T::default()).collect()
+    }
+}
+
+impl<C: Hash> GenomeBuild<C> {
+    /// Get a map with the default value per contig, e.g. to count the records per contig.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    ///
+    /// let mut counts = build.contig_map::<u64>();
+    /// for name in ["chr1", "1", "chrX"] {
+    ///     counts[build.contig_key(name).expect("Known contig")] += 1;
+    /// }
+    ///
+    /// let counted: Vec<_> = counts
+    ///     .iter()
+    ///     .filter(|&(_, &count)| count > 0)
+    ///     .map(|(key, count)| (build.contig_by_key(key).map(|c| c.name()), *count))
+    ///     .collect();
+    /// assert_eq!(counted, [(Some("1"), 2), (Some("X"), 1)]);
+    /// ```
+    pub fn contig_map<T: Default>(&self) -> ContigMap<T> {
+        ContigMap {
+            build: self.fingerprint(),
+            values: self.make_vec(),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! # Cytogenetic bands
+//!
+//! The module includes [`Cytobands`], the Giemsa-stained bands of the chromosomes
+//! of a genome build, e.g. parsed by [`crate::formats::parse_cytobands`].
+//! The bands resolve the band notation, such as `1q21.1`,
+//! see [`GenomeBuild::parse_region_with_bands`], and describe the positions and regions
+//! in the ISCN-like notation, such as `1q21.1(145,000,000)`, see [`Cytobands::iscn`].
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{Display, Write},
+    ops::{Add, Sub},
+};
+
+use num_traits::{One, Zero};
+
+use crate::{karyotype::strip_chr, units::write_grouped, Contig, GenomeBuild, GenomicRegion};
+
+/// A cytogenetic band, such as `p36.33` of chromosome 1.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cytoband<C> {
+    region: GenomicRegion<C>,
+    name: String,
+    stain: String,
+}
+
+impl<C> Cytoband<C> {
+    /// Create the band `name`, including the arm, e.g. `q21.1`,
+    /// spanning the `region` and with the Giemsa `stain`, e.g. `gpos50` or `acen`.
+    pub fn new<T, U>(region: GenomicRegion<C>, name: T, stain: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        Cytoband {
+            region,
+            name: name.into(),
+            stain: stain.into(),
+        }
+    }
+
+    /// Get the region of the band.
+    pub fn region(&self) -> &GenomicRegion<C> {
+        &self.region
+    }
+
+    /// Get the band name, including the arm, e.g. `q21.1`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the Giemsa stain, e.g. `gneg`, `gpos50`, or `acen` for the centromere.
+    pub fn stain(&self) -> &str {
+        &self.stain
+    }
+}
+
+/// The cytogenetic bands of the contigs of a genome build.
+///
+/// The bands are keyed by the main name of the contig, and kept sorted by the start coordinate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cytobands<C> {
+    bands: BTreeMap<String, Vec<Cytoband<C>>>,
+}
+
+impl<C> Default for Cytobands<C> {
+    fn default() -> Self {
+        Cytobands {
+            bands: BTreeMap::new(),
+        }
+    }
+}
+
+impl<C> Cytobands<C> {
+    /// Create empty bands.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of the bands.
+    pub fn len(&self) -> usize {
+        self.bands.values().map(Vec::len).sum()
+    }
+
+    /// Test if there are no bands.
+    pub fn is_empty(&self) -> bool {
+        self.bands.is_empty()
+    }
+
+    /// Get the bands of the contig with the main `name`, sorted by the start coordinate.
+    pub fn contig_bands(&self, name: &str) -> &[Cytoband<C>] {
+        self.bands.get(name).map_or(&[], |bands| bands.as_slice())
+    }
+
+    /// Add the `band`.
+    pub fn insert(&mut self, band: Cytoband<C>)
+    where
+        C: PartialOrd,
+    {
+        let bands = self
+            .bands
+            .entry(String::from(band.region.contig().name()))
+            .or_default();
+        let i = bands.partition_point(|b| b.region.start() <= band.region.start());
+        bands.insert(i, band);
+    }
+
+    /// Get the band of the `contig` that includes the 0-based `position`.
+    pub fn band_at(&self, contig: &Contig<C>, position: &C) -> Option<&Cytoband<C>>
+    where
+        C: PartialOrd,
+    {
+        let bands = self.contig_bands(contig.name());
+        let i = bands.partition_point(|b| b.region.end() <= position);
+        bands.get(i).filter(|b| b.region.start() <= position)
+    }
+
+    /// Describe the 0-based `position` on the `contig` in the ISCN-like notation,
+    /// with the chromosome, the band, and the 1-based position, e.g. `1q21.1(145,000,000)`.
+    ///
+    /// The chromosome is the main contig name without the `chr` prefix.
+    /// Returns `None` if no band includes the position.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{Contig, Cytoband, Cytobands, GenomicRegion};
+    ///
+    /// let chr1 = Contig::new("chr1", &["1"], 248_956_422u32).unwrap();
+    /// let mut cytobands = Cytobands::new();
+    /// for (start, end, name) in [(143_200_000, 147_500_000, "q21.1"), (147_500_000, 150_600_000, "q21.2")] {
+    ///     let region = GenomicRegion::new(&chr1, start, end).unwrap();
+    ///     cytobands.insert(Cytoband::new(region, name, "gneg"));
+    /// }
+    ///
+    /// assert_eq!(cytobands.iscn_position(&chr1, 144_999_999).as_deref(), Some("1q21.1(145,000,000)"));
+    ///
+    /// let region = GenomicRegion::new(&chr1, 145_000_000, 148_000_000).unwrap();
+    /// assert_eq!(
+    ///     cytobands.iscn(&region).as_deref(),
+    ///     Some("1q21.1q21.2(145,000,001_148,000,000)"),
+    /// );
+    /// ```
+    pub fn iscn_position(&self, contig: &Contig<C>, position: C) -> Option<String>
+    where
+        C: Copy + PartialOrd + One + Add<Output = C> + Display,
+    {
+        let band = self.band_at(contig, &position)?;
+        let mut iscn = String::new();
+        write!(iscn, "{}{}(", strip_chr(contig.name()), band.name()).ok()?;
+        write_grouped(&mut iscn, &(position + C::one()).to_string()).ok()?;
+        iscn.push(')');
+        Some(iscn)
+    }
+
+    /// Describe the `region` in the ISCN-like notation, with the chromosome, the bands
+    /// of the first and the last base, and the 1-based closed coordinates,
+    /// e.g. `1q21.1q21.2(145,000,001_148,000,000)`, or `1q21.1(145,000,001_145,100,000)`
+    /// if the region is within a single band.
+    ///
+    /// See [`Cytobands::iscn_position`] for an example.
+    /// Returns `None` if no band includes the first or the last base of the region.
+    pub fn iscn(&self, region: &GenomicRegion<C>) -> Option<String>
+    where
+        C: Copy + PartialOrd + One + Add<Output = C> + Sub<Output = C> + Display,
+    {
+        let (start, end) = (*region.start(), *region.end());
+        let last = if end > start { end - C::one() } else { start };
+        let first = self.band_at(region.contig(), &start)?;
+        let last = self.band_at(region.contig(), &last)?;
+
+        let mut iscn = String::new();
+        write!(
+            iscn,
+            "{}{}",
+            strip_chr(region.contig().name()),
+            first.name()
+        )
+        .ok()?;
+        if last.name() != first.name() {
+            iscn.push_str(last.name());
+        }
+        iscn.push('(');
+        write_grouped(&mut iscn, &(start + C::one()).to_string()).ok()?;
+        iscn.push('_');
+        write_grouped(&mut iscn, &end.to_string()).ok()?;
+        iscn.push(')');
+        Some(iscn)
+    }
+
+    /// Resolve the band notation, such as `1q21.1` or `17p`, to the region spanning the bands.
+    ///
+    /// Returns `None` if the text does not name the bands of a contig of the `build`.
+    pub(crate) fn resolve(&self, build: &GenomeBuild<C>, text: &str) -> Option<GenomicRegion<C>>
+    where
+        C: Copy + Zero + PartialOrd,
+    {
+        // The contig names may include the arm letters, hence try every split before `p` or `q`.
+        text.match_indices(['p', 'q']).find_map(|(i, _)| {
+            let (name, band) = text.split_at(i);
+            let contig = build.contig_by_name(name)?;
+            // A band includes its sub-bands, e.g. `q21` includes `q21.1`, and `q2` includes `q21`.
+            let mut bands = self
+                .contig_bands(contig.name())
+                .iter()
+                .filter(|b| b.name().starts_with(band));
+            let first = bands.next()?;
+            let last = bands.last().unwrap_or(first);
+            GenomicRegion::new(contig, *first.region.start(), *last.region.end())
+        })
+    }
+}
@@ -0,0 +1,111 @@
+//! # Transparent decompression
+//!
+//! The text parsers accept gzip and BGZF-compressed inputs, recognized by the gzip magic bytes.
+//! The decompression requires the `bgzf` feature.
+
+use std::io::{self, BufRead, Read};
+
+/// The gzip magic bytes, shared by the BGZF files.
+pub(crate) const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A reader of plain or decompressed input.
+pub(crate) enum MaybeCompressed<R> {
+    Plain(R),
+    #[cfg(feature = "bgzf")]
+    Gzip(io::BufReader<flate2::bufread::MultiGzDecoder<R>>),
+}
+
+/// Wrap the `read` to decompress the input if it starts with the gzip magic bytes.
+///
+/// Without the `bgzf` feature, a compressed input is an error.
+pub(crate) fn decompressed<R>(mut read: R) -> io::Result<MaybeCompressed<R>>
+where
+    R: BufRead,
+{
+    if !read.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        return Ok(MaybeCompressed::Plain(read));
+    }
+
+    #[cfg(feature = "bgzf")]
+    {
+        Ok(MaybeCompressed::Gzip(io::BufReader::new(
+            flate2::bufread::MultiGzDecoder::new(read),
+        )))
+    }
+    #[cfg(not(feature = "bgzf"))]
+    {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Reading compressed input requires the `bgzf` feature",
+        ))
+    }
+}
+
+impl<R: BufRead> Read for MaybeCompressed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            MaybeCompressed::Plain(read) => read.read(buf),
+            #[cfg(feature = "bgzf")]
+            MaybeCompressed::Gzip(read) => read.read(buf),
+        }
+    }
+}
+
+impl<R: BufRead> BufRead for MaybeCompressed<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match self {
+            MaybeCompressed::Plain(read) => read.fill_buf(),
+            #[cfg(feature = "bgzf")]
+            MaybeCompressed::Gzip(read) => read.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match self {
+            MaybeCompressed::Plain(read) => read.consume(amt),
+            #[cfg(feature = "bgzf")]
+            MaybeCompressed::Gzip(read) => read.consume(amt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+
+    use super::decompressed;
+
+    #[test]
+    fn test_plain_input() {
+        let lines: Vec<_> = decompressed("chr1\t10\n".as_bytes())
+            .unwrap()
+            .lines()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(lines, ["chr1\t10"]);
+        assert!(decompressed(&b""[..]).is_ok());
+    }
+
+    #[cfg(feature = "bgzf")]
+    #[test]
+    fn test_gzip_input() {
+        use std::io::Write;
+
+        use flate2::{write::GzEncoder, Compression};
+
+        // Concatenated gzip members, as in BGZF.
+        let mut compressed = vec![];
+        for text in ["chr1\t10\n", "chr2\t20\n"] {
+            let mut encoder = GzEncoder::new(vec![], Compression::default());
+            encoder.write_all(text.as_bytes()).unwrap();
+            compressed.extend(encoder.finish().unwrap());
+        }
+
+        let lines: Vec<_> = decompressed(compressed.as_slice())
+            .unwrap()
+            .lines()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(lines, ["chr1\t10", "chr2\t20"]);
+    }
+}
@@ -0,0 +1,269 @@
+//! # Genome build digests
+//!
+//! The digests follow the [GA4GH](https://www.ga4gh.org/) refget and sequence collections
+//! (seqcol) conventions: an object is serialized into the canonical JSON
+//! ([RFC 8785](https://www.rfc-editor.org/rfc/rfc8785)) and digested with [`sha512t24u`].
+//!
+//! See [`GenomeBuild::seqcol_digests`] for the digests of a genome build.
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{Display, Write};
+
+use crate::GenomeBuild;
+
+/// The digests of a genome build computed from the contig names and lengths.
+///
+/// The contig sequences are not known, hence the `sequences` digest
+/// and the seqcol top-level digest cannot be computed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SeqColDigests {
+    /// The digest of the names array.
+    pub names: String,
+    /// The digest of the lengths array.
+    pub lengths: String,
+    /// The order-independent digest of the name-length pairs.
+    pub sorted_name_length_pairs: String,
+    /// The digest of the `lengths` and `names` digests.
+    ///
+    /// The fingerprint is computed like the seqcol top-level digest,
+    /// but from the names and lengths instead of the names and sequences.
+    /// Hence, it depends on the contig order.
+    pub fingerprint: String,
+}
+
+impl<C> GenomeBuild<C>
+where
+    C: Display,
+{
+    /// Compute the seqcol digests of the build.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use dabuild::{ContigOrdering, GenomeBuild, GenomeBuildIdentifier};
+    ///
+    /// let toy = |contigs: [(&str, u32); 2]| -> GenomeBuild<u32> {
+    ///     GenomeBuild::builder()
+    ///         .id(GenomeBuildIdentifier::from_str("toy").expect("Infallible"))
+    ///         .contig(contigs[0].0, contigs[0].1)
+    ///         .contig(contigs[1].0, contigs[1].1)
+    ///         .ordering(ContigOrdering::Insertion)
+    ///         .build()
+    ///         .expect("Valid build")
+    /// };
+    /// let ab = toy([("a", 100), ("b", 200)]).seqcol_digests();
+    /// let ba = toy([("b", 200), ("a", 100)]).seqcol_digests();
+    ///
+    /// // The fingerprint depends on the contig order, the name-length pairs do not.
+    /// assert_ne!(ab.fingerprint, ba.fingerprint);
+    /// assert_eq!(ab.sorted_name_length_pairs, ba.sorted_name_length_pairs);
+    /// ```
+    pub fn seqcol_digests(&self) -> SeqColDigests {
+        let mut names = String::from("[");
+        let mut lengths = String::from("[");
+        let mut pairs = Vec::new();
+        for (i, contig) in self.contigs().enumerate() {
+            if i > 0 {
+                names.push(',');
+                lengths.push(',');
+            }
+            push_json_str(&mut names, contig.name());
+            let length = format!("{}", contig.length());
+            lengths.push_str(&length);
+
+            let mut pair = format!("{{\"length\":{length},\"name\":");
+            push_json_str(&mut pair, contig.name());
+            pair.push('}');
+            pairs.push(sha512t24u(pair.as_bytes()));
+        }
+        names.push(']');
+        lengths.push(']');
+        pairs.sort_unstable();
+
+        let mut sorted_pairs = String::from("[");
+        for (i, pair) in pairs.iter().enumerate() {
+            if i > 0 {
+                sorted_pairs.push(',');
+            }
+            push_json_str(&mut sorted_pairs, pair);
+        }
+        sorted_pairs.push(']');
+
+        let names = sha512t24u(names.as_bytes());
+        let lengths = sha512t24u(lengths.as_bytes());
+        let fingerprint =
+            sha512t24u(format!("{{\"lengths\":\"{lengths}\",\"names\":\"{names}\"}}").as_bytes());
+        SeqColDigests {
+            names,
+            lengths,
+            sorted_name_length_pairs: sha512t24u(sorted_pairs.as_bytes()),
+            fingerprint,
+        }
+    }
+}
+
+/// Push the `value` as a canonical JSON string.
+fn push_json_str(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\u{8}' => json.push_str("\\b"),
+            '\u{c}' => json.push_str("\\f"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// Compute the GA4GH `sha512t24u` digest of the `bytes`:
+/// the first 24 bytes of the SHA-512 digest, encoded in URL-safe base64.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::digest::sha512t24u;
+///
+/// assert_eq!(sha512t24u(b"ACGT"), "aKF498dAxcJAqme6QYQ7EZ07-fiw8Kw2");
+/// ```
+pub fn sha512t24u(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let digest = sha512(bytes);
+    let mut encoded = String::with_capacity(32);
+    for chunk in digest[..24].chunks(3) {
+        let n = u32::from(chunk[0]) << 16 | u32::from(chunk[1]) << 8 | u32::from(chunk[2]);
+        for shift in [18, 12, 6, 0] {
+            encoded.push(char::from(ALPHABET[(n >> shift & 0x3f) as usize]));
+        }
+    }
+    encoded
+}
+
+/// The SHA-512 round constants.
+#[rustfmt::skip]
+const K: [u64; 80] = [
+    0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
+    0x3956c25bf348b538, 0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118,
+    0xd807aa98a3030242, 0x12835b0145706fbe, 0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235, 0xc19bf174cf692694,
+    0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5,
+    0x983e5152ee66dfab, 0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2, 0xd5a79147930aa725, 0x06ca6351e003826f, 0x142929670a0e6e70,
+    0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed, 0x53380d139d95b3df,
+    0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+    0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30,
+    0xd192e819d6ef5218, 0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8, 0x1e376c085141ab53, 0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373, 0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+    0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b,
+    0xca273eceea26619c, 0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178,
+    0x06f067aa72176fba, 0x0a637dc5a2c898a6, 0x113f9804bef90dae, 0x1b710b35131c471b,
+    0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc, 0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
+];
+
+/// Compute the SHA-512 digest of the `bytes`.
+fn sha512(bytes: &[u8]) -> [u8; 64] {
+    let mut state: [u64; 8] = [
+        0x6a09e667f3bcc908,
+        0xbb67ae8584caa73b,
+        0x3c6ef372fe94f82b,
+        0xa54ff53a5f1d36f1,
+        0x510e527fade682d1,
+        0x9b05688c2b3e6c1f,
+        0x1f83d9abfb41bd6b,
+        0x5be0cd19137e2179,
+    ];
+
+    // Pad the message with `1`, zeros, and the 128-bit message length in bits.
+    let mut message = Vec::with_capacity(bytes.len() + 256);
+    message.extend_from_slice(bytes);
+    message.push(0x80);
+    while message.len() % 128 != 112 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((bytes.len() as u128) * 8).to_be_bytes());
+
+    for block in message.chunks_exact(128) {
+        let mut w = [0u64; 80];
+        for (i, word) in block.chunks_exact(8).enumerate() {
+            w[i] = u64::from_be_bytes(word.try_into().expect("8 bytes"));
+        }
+        for i in 16..80 {
+            let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+            let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for i in 0..80 {
+            let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (s, x) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *s = s.wrapping_add(x);
+        }
+    }
+
+    let mut digest = [0; 64];
+    for (chunk, s) in digest.chunks_exact_mut(8).zip(state) {
+        chunk.copy_from_slice(&s.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sha512, sha512t24u};
+
+    #[test]
+    fn test_sha512() {
+        let digest = sha512(b"abc");
+        assert_eq!(
+            digest[..8],
+            [0xdd, 0xaf, 0x35, 0xa1, 0x93, 0x61, 0x7a, 0xba]
+        );
+        assert_eq!(
+            digest[56..],
+            [0x2a, 0x9a, 0xc9, 0x4f, 0xa5, 0x4c, 0xa4, 0x9f]
+        );
+    }
+
+    #[test]
+    fn test_sha512t24u_of_empty_sequence() {
+        // The refget digest of an empty sequence.
+        assert_eq!(sha512t24u(b""), "z4PhNX7vuL3xVChQ1m2AB9Yg5AULVxXc");
+    }
+}
@@ -0,0 +1,738 @@
+//! # Download assembly reports from NCBI
+//!
+//! The assembly reports of the assemblies deposited in GenBank (`GCA_`)
+//! or RefSeq (`GCF_`) are downloaded from the
+//! [NCBI genomes FTP site](https://ftp.ncbi.nlm.nih.gov/genomes/all/) over HTTPS.
+//!
+//! The report of an accession, such as `GCF_000001405.40`, is stored in a directory
+//! named after the accession and the assembly name (e.g. `GCF_000001405.40_GRCh38.p14`).
+//! The assembly name is not known in advance, hence it is looked up in the listing
+//! of the parent directory first.
+//!
+//! Use [`BuildCache`] to keep the downloaded reports on the disk,
+//! and [`Downloader`] to retry the failed downloads and to report the download progress.
+
+use std::{
+    env,
+    error::Error,
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use num_traits::Zero;
+
+use crate::{
+    builds::parse_assembly_report_bytes, digest::sha512t24u, GenomeBuild, GenomeBuildIdentifier,
+};
+
+/// The base URL of the NCBI genomes site.
+pub const NCBI_GENOMES_URL: &str = "https://ftp.ncbi.nlm.nih.gov/genomes/all";
+
+/// Check that the `accession` is a versioned GenBank or RefSeq assembly accession,
+/// e.g. `GCF_000001405.40`.
+///
+/// ## Errors
+///
+/// If the accession is not of the form `GCA_` or `GCF_`, 9 digits, a dot, and the version.
+pub fn check_accession(accession: &str) -> Result<(), String> {
+    let valid = accession
+        .split_once('.')
+        .and_then(|(accession, version)| {
+            let digits = accession
+                .strip_prefix("GCA_")
+                .or_else(|| accession.strip_prefix("GCF_"))?;
+            Some(
+                digits.len() == 9
+                    && digits.bytes().all(|b| b.is_ascii_digit())
+                    && !version.is_empty()
+                    && version.bytes().all(|b| b.is_ascii_digit()),
+            )
+        })
+        .unwrap_or(false);
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid assembly accession {accession:?}"))
+    }
+}
+
+/// Get the URL of the directory that includes the assembly directory of the `accession`.
+///
+/// For instance, `GCF_000001405.40` is in `https://ftp.ncbi.nlm.nih.gov/genomes/all/GCF/000/001/405`.
+///
+/// ## Errors
+///
+/// If the accession is invalid, see [`check_accession`].
+pub fn assembly_parent_url(accession: &str) -> Result<String, String> {
+    check_accession(accession)?;
+    let (prefix, digits) = (&accession[..3], &accession[4..13]);
+    Ok(format!(
+        "{NCBI_GENOMES_URL}/{prefix}/{}/{}/{}",
+        &digits[..3],
+        &digits[3..6],
+        &digits[6..]
+    ))
+}
+
+/// The accessions (without the version) of the well-known assemblies.
+const KNOWN_ASSEMBLIES: &[(&str, &str)] = &[
+    ("GRCh37", "GCF_000001405"),
+    ("GRCh38", "GCF_000001405"),
+    ("GRCm38", "GCF_000001635"),
+    ("GRCm39", "GCF_000001635"),
+];
+
+/// The accessions of the assemblies of the common UCSC databases.
+const UCSC_ACCESSIONS: &[(&str, &str)] = &[
+    ("hg19", "GCF_000001405.13"),
+    ("hg38", "GCF_000001405.26"),
+    ("mm10", "GCF_000001635.20"),
+    ("mm39", "GCF_000001635.27"),
+    ("rn6", "GCF_000001895.5"),
+    ("danRer11", "GCF_000002035.6"),
+    ("dm6", "GCF_000001215.4"),
+    ("ce11", "GCF_000002985.6"),
+    ("sacCer3", "GCF_000146045.2"),
+];
+
+/// The URL of the UCSC Genome Browser API listing the UCSC databases.
+pub const UCSC_GENOMES_URL: &str = "https://api.genome.ucsc.edu/list/ucscGenomes";
+
+/// Resolve the UCSC database name (e.g. `hg38`) to the accession of the assembly
+/// (e.g. `GCF_000001405.26`).
+///
+/// The common databases are resolved without network access.
+/// Other databases are looked up in the UCSC Genome Browser API,
+/// which provides the GenBank accessions (e.g. `GCA_000001405.15`).
+///
+/// ## Errors
+///
+/// * Unknown database
+/// * Network error
+pub fn resolve_ucsc_name(name: &str) -> Result<String, Box<dyn Error>> {
+    if let Some((_, accession)) = UCSC_ACCESSIONS.iter().find(|(db, _)| *db == name) {
+        return Ok(accession.to_string());
+    }
+
+    let genomes = get(UCSC_GENOMES_URL)?;
+    find_ucsc_accession(&genomes, name)
+        .map(String::from)
+        .ok_or_else(|| format!("No assembly accession found for UCSC database {name:?}").into())
+}
+
+/// Find the accession in the description of the UCSC database `name`
+/// in the JSON listing of the UCSC `genomes`.
+fn find_ucsc_accession<'a>(genomes: &'a str, name: &str) -> Option<&'a str> {
+    let start = genomes.find(&format!("\"{name}\":{{"))?;
+    let end = start + genomes[start..].find('}')?;
+    let description = &genomes[start..end];
+
+    description
+        .match_indices("GC")
+        .map(|(i, _)| {
+            let candidate = &description[i..];
+            let len = candidate
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(candidate.len());
+            &candidate[..len]
+        })
+        .find(|candidate| check_accession(candidate).is_ok())
+}
+
+/// A release of an assembly, such as a patch.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AssemblyRelease {
+    version: u32,
+    accession: String,
+    id: GenomeBuildIdentifier,
+}
+
+impl AssemblyRelease {
+    /// Get the versioned accession, e.g. `GCF_000001405.40`.
+    pub fn accession(&self) -> &str {
+        &self.accession
+    }
+
+    /// Get the build identifier, e.g. `GRCh38` and `p14`.
+    pub fn id(&self) -> &GenomeBuildIdentifier {
+        &self.id
+    }
+}
+
+/// List the releases of the `assembly`, the oldest first.
+///
+/// See [`crate::builds::list_patches`].
+pub(crate) fn list_releases(assembly: &str) -> Result<Vec<AssemblyRelease>, Box<dyn Error>> {
+    let (base, major) = resolve_assembly(assembly)?;
+    let listing = get(&format!("{}/", assembly_parent_url(&format!("{base}.1"))?))?;
+    Ok(parse_releases(&listing, base, major))
+}
+
+/// Get the accession without the version and the major assembly name, if known.
+fn resolve_assembly(assembly: &str) -> Result<(&str, Option<&str>), String> {
+    if let Some(&(major, base)) = KNOWN_ASSEMBLIES
+        .iter()
+        .find(|(major, _)| major.eq_ignore_ascii_case(assembly))
+    {
+        Ok((base, Some(major)))
+    } else {
+        check_accession(&format!("{assembly}.1"))
+            .map(|_| (assembly, None))
+            .map_err(|_| format!("Unknown assembly {assembly:?}"))
+    }
+}
+
+/// Find the releases of the `base` accession in the HTML directory `listing`,
+/// optionally limited to the `major` assembly.
+fn parse_releases(listing: &str, base: &str, major: Option<&str>) -> Vec<AssemblyRelease> {
+    let needle = format!("href=\"{base}.");
+    let mut releases: Vec<_> = listing
+        .match_indices(&needle)
+        .filter_map(|(start, _)| {
+            let start = start + "href=\"".len();
+            let end = start + listing[start..].find(['/', '"'])?;
+            let directory = &listing[start..end];
+            let rest = directory.strip_prefix(base)?.strip_prefix('.')?;
+            let (version, _) = rest.split_once('_')?;
+            let accession = &directory[..base.len() + 1 + version.len()];
+            let version = version.parse().ok()?;
+            Some(AssemblyRelease {
+                version,
+                accession: accession.to_string(),
+                id: identifier_from_directory(directory),
+            })
+        })
+        .filter(|release| major.is_none_or(|major| release.id.major_assembly() == major))
+        .collect();
+    releases.sort();
+    releases.dedup();
+    releases
+}
+
+/// Find the assembly directory name of the `accession`
+/// (e.g. `GCF_000001405.40_GRCh38.p14`) in the HTML directory `listing`.
+fn find_assembly_directory<'a>(listing: &'a str, accession: &str) -> Option<&'a str> {
+    let needle = format!("href=\"{accession}_");
+    let start = listing.find(&needle)? + "href=\"".len();
+    let end = start + listing[start..].find(['/', '"'])?;
+    Some(&listing[start..end])
+}
+
+/// Get the URL of the assembly report of the `accession`.
+///
+/// The function queries the NCBI site to find the assembly name.
+///
+/// ## Errors
+///
+/// * Invalid accession, see [`check_accession`]
+/// * Network error
+/// * Unknown accession
+pub fn assembly_report_url(accession: &str) -> Result<String, Box<dyn Error>> {
+    Downloader::new()
+        .locate_assembly_report(accession)
+        .map(|(_, url)| url)
+}
+
+/// Get the build identifier from the assembly directory name,
+/// e.g. `GRCh38` and `p14` from `GCF_000001405.40_GRCh38.p14`.
+fn identifier_from_directory(directory: &str) -> GenomeBuildIdentifier {
+    // Skip the accession prefix, the digits, and the version.
+    directory
+        .splitn(3, '_')
+        .nth(2)
+        .unwrap_or(directory)
+        .parse()
+        .expect("Infallible")
+}
+
+/// Download the assembly report of the `accession`, e.g. `GCF_000001405.40`.
+///
+/// The function blocks until the report is downloaded.
+/// Use [`Downloader::download_assembly_report`] to retry the failed downloads.
+///
+/// ## Errors
+///
+/// * Invalid or unknown accession
+/// * Network error
+pub fn download_assembly_report(accession: &str) -> Result<String, Box<dyn Error>> {
+    Downloader::new().download_assembly_report(accession)
+}
+
+/// Observe the downloads of a [`Downloader`], e.g. to show a progress bar.
+///
+/// The methods do nothing by default.
+pub trait DownloadObserver {
+    /// Called after receiving a chunk of the `url` body with the number of bytes
+    /// `downloaded` so far and the `total` number of bytes, if known.
+    fn on_progress(&self, url: &str, downloaded: u64, total: Option<u64>) {
+        let _ = (url, downloaded, total);
+    }
+
+    /// Called when the download of the `url` failed with the `error`
+    /// and is about to be retried after the `delay`.
+    ///
+    /// The retries are numbered from 1.
+    fn on_retry(&self, url: &str, retry: u32, delay: Duration, error: &dyn Error) {
+        let _ = (url, retry, delay, error);
+    }
+}
+
+/// Downloads the files from the NCBI site, retrying the failed downloads.
+///
+/// Only the transient failures, such as a connection failure, a timeout,
+/// or a server error, are retried. The delay before a retry starts at the backoff
+/// and doubles with each retry.
+///
+/// By default, the failed downloads are not retried.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use dabuild::fetch::{DownloadObserver, Downloader};
+///
+/// struct Progress;
+///
+/// impl DownloadObserver for Progress {
+///     fn on_progress(&self, url: &str, downloaded: u64, total: Option<u64>) {
+///         eprintln!("{url}: {downloaded}/{}", total.unwrap_or_default());
+///     }
+/// }
+///
+/// let downloader = Downloader::new()
+///     .retries(3)
+///     .backoff(Duration::from_secs(2))
+///     .observer(Progress);
+/// let report = downloader
+///     .download_assembly_report("GCF_000001405.40")
+///     .expect("Downloaded report");
+/// ```
+#[derive(Clone)]
+pub struct Downloader {
+    retries: u32,
+    backoff: Duration,
+    observer: Option<Arc<dyn DownloadObserver + Send + Sync>>,
+}
+
+impl Downloader {
+    /// Create a downloader with no retries and the backoff of one second.
+    pub fn new() -> Self {
+        Downloader {
+            retries: 0,
+            backoff: Duration::from_secs(1),
+            observer: None,
+        }
+    }
+
+    /// Set the maximum number of retries of a failed download.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Set the delay before the first retry.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set the observer of the downloads.
+    pub fn observer<O>(mut self, observer: O) -> Self
+    where
+        O: DownloadObserver + Send + Sync + 'static,
+    {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Download the assembly report of the `accession`, e.g. `GCF_000001405.40`.
+    ///
+    /// ## Errors
+    ///
+    /// * Invalid or unknown accession
+    /// * Network error, after the retries are exhausted
+    pub fn download_assembly_report(&self, accession: &str) -> Result<String, Box<dyn Error>> {
+        let (_, url) = self.locate_assembly_report(accession)?;
+        self.download(&url)
+    }
+
+    /// Download the `url` body as text.
+    ///
+    /// ## Errors
+    ///
+    /// Network error, after the retries are exhausted.
+    pub fn download(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        let mut retry = 0;
+        loop {
+            match self.try_download(url) {
+                Ok(body) => return Ok(body),
+                Err(e) if retry < self.retries && is_transient(&e) => {
+                    retry += 1;
+                    let delay = self.delay(retry);
+                    if let Some(observer) = &self.observer {
+                        observer.on_retry(url, retry, delay, &e);
+                    }
+                    thread::sleep(delay);
+                }
+                Err(e) => return Err(format!("Cannot download {url}: {e}").into()),
+            }
+        }
+    }
+
+    fn try_download(&self, url: &str) -> Result<String, ureq::Error> {
+        let mut response = ureq::get(url).call()?;
+        let total = response.body().content_length();
+        let mut reader = response.body_mut().as_reader();
+
+        let mut body = Vec::new();
+        let mut buffer = [0; 64 * 1024];
+        loop {
+            let n = reader.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            body.extend_from_slice(&buffer[..n]);
+            if let Some(observer) = &self.observer {
+                observer.on_progress(url, body.len() as u64, total);
+            }
+        }
+
+        String::from_utf8(body)
+            .map_err(|e| ureq::Error::Io(io::Error::new(io::ErrorKind::InvalidData, e)))
+    }
+
+    /// Get the delay before the `retry`, numbered from 1.
+    fn delay(&self, retry: u32) -> Duration {
+        self.backoff
+            .saturating_mul(1 << retry.saturating_sub(1).min(16))
+    }
+
+    /// Get the assembly directory name and the URL of the assembly report of the `accession`.
+    fn locate_assembly_report(&self, accession: &str) -> Result<(String, String), Box<dyn Error>> {
+        let parent = assembly_parent_url(accession)?;
+        let listing = self.download(&format!("{parent}/"))?;
+        let directory = find_assembly_directory(&listing, accession)
+            .ok_or_else(|| format!("No assembly found for accession {accession:?}"))?;
+
+        let url = format!("{parent}/{directory}/{directory}_assembly_report.txt");
+        Ok((directory.to_string(), url))
+    }
+
+    /// Download the assembly report of the `accession` along with the build identifier
+    /// derived from the assembly name.
+    pub(crate) fn download_identified_report(
+        &self,
+        accession: &str,
+    ) -> Result<(GenomeBuildIdentifier, String), Box<dyn Error>> {
+        let (directory, url) = self.locate_assembly_report(accession)?;
+        Ok((identifier_from_directory(&directory), self.download(&url)?))
+    }
+}
+
+impl Default for Downloader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Downloader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Downloader")
+            .field("retries", &self.retries)
+            .field("backoff", &self.backoff)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+/// Test if the download may succeed when retried.
+fn is_transient(error: &ureq::Error) -> bool {
+    match error {
+        ureq::Error::StatusCode(code) => *code >= 500 || *code == 408 || *code == 429,
+        ureq::Error::Io(_)
+        | ureq::Error::Timeout(_)
+        | ureq::Error::HostNotFound
+        | ureq::Error::ConnectionFailed
+        | ureq::Error::BodyStalled => true,
+        _ => false,
+    }
+}
+
+/// An on-disk cache of the assembly reports downloaded from NCBI.
+///
+/// The reports are stored in the cache directory, keyed by the accession,
+/// along with their [`sha512t24u`] checksums.
+/// A report is downloaded again if its checksum does not match.
+/// In the offline mode, the cache never downloads and fails on missing reports.
+/// The reports are downloaded with the [`Downloader`] set by [`BuildCache::downloader`].
+///
+/// ## Example
+///
+/// Load a report that was stored in the cache beforehand, e.g. when provisioning
+/// a machine without network access:
+///
+/// ```rust
+/// use dabuild::GenomeBuild;
+/// use dabuild::fetch::BuildCache;
+///
+/// # let dir = std::env::temp_dir().join("dabuild-doc-cache");
+/// let cache = BuildCache::new(&dir).offline(true);
+/// let report = std::fs::read_to_string("data/GCF_000001635.27_GRCm39_assembly_report.txt")
+///     .expect("File not found");
+/// cache.store("GCF_000001635.27", &report).expect("Writable cache");
+///
+/// let build: GenomeBuild<u32> = cache.load("GCF_000001635.27").expect("Cached report");
+/// assert_eq!(build.id().major_assembly(), "GRCm39");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BuildCache {
+    dir: PathBuf,
+    offline: bool,
+    downloader: Downloader,
+}
+
+impl BuildCache {
+    /// Create a cache in the `dir`.
+    ///
+    /// The directory is created when the first report is stored.
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        BuildCache {
+            dir: dir.as_ref().to_path_buf(),
+            offline: false,
+            downloader: Downloader::new(),
+        }
+    }
+
+    /// Get the default cache directory:
+    /// `$XDG_CACHE_HOME/dabuild` or `$HOME/.cache/dabuild`.
+    pub fn default_dir() -> Option<PathBuf> {
+        env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+            .map(|cache| cache.join("dabuild"))
+    }
+
+    /// Set the offline mode.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Set the downloader of the missing reports.
+    pub fn downloader(mut self, downloader: Downloader) -> Self {
+        self.downloader = downloader;
+        self
+    }
+
+    /// Get the cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get the path of the assembly report of the `accession`, regardless of it being cached.
+    pub fn report_path(&self, accession: &str) -> PathBuf {
+        self.dir.join(format!("{accession}_assembly_report.txt"))
+    }
+
+    fn checksum_path(&self, accession: &str) -> PathBuf {
+        self.dir
+            .join(format!("{accession}_assembly_report.txt.sha512t24u"))
+    }
+
+    /// Test if the assembly report of the `accession` is cached and matches its checksum.
+    pub fn contains(&self, accession: &str) -> bool {
+        match (
+            fs::read(self.report_path(accession)),
+            fs::read_to_string(self.checksum_path(accession)),
+        ) {
+            (Ok(report), Ok(checksum)) => sha512t24u(&report) == checksum.trim(),
+            _ => false,
+        }
+    }
+
+    /// Store the assembly `report` of the `accession` along with its checksum
+    /// and get the path of the stored report.
+    ///
+    /// ## Errors
+    ///
+    /// * Invalid accession, see [`check_accession`]
+    /// * I/O error
+    pub fn store(&self, accession: &str, report: &str) -> Result<PathBuf, Box<dyn Error>> {
+        check_accession(accession)?;
+        fs::create_dir_all(&self.dir)?;
+        let path = self.report_path(accession);
+        // Write into temporary files first to never leave a partial report in the cache.
+        write_atomically(
+            &self.checksum_path(accession),
+            &sha512t24u(report.as_bytes()),
+        )?;
+        write_atomically(&path, report)?;
+        Ok(path)
+    }
+
+    /// Get the path of the cached assembly report of the `accession`,
+    /// downloading the report if it is missing or does not match its checksum.
+    ///
+    /// ## Errors
+    ///
+    /// * Invalid or unknown accession
+    /// * Missing report in the offline mode
+    /// * Network or I/O error
+    pub fn fetch(&self, accession: &str) -> Result<PathBuf, Box<dyn Error>> {
+        check_accession(accession)?;
+        if self.contains(accession) {
+            Ok(self.report_path(accession))
+        } else if self.offline {
+            Err(format!("Assembly report of {accession} is not cached").into())
+        } else {
+            self.store(
+                accession,
+                &self.downloader.download_assembly_report(accession)?,
+            )
+        }
+    }
+
+    /// Load the genome build of the `accession` from the cache,
+    /// downloading the assembly report if necessary.
+    ///
+    /// The build identifier is read from the `# Assembly name:` header line of the report.
+    ///
+    /// ## Errors
+    ///
+    /// The errors of [`BuildCache::fetch`], and an invalid assembly report.
+    pub fn load<C>(&self, accession: &str) -> Result<GenomeBuild<C>, Box<dyn Error>>
+    where
+        C: FromStr + Zero + PartialOrd,
+    {
+        let report = fs::read_to_string(self.fetch(accession)?)?;
+        let id = report
+            .lines()
+            .take_while(|line| line.starts_with('#'))
+            .find_map(|line| line.strip_prefix("# Assembly name:"))
+            .map(|name| name.trim().parse().expect("Infallible"))
+            .unwrap_or_else(|| accession.parse().expect("Infallible"));
+        parse_assembly_report_bytes(id, report.as_bytes())
+    }
+}
+
+fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    fs::write(&partial, contents)?;
+    fs::rename(&partial, path)
+}
+
+fn get(url: &str) -> Result<String, Box<dyn Error>> {
+    Downloader::new().download(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        assembly_parent_url, check_accession, find_assembly_directory, find_ucsc_accession,
+        identifier_from_directory, is_transient, parse_releases, Downloader,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_check_accession() {
+        assert!(check_accession("GCF_000001405.40").is_ok());
+        assert!(check_accession("GCA_000001635.9").is_ok());
+
+        for accession in [
+            "GCF_000001405",
+            "GCX_000001405.40",
+            "GCF_0000014.40",
+            "GRCh38",
+        ] {
+            assert!(check_accession(accession).is_err(), "{accession}");
+        }
+    }
+
+    #[test]
+    fn test_assembly_parent_url() {
+        assert_eq!(
+            assembly_parent_url("GCF_000001405.40").unwrap(),
+            "https://ftp.ncbi.nlm.nih.gov/genomes/all/GCF/000/001/405"
+        );
+    }
+
+    #[test]
+    fn test_find_assembly_directory() {
+        let listing = r#"<a href="GCF_000001405.39_GRCh38.p13/">GCF_000001405.39_GRCh38.p13/</a>
+<a href="GCF_000001405.40_GRCh38.p14/">GCF_000001405.40_GRCh38.p14/</a>"#;
+
+        assert_eq!(
+            find_assembly_directory(listing, "GCF_000001405.40"),
+            Some("GCF_000001405.40_GRCh38.p14")
+        );
+        assert_eq!(find_assembly_directory(listing, "GCF_000001405.4"), None);
+    }
+
+    #[test]
+    fn test_parse_releases() {
+        let listing = r#"<a href="GCF_000001405.25_GRCh37.p13/">GCF_000001405.25_GRCh37.p13/</a>
+<a href="GCF_000001405.26_GRCh38/">GCF_000001405.26_GRCh38/</a>
+<a href="GCF_000001405.40_GRCh38.p14/">GCF_000001405.40_GRCh38.p14/</a>
+<a href="GCF_000001405.9_NCBI34/">GCF_000001405.9_NCBI34/</a>"#;
+
+        let releases = parse_releases(listing, "GCF_000001405", Some("GRCh38"));
+        let releases: Vec<_> = releases
+            .iter()
+            .map(|r| (r.accession(), r.id().patch()))
+            .collect();
+        assert_eq!(
+            releases,
+            vec![
+                ("GCF_000001405.26", None),
+                ("GCF_000001405.40", Some("p14"))
+            ]
+        );
+
+        let releases = parse_releases(listing, "GCF_000001405", None);
+        assert_eq!(releases.len(), 4);
+        assert_eq!(releases[0].accession(), "GCF_000001405.9");
+    }
+
+    #[test]
+    fn test_retries() {
+        let downloader = Downloader::new().backoff(Duration::from_millis(500));
+        assert_eq!(downloader.delay(1), Duration::from_millis(500));
+        assert_eq!(downloader.delay(2), Duration::from_secs(1));
+        assert_eq!(downloader.delay(4), Duration::from_secs(4));
+
+        assert!(is_transient(&ureq::Error::StatusCode(503)));
+        assert!(is_transient(&ureq::Error::StatusCode(429)));
+        assert!(is_transient(&ureq::Error::ConnectionFailed));
+        assert!(!is_transient(&ureq::Error::StatusCode(404)));
+        assert!(!is_transient(&ureq::Error::BadUri("ftp:".into())));
+    }
+
+    #[test]
+    fn test_find_ucsc_accession() {
+        let genomes = r#"{"ucscGenomes":{"hg38":{"description":"Dec. 2013 (GRCh38/hg38)","sourceName":"Genome Reference Consortium Human GRCh38.p14 (GCA_000001405.29)"},"canFam6":{"sourceName":"Dog10K_Boxer_Tasha (GCA_000002285.4)"}}}"#;
+
+        assert_eq!(
+            find_ucsc_accession(genomes, "canFam6"),
+            Some("GCA_000002285.4")
+        );
+        assert_eq!(find_ucsc_accession(genomes, "canFam5"), None);
+    }
+
+    #[test]
+    fn test_identifier_from_directory() {
+        let id = identifier_from_directory("GCF_000001405.40_GRCh38.p14");
+        assert_eq!(id.major_assembly(), "GRCh38");
+        assert_eq!(id.patch(), Some("p14"));
+
+        let id = identifier_from_directory("GCF_000001635.27_GRCm39");
+        assert_eq!(id.major_assembly(), "GRCm39");
+        assert_eq!(id.patch(), None);
+    }
+}
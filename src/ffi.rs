@@ -0,0 +1,176 @@
+//! # C bindings
+//!
+//! The `ffi` feature exposes functions to load genome builds and to look up contigs
+//! from C/C++. The declarations are in `include/dabuild.h`.
+//!
+//! Build a shared or static library with:
+//!
+//! ```shell
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! cargo rustc --release --features ffi --crate-type staticlib
+//! ```
+//!
+//! The genome builds are returned as opaque pointers that must be released
+//! with [`dabuild_genome_build_free`]. The contig lengths are represented as `uint64_t`.
+
+use std::{
+    ffi::{c_char, c_int, CStr},
+    fs::File,
+    io::BufReader,
+    ptr,
+    str::FromStr,
+};
+
+use crate::{
+    builds::{get_grch37_p13, get_grch38_p13, parse_assembly_report},
+    GenomeBuild, GenomeBuildIdentifier,
+};
+
+/// An opaque genome build handle.
+pub struct DabuildGenomeBuild(GenomeBuild<u64>);
+
+fn into_handle(build: GenomeBuild<u64>) -> *mut DabuildGenomeBuild {
+    Box::into_raw(Box::new(DabuildGenomeBuild(build)))
+}
+
+/// Get the *GRCh37.p13* build.
+#[no_mangle]
+pub extern "C" fn dabuild_get_grch37_p13() -> *mut DabuildGenomeBuild {
+    into_handle(get_grch37_p13())
+}
+
+/// Get the *GRCh38.p13* build.
+#[no_mangle]
+pub extern "C" fn dabuild_get_grch38_p13() -> *mut DabuildGenomeBuild {
+    into_handle(get_grch38_p13())
+}
+
+/// Parse the assembly report at `path` into a genome build.
+///
+/// The `patch` can be null. Returns null if the report cannot be read or parsed.
+///
+/// # Safety
+///
+/// `path` and `major_assembly` must be valid NUL-terminated strings.
+/// `patch` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn dabuild_parse_assembly_report(
+    path: *const c_char,
+    major_assembly: *const c_char,
+    patch: *const c_char,
+) -> *mut DabuildGenomeBuild {
+    if path.is_null() || major_assembly.is_null() {
+        return ptr::null_mut();
+    }
+    let (Ok(path), Ok(major_assembly)) = (
+        CStr::from_ptr(path).to_str(),
+        CStr::from_ptr(major_assembly).to_str(),
+    ) else {
+        return ptr::null_mut();
+    };
+    let id = if patch.is_null() {
+        GenomeBuildIdentifier::from_str(major_assembly).expect("Infallible")
+    } else {
+        match CStr::from_ptr(patch).to_str() {
+            Ok(patch) => GenomeBuildIdentifier::from((major_assembly, patch)),
+            Err(_) => return ptr::null_mut(),
+        }
+    };
+
+    match File::open(path)
+        .map_err(Into::into)
+        .and_then(|file| parse_assembly_report(id, BufReader::new(file)))
+    {
+        Ok(build) => into_handle(build),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Release the genome build. Null is ignored.
+///
+/// # Safety
+///
+/// `build` must be null or a pointer returned by this library that was not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn dabuild_genome_build_free(build: *mut DabuildGenomeBuild) {
+    if !build.is_null() {
+        drop(Box::from_raw(build));
+    }
+}
+
+/// Get the number of contigs of the genome build.
+///
+/// # Safety
+///
+/// `build` must be a valid genome build pointer.
+#[no_mangle]
+pub unsafe extern "C" fn dabuild_genome_build_contig_count(
+    build: *const DabuildGenomeBuild,
+) -> usize {
+    (*build).0.contigs().count()
+}
+
+/// Look up the length of the contig with the main or alternative `name`.
+///
+/// Returns `0` and stores the length into `length` on success,
+/// or `-1` if the contig is unknown or the name is not valid UTF-8.
+///
+/// # Safety
+///
+/// `build` must be a valid genome build pointer, `name` must be a valid NUL-terminated string,
+/// and `length` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn dabuild_contig_length(
+    build: *const DabuildGenomeBuild,
+    name: *const c_char,
+    length: *mut u64,
+) -> c_int {
+    match lookup(build, name) {
+        Some(contig) => {
+            *length = *contig.length();
+            0
+        }
+        None => -1,
+    }
+}
+
+/// Write the main name of the contig with the main or alternative `name` into `buf`.
+///
+/// At most `buf_len - 1` bytes are written, followed by a NUL terminator.
+/// Returns the length of the main name (excluding the terminator), like `snprintf`,
+/// hence the name was truncated if the result is at least `buf_len`.
+/// Returns `-1` if the contig is unknown or the name is not valid UTF-8.
+///
+/// # Safety
+///
+/// `build` must be a valid genome build pointer, `name` must be a valid NUL-terminated string,
+/// and `buf` must be null or valid for writing `buf_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn dabuild_contig_name(
+    build: *const DabuildGenomeBuild,
+    name: *const c_char,
+    buf: *mut c_char,
+    buf_len: usize,
+) -> isize {
+    let Some(contig) = lookup(build, name) else {
+        return -1;
+    };
+    let primary = contig.name().as_bytes();
+    if !buf.is_null() && buf_len > 0 {
+        let n = primary.len().min(buf_len - 1);
+        ptr::copy_nonoverlapping(primary.as_ptr().cast(), buf, n);
+        *buf.add(n) = 0;
+    }
+    primary.len() as isize
+}
+
+unsafe fn lookup<'a>(
+    build: *const DabuildGenomeBuild,
+    name: *const c_char,
+) -> Option<&'a crate::Contig<u64>> {
+    if build.is_null() || name.is_null() {
+        return None;
+    }
+    let name = CStr::from_ptr(name).to_str().ok()?;
+    (*build).0.contig_by_name(name)
+}
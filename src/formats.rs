@@ -0,0 +1,1719 @@
+//! # Contig lists in common formats
+//!
+//! Besides the assembly reports (see [`crate::builds`]), a genome build can be loaded from:
+//!
+//! * FASTA index (`.fai`): [`parse_fai`]
+//! * `chrom.sizes`: [`parse_chrom_sizes`]
+//! * sequence dictionary (`.dict`) or SAM header: [`parse_dict`]
+//! * VCF header: [`parse_vcf_header`]
+//! * BAM header: [`parse_bam_header`] (requires the `bgzf` feature)
+//!
+//! The contigs keep the order of the input file.
+//! The gzip and BGZF-compressed inputs are decompressed transparently
+//! if the `bgzf` feature is enabled.
+//!
+//! The regions of a Picard interval list (`.interval_list`) are read by [`parse_interval_list`].
+//! An exclusion list, such as the ENCODE blacklist, can be loaded into a [`RegionSet`]
+//! of a genome build using [`parse_exclusion_list`], and the placements of the alt scaffolds
+//! into [`AltPlacements`] using [`parse_alt_placements`]. The cytogenetic bands, such as the UCSC
+//! `cytoBand.txt`, are loaded into [`Cytobands`] using [`parse_cytobands`].
+//!
+//! The sequence names and lengths of a FASTA file or a FASTA index can be checked
+//! against a genome build using [`check_fasta`] and [`check_fai`].
+//! The sequence names of a tabix or CSI index are read by [`read_index_names`]
+//! (requires the `bgzf` feature).
+//!
+//! A genome build can be written into:
+//!
+//! * `chrom.sizes`: [`write_chrom_sizes`]
+//! * sequence dictionary (`.dict`): [`write_dict`]
+//! * FASTA index stub (`.fai`) without sequence offsets: [`write_fai_stub`]
+//! * VCF header with `##contig` lines: [`write_vcf_header`]
+//! * Picard interval list of regions, e.g. of the shards of [`crate::partition_regions`]:
+//!   [`write_interval_list`]
+//! * JSON: [`write_json`], read back by `from_json_any_version` (requires the `json` feature)
+//!
+//! and into an assembly report using [`crate::builds::write_assembly_report`].
+//! The contigs with the same sequence in two builds are written into an identity chain file,
+//! e.g. for UCSC `liftOver`, using [`write_identity_chain`].
+//! The writers name the contigs in the requested [`NameStyle`].
+//! A contig without a name in the style keeps its main name.
+//!
+//! The parts of a build that do not survive a round trip through an assembly report,
+//! a sequence dictionary, or `chrom.sizes` are reported by [`round_trip_report`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use std::str::FromStr;
+//! use dabuild::{GenomeBuild, GenomeBuildIdentifier};
+//! use dabuild::formats::parse_fai;
+//!
+//! let fai = "chr1\t248956422\t112\t70\t71\nchrM\t16569\t252513167\t70\t71\n";
+//! let build: GenomeBuild<u32> = parse_fai(
+//!     GenomeBuildIdentifier::from_str("hg38").expect("Infallible"),
+//!     fai.as_bytes(),
+//! ).expect("Valid FASTA index");
+//!
+//! let names: Vec<_> = build.contigs().map(|c| c.name()).collect();
+//! assert_eq!(names, vec!["chr1", "chrM"]);
+//! ```
+
+#[cfg(feature = "bgzf")]
+use std::io::Read;
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead, Write},
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+use num_traits::{One, Zero};
+
+use crate::{
+    builds::ParseOptions, decompress::decompressed, liftover::same_sequence, AltPlacement,
+    AltPlacements, BuildMetadata, Contig, ContigOrdering, Cytoband, Cytobands, GenomeBuild,
+    GenomeBuildIdentifier, GenomicRegion, NameStyle, RegionSet, SequenceCheck,
+};
+
+/// Parse a FASTA index (`.fai`) into a [`GenomeBuild`].
+///
+/// Each line is expected to contain at least 2 tab-separated fields:
+/// the sequence name and the sequence length.
+/// The remaining fields (offset, line bases, line width) are ignored.
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`BufRead`]
+/// * Missing or unparsable sequence length
+/// * Duplicate sequence names or an empty index
+pub fn parse_fai<C, R>(id: GenomeBuildIdentifier, read: R) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    parse_names_and_lengths(id, read, &ParseOptions::default())
+}
+
+/// Parse a FASTA index (`.fai`) into a [`GenomeBuild`] with the `options`.
+///
+/// The contigs have no role, hence all are dropped if [`ParseOptions::roles`] are set.
+///
+/// ## Errors
+///
+/// Same as [`parse_fai`], and more than [`ParseOptions::max_contigs`] contigs.
+pub fn parse_fai_with<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+    options: &ParseOptions,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: Clone + FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    Ok(options.finish(parse_names_and_lengths(id, read, options)?))
+}
+
+/// Parse a `chrom.sizes` file, such as distributed by UCSC, into a [`GenomeBuild`].
+///
+/// Each line is expected to contain the tab-separated sequence name and length.
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`BufRead`]
+/// * Missing or unparsable sequence length
+/// * Duplicate sequence names or an empty file
+pub fn parse_chrom_sizes<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    parse_names_and_lengths(id, read, &ParseOptions::default())
+}
+
+/// Parse a `chrom.sizes` file into a [`GenomeBuild`] with the `options`.
+///
+/// The contigs have no role, hence all are dropped if [`ParseOptions::roles`] are set.
+/// See [`ParseOptions`] for an example.
+///
+/// ## Errors
+///
+/// Same as [`parse_chrom_sizes`], and more than [`ParseOptions::max_contigs`] contigs.
+pub fn parse_chrom_sizes_with<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+    options: &ParseOptions,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: Clone + FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    Ok(options.finish(parse_names_and_lengths(id, read, options)?))
+}
+
+fn parse_names_and_lengths<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+    options: &ParseOptions,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    let mut builder = GenomeBuild::builder()
+        .id(id)
+        .ordering(ContigOrdering::Insertion);
+    let mut count = 0;
+
+    for (i, line) in decompressed(read)?.lines().enumerate() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\t');
+        let name = fields.next().unwrap_or_default();
+        let length = fields.next().unwrap_or_default();
+        let Ok(parsed) = length.parse() else {
+            if options.is_lenient() {
+                continue;
+            }
+            return Err(format!("Missing or invalid sequence length in line #{i} {line}").into());
+        };
+        if !options.keeps_contig(None, length) {
+            continue;
+        }
+        count += 1;
+        options.check_count(count)?;
+        builder = builder.contig(name, parsed).source_index(name, i);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Parse an exclusion list of the contigs of the `build` into a [`RegionSet`].
+///
+/// Each line includes either a BED record, e.g. an ENCODE blacklist region,
+/// or a contig name alone to exclude the whole contig.
+/// The contigs are looked up in the build by any of their names.
+/// Empty lines, comments (`#`), and the `track` and `browser` lines are skipped.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::{GenomeBuild, GenomicRegion};
+/// use dabuild::builds::get_grch38_p13;
+/// use dabuild::formats::parse_exclusion_list;
+///
+/// let build: GenomeBuild<u32> = get_grch38_p13();
+/// let list = "# Exclusions\nchr1\t628903\t635104\tHigh Signal Region\nchrM\n";
+/// let excluded = parse_exclusion_list(&build, list.as_bytes()).expect("Valid exclusion list");
+///
+/// let chr1 = build.contig_by_name("chr1").expect("Known contig");
+/// let chrm = build.contig_by_name("chrM").expect("Known contig");
+/// assert!(excluded.is_excluded(chrm));
+/// assert!(!excluded.is_excluded(chr1));
+/// assert!(excluded.overlaps(&GenomicRegion::new(chr1, 630_000, 630_100).expect("Valid region")));
+/// ```
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`BufRead`]
+/// * Contig not in the build
+/// * Unparsable coordinates, or a region beyond the contig end
+pub fn parse_exclusion_list<C, R>(
+    build: &GenomeBuild<C>,
+    read: R,
+) -> Result<RegionSet<C>, Box<dyn Error>>
+where
+    C: Copy + FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    let mut set = RegionSet::new();
+
+    for (i, line) in decompressed(read)?.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty()
+            || line.starts_with('#')
+            || line.starts_with("track")
+            || line.starts_with("browser")
+        {
+            continue;
+        }
+
+        let mut fields = line.split('\t');
+        let name = fields.next().unwrap_or_default().trim();
+        let contig = build
+            .contig_by_name(name)
+            .ok_or_else(|| format!("Unknown contig {name:?} in line #{i} {line}"))?;
+
+        match (fields.next(), fields.next()) {
+            (None, _) => set.insert_contig(contig),
+            (Some(start), Some(end)) => {
+                let region = match (start.trim().parse(), end.trim().parse()) {
+                    (Ok(start), Ok(end)) => GenomicRegion::new(contig, start, end),
+                    _ => None,
+                }
+                .ok_or_else(|| format!("Invalid region in line #{i} {line}"))?;
+                set.insert(region);
+            }
+            (Some(_), None) => return Err(format!("Missing region end in line #{i} {line}").into()),
+        }
+    }
+
+    Ok(set)
+}
+
+/// Parse the placements of the alt scaffolds and patches of the `build`,
+/// such as the NCBI `alt_scaffold_placement.txt` file, into [`AltPlacements`].
+///
+/// Each line includes at least 14 tab-separated fields, of which are used:
+///
+/// * 3rd and 4th - the name and the accession of the alt scaffold
+/// * 6th and 7th - the name and the accession of the parent
+/// * 9th - the orientation (`+` or `-`)
+/// * 11th and 12th - the 1-based start and end of the aligned part of the alt scaffold
+/// * 13th and 14th - the 1-based start and end of the aligned part of the parent
+///
+/// The contigs are looked up in the build by the accession or by the name.
+/// Empty lines and comments (`#`) are skipped.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::{GenomeBuild, GenomicRegion};
+/// use dabuild::builds::get_grch38_p13;
+/// use dabuild::formats::parse_alt_placements;
+///
+/// let build: GenomeBuild<u32> = get_grch38_p13();
+/// let placement = "ALT_REF_LOCI_1\tPrimary Assembly\tHSCHR6_MHC_APD_CTG1\tGL000250.2\tCHROMOSOME\t6\tCM000668.2\tREGION108\t+\t4672374\t1\t4672374\t28510128\t33383765\t0\t0\n";
+/// let placements = parse_alt_placements(&build, placement.as_bytes()).expect("Valid placements");
+///
+/// let alt = build.contig_by_name("chr6_GL000250v2_alt").expect("Known contig");
+/// let region = GenomicRegion::new(alt, 0, 1_000).expect("Valid region");
+/// let projected = placements.project_to_primary(&region).expect("Placed region");
+/// assert_eq!(projected.contig().name(), "6");
+/// assert_eq!(*projected.start(), 28_510_127);
+/// ```
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`BufRead`]
+/// * Missing fields, or contig not in the build
+/// * Unparsable orientation or coordinates, or a region beyond the contig end
+pub fn parse_alt_placements<C, R>(
+    build: &GenomeBuild<C>,
+    read: R,
+) -> Result<AltPlacements<C>, Box<dyn Error>>
+where
+    C: Clone + FromStr + Zero + PartialOrd + One + Sub<Output = C>,
+    R: BufRead,
+{
+    let mut placements = AltPlacements::new();
+
+    for (i, line) in decompressed(read)?.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<_> = line.split('\t').map(str::trim).collect();
+        if fields.len() < 14 {
+            return Err(format!("Missing fields in line #{i} {line}").into());
+        }
+        let contig = |name: &str, accession: &str| {
+            build
+                .contig_by_name(accession)
+                .or_else(|| build.contig_by_name(name))
+                .ok_or_else(|| format!("Unknown contig {name:?} in line #{i} {line}"))
+        };
+        let (alt, parent) = (contig(fields[2], fields[3])?, contig(fields[5], fields[6])?);
+        let reverse = match fields[8] {
+            "+" => false,
+            "-" => true,
+            _ => return Err(format!("Invalid orientation in line #{i} {line}").into()),
+        };
+        // Convert the 1-based closed coordinates into the 0-based half-open ones.
+        let region = |contig: &Contig<C>, start: &str, end: &str| {
+            match (start.parse::<C>(), end.parse()) {
+                (Ok(start), Ok(end)) if start > C::zero() => {
+                    GenomicRegion::new(contig, start - C::one(), end)
+                }
+                _ => None,
+            }
+            .ok_or_else(|| format!("Invalid region in line #{i} {line}"))
+        };
+        placements.insert(AltPlacement::new(
+            region(alt, fields[10], fields[11])?,
+            region(parent, fields[12], fields[13])?,
+            reverse,
+        ));
+    }
+
+    Ok(placements)
+}
+
+/// Parse the cytogenetic bands of the `build`, such as the UCSC `cytoBand.txt` file,
+/// into [`Cytobands`].
+///
+/// Each line includes 5 tab-separated fields: the contig name, the 0-based start and end
+/// of the band, the band name (e.g. `q21.1`), and the Giemsa stain (e.g. `gpos50`).
+/// The contigs are looked up in the build by any of their names.
+/// Empty lines and comments (`#`) are skipped.
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`BufRead`]
+/// * Missing fields, or contig not in the build
+/// * Unparsable coordinates, or a band beyond the contig end
+pub fn parse_cytobands<C, R>(
+    build: &GenomeBuild<C>,
+    read: R,
+) -> Result<Cytobands<C>, Box<dyn Error>>
+where
+    C: Copy + FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    let mut cytobands = Cytobands::new();
+
+    for (i, line) in decompressed(read)?.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<_> = line.split('\t').map(str::trim).collect();
+        let [name, start, end, band, stain] = fields[..] else {
+            return Err(format!("Expected 5 fields in line #{i} {line}").into());
+        };
+        let contig = build
+            .contig_by_name(name)
+            .ok_or_else(|| format!("Unknown contig {name:?} in line #{i} {line}"))?;
+        let region = match (start.parse(), end.parse()) {
+            (Ok(start), Ok(end)) => GenomicRegion::new(contig, start, end),
+            _ => None,
+        }
+        .ok_or_else(|| format!("Invalid band in line #{i} {line}"))?;
+        cytobands.insert(Cytoband::new(region, band, stain));
+    }
+
+    Ok(cytobands)
+}
+
+/// Parse a sequence dictionary (`.dict`), such as produced by Picard `CreateSequenceDictionary`,
+/// into a [`GenomeBuild`].
+///
+/// The contigs are read from the `@SQ` lines, using the tags:
+///
+/// * `SN` - the sequence name
+/// * `LN` - the sequence length
+/// * `AN` - the comma-separated alternative names (optional)
+/// * `M5` - the MD5 digest of the sequence (optional)
+///
+/// Other lines and tags are ignored.
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`BufRead`]
+/// * Missing `SN` tag or missing/unparsable `LN` tag
+/// * Invalid `M5` tag
+/// * Duplicate sequence names or no `@SQ` lines
+pub fn parse_dict<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    read_dict(id, read, &ParseOptions::default())
+}
+
+/// Parse a sequence dictionary (`.dict`) into a [`GenomeBuild`] with the `options`.
+///
+/// The contigs have no role, hence all are dropped if [`ParseOptions::roles`] are set.
+///
+/// ## Errors
+///
+/// Same as [`parse_dict`], and more than [`ParseOptions::max_contigs`] contigs.
+pub fn parse_dict_with<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+    options: &ParseOptions,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: Clone + FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    Ok(options.finish(read_dict(id, read, options)?))
+}
+
+fn read_dict<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+    options: &ParseOptions,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    let mut builder = GenomeBuild::builder()
+        .id(id)
+        .ordering(ContigOrdering::Insertion);
+    let mut count = 0;
+
+    for (i, line) in decompressed(read)?.lines().enumerate() {
+        let line = line?;
+        if !line.starts_with("@SQ\t") {
+            continue;
+        }
+
+        let (mut name, mut length, mut alt_names, mut md5) = (None, None, None, None);
+        for field in line.split('\t').skip(1) {
+            match field.split_once(':') {
+                Some(("SN", value)) => name = Some(value),
+                Some(("LN", value)) => length = Some(value),
+                Some(("AN", value)) => alt_names = Some(value),
+                Some(("M5", value)) => md5 = Some(value),
+                _ => {}
+            }
+        }
+
+        let (Some(name), Some((text, length))) =
+            (name, length.and_then(|l| Some((l, l.parse::<C>().ok()?))))
+        else {
+            if options.is_lenient() {
+                continue;
+            }
+            return Err(format!("Missing or invalid `SN` or `LN` tag in line #{i} {line}").into());
+        };
+        if !options.keeps_contig(None, text) {
+            continue;
+        }
+        count += 1;
+        options.check_count(count)?;
+        builder = builder.contig(name, length).source_index(name, i);
+        for alt_name in alt_names.into_iter().flat_map(|names| names.split(',')) {
+            builder = builder.alias(name, alt_name);
+        }
+        if let Some(md5) = md5 {
+            builder = builder.md5(name, md5);
+        }
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Parse the regions of a Picard interval list (`.interval_list`), e.g. a GATK scatter interval,
+/// of the contigs of the `build`.
+///
+/// The `@SQ` lines of the header are validated against the build: each sequence must be
+/// a contig of the build, with the contig length. The other header lines are ignored.
+/// Each interval line includes 5 tab-separated fields: the contig name, the 1-based start
+/// and end, the strand, and the interval name, of which the strand and the name are ignored.
+/// The contigs are looked up in the build by any of their names,
+/// and the regions keep the order of the file.
+///
+/// The interval list can be written by [`write_interval_list`].
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::GenomeBuild;
+/// use dabuild::builds::get_grch38_p13;
+/// use dabuild::formats::parse_interval_list;
+///
+/// let build: GenomeBuild<u32> = get_grch38_p13();
+/// let list = "@HD\tVN:1.6\n@SQ\tSN:chr1\tLN:248956422\nchr1\t1001\t2000\t+\ttarget_1\n";
+/// let regions = parse_interval_list(&build, list.as_bytes()).expect("Valid interval list");
+///
+/// assert_eq!((*regions[0].start(), *regions[0].end()), (1_000, 2_000));
+/// ```
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`BufRead`]
+/// * Header sequence not in the build, or with another length
+/// * Missing fields, or contig not in the build
+/// * Unparsable coordinates, or an interval beyond the contig end
+pub fn parse_interval_list<C, R>(
+    build: &GenomeBuild<C>,
+    read: R,
+) -> Result<Vec<GenomicRegion<C>>, Box<dyn Error>>
+where
+    C: Copy + FromStr + Zero + One + PartialOrd + Sub<Output = C>,
+    R: BufRead,
+{
+    let mut regions = vec![];
+
+    for (i, line) in decompressed(read)?.lines().enumerate() {
+        let line = line?;
+        if line.starts_with("@SQ\t") {
+            let (mut name, mut length) = (None, None);
+            for field in line.split('\t').skip(1) {
+                match field.split_once(':') {
+                    Some(("SN", value)) => name = Some(value),
+                    Some(("LN", value)) => length = value.parse::<C>().ok(),
+                    _ => {}
+                }
+            }
+            let (Some(name), Some(length)) = (name, length) else {
+                return Err(
+                    format!("Missing or invalid `SN` or `LN` tag in line #{i} {line}").into(),
+                );
+            };
+            match build.contig_by_name(name) {
+                Some(contig) if *contig.length() == length => {}
+                Some(_) => {
+                    return Err(format!("Length mismatch of {name:?} in line #{i} {line}").into())
+                }
+                None => return Err(format!("Unknown contig {name:?} in line #{i} {line}").into()),
+            }
+            continue;
+        }
+        if line.trim().is_empty() || line.starts_with('@') {
+            continue;
+        }
+
+        let fields: Vec<_> = line.split('\t').map(str::trim).collect();
+        let [name, start, end, _, _] = fields[..] else {
+            return Err(format!("Expected 5 fields in line #{i} {line}").into());
+        };
+        let contig = build
+            .contig_by_name(name)
+            .ok_or_else(|| format!("Unknown contig {name:?} in line #{i} {line}"))?;
+        let region = match (start.parse::<C>(), end.parse()) {
+            (Ok(start), Ok(end)) if start > C::zero() => {
+                GenomicRegion::new(contig, start - C::one(), end)
+            }
+            _ => None,
+        }
+        .ok_or_else(|| format!("Invalid interval in line #{i} {line}"))?;
+        regions.push(region);
+    }
+
+    Ok(regions)
+}
+
+/// Parse the `##contig` lines of a VCF header into a [`GenomeBuild`].
+///
+/// The contigs are read from the `ID` and `length` fields, e.g.
+/// `##contig=<ID=chr1,length=248956422,assembly=GRCh38>`.
+/// Other header lines are ignored and the parsing stops at the first line
+/// that does not start with `##`, hence the records are not read.
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`BufRead`]
+/// * Missing `ID` field or missing/unparsable `length` field
+/// * Duplicate sequence names or no `##contig` lines
+pub fn parse_vcf_header<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    read_vcf_header(id, read, &ParseOptions::default())
+}
+
+/// Parse the `##contig` lines of a VCF header into a [`GenomeBuild`] with the `options`.
+///
+/// The contigs have no role, hence all are dropped if [`ParseOptions::roles`] are set.
+///
+/// ## Errors
+///
+/// Same as [`parse_vcf_header`], and more than [`ParseOptions::max_contigs`] contigs.
+pub fn parse_vcf_header_with<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+    options: &ParseOptions,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: Clone + FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    Ok(options.finish(read_vcf_header(id, read, options)?))
+}
+
+fn read_vcf_header<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+    options: &ParseOptions,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: BufRead,
+{
+    let mut builder = GenomeBuild::builder()
+        .id(id)
+        .ordering(ContigOrdering::Insertion);
+    let mut count = 0;
+
+    for (i, line) in decompressed(read)?.lines().enumerate() {
+        let line = line?;
+        if !line.starts_with("##") {
+            break;
+        }
+        let Some(fields) = line
+            .strip_prefix("##contig=<")
+            .and_then(|fields| fields.strip_suffix('>'))
+        else {
+            continue;
+        };
+
+        let (mut name, mut length) = (None, None);
+        for field in split_structured_fields(fields) {
+            match field.split_once('=') {
+                Some(("ID", value)) => name = Some(value),
+                Some(("length", value)) => length = Some(value),
+                _ => {}
+            }
+        }
+
+        let (Some(name), Some((text, length))) =
+            (name, length.and_then(|l| Some((l, l.parse::<C>().ok()?))))
+        else {
+            if options.is_lenient() {
+                continue;
+            }
+            return Err(
+                format!("Missing or invalid `ID` or `length` field in line #{i} {line}").into(),
+            );
+        };
+        if !options.keeps_contig(None, text) {
+            continue;
+        }
+        count += 1;
+        options.check_count(count)?;
+        builder = builder.contig(name, length).source_index(name, i);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Split the fields of a structured VCF header line, ignoring the commas in quoted values.
+fn split_structured_fields(fields: &str) -> impl Iterator<Item = &str> {
+    let mut quoted = false;
+    fields.split(move |c| {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        c == ',' && !quoted
+    })
+}
+
+/// Parse the reference sequences of a BAM file header into a [`GenomeBuild`].
+///
+/// The `read` is expected to provide the BGZF-compressed BAM file,
+/// and only the header is read.
+/// The contigs are read from the binary reference sequence dictionary
+/// rather than from the header text.
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`Read`]
+/// * Missing BAM magic bytes or a truncated header
+/// * Duplicate sequence names or no reference sequences
+#[cfg(feature = "bgzf")]
+pub fn parse_bam_header<C, R>(
+    id: GenomeBuildIdentifier,
+    read: R,
+) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: Read,
+{
+    let mut read = flate2::read::MultiGzDecoder::new(read);
+
+    let mut magic = [0; 4];
+    read.read_exact(&mut magic)?;
+    if &magic != b"BAM\x01" {
+        return Err("Missing BAM magic bytes".into());
+    }
+    // Skip the header text.
+    let l_text = read_u32(&mut read)?;
+    io::copy(&mut (&mut read).take(u64::from(l_text)), &mut io::sink())?;
+
+    let mut builder = GenomeBuild::builder()
+        .id(id)
+        .ordering(ContigOrdering::Insertion);
+    let n_ref = read_u32(&mut read)?;
+    for i in 0..n_ref as usize {
+        let l_name = read_u32(&mut read)?;
+        if l_name == 0 {
+            return Err(format!("Empty name of reference sequence #{i}").into());
+        }
+        let mut name = read_bytes(&mut read, l_name)?;
+        // The name is NUL-terminated.
+        if name.pop() != Some(0) {
+            return Err(format!("Name of reference sequence #{i} is not NUL-terminated").into());
+        }
+        let name = String::from_utf8(name)?;
+        let l_ref = read_u32(&mut read)?;
+        let length = l_ref
+            .to_string()
+            .parse()
+            .map_err(|_| format!("Cannot represent length {l_ref} of sequence {name:?}"))?;
+        builder = builder.contig(&name, length).source_index(name, i);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Read the sequence names of a tabix (`.tbi`) or CSI (`.csi`) index.
+///
+/// The `read` is expected to provide the BGZF-compressed index.
+/// Use [`GenomeBuild::check_names`] to check the names against a build,
+/// e.g. to get the rename map for querying the indexed file with the names of the build.
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`Read`]
+/// * Missing tabix or CSI magic bytes or a truncated header
+/// * CSI index without the sequence names, e.g. a BAM index
+#[cfg(feature = "bgzf")]
+pub fn read_index_names<R>(read: R) -> Result<Vec<String>, Box<dyn Error>>
+where
+    R: Read,
+{
+    let mut read = flate2::read::MultiGzDecoder::new(read);
+
+    let mut magic = [0; 4];
+    read.read_exact(&mut magic)?;
+    match &magic {
+        b"TBI\x01" => {
+            // Skip `n_ref`.
+            read_u32(&mut read)?;
+        }
+        b"CSI\x01" => {
+            // Skip `min_shift` and `depth`.
+            read_u32(&mut read)?;
+            read_u32(&mut read)?;
+            if read_u32(&mut read)? == 0 {
+                return Err("No sequence names in the CSI index".into());
+            }
+        }
+        _ => return Err("Missing tabix or CSI magic bytes".into()),
+    }
+    // Skip `format`, `col_seq`, `col_beg`, `col_end`, `meta`, and `skip`.
+    io::copy(&mut (&mut read).take(24), &mut io::sink())?;
+
+    let l_nm = read_u32(&mut read)?;
+    let names = read_bytes(&mut read, l_nm)?;
+    // The names are NUL-terminated.
+    names
+        .split(|&b| b == 0)
+        .filter(|name| !name.is_empty())
+        .map(|name| Ok(String::from_utf8(name.to_vec())?))
+        .collect()
+}
+
+#[cfg(feature = "bgzf")]
+fn read_u32<R: Read>(read: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    read.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Read `len` bytes, allocating as the bytes are read,
+/// hence a corrupt length of a truncated input does not allocate up front.
+#[cfg(feature = "bgzf")]
+fn read_bytes<R: Read>(read: &mut R, len: u32) -> io::Result<Vec<u8>> {
+    let mut bytes = vec![];
+    read.take(u64::from(len)).read_to_end(&mut bytes)?;
+    if bytes.len() != len as usize {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+/// Check the sequences of a FASTA file against the `build`.
+///
+/// The sequence names are read from the headers, up to the first whitespace,
+/// and the sequence lengths are counted from the sequence lines.
+/// Use [`check_fai`] to check the FASTA index instead of reading the sequences.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::{GenomeBuild, NameStyle};
+/// use dabuild::builds::get_grch38_p13;
+/// use dabuild::formats::check_fasta;
+///
+/// let build: GenomeBuild<u32> = get_grch38_p13();
+/// let fasta = ">chrM mitochondrion\nGATCACAGGT\nCTATCACCC\n";
+///
+/// let check = check_fasta(&build, fasta.as_bytes()).expect("Valid FASTA");
+///
+/// assert_eq!(check.styles(), [NameStyle::Ucsc]);
+/// assert_eq!(check.length_mismatches()[0].found, 19);
+/// assert_eq!(check.rename_map(NameStyle::Main), [("chrM".to_string(), "MT".to_string())]);
+/// ```
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`BufRead`]
+/// * Sequence line before the first header or a header without a name
+/// * Sequence length that cannot be represented by `C`
+pub fn check_fasta<C, R>(
+    build: &GenomeBuild<C>,
+    read: R,
+) -> Result<SequenceCheck<C>, Box<dyn Error>>
+where
+    C: Copy + FromStr + PartialEq,
+    R: BufRead,
+{
+    let mut sequences: Vec<(String, u64)> = vec![];
+    for (i, line) in decompressed(read)?.lines().enumerate() {
+        let line = line?;
+        if let Some(header) = line.strip_prefix('>') {
+            match header.split_whitespace().next() {
+                Some(name) => sequences.push((name.to_string(), 0)),
+                None => return Err(format!("Missing sequence name in line #{i} {line}").into()),
+            }
+        } else {
+            let bases = line.trim_end().len() as u64;
+            match sequences.last_mut() {
+                Some((_, length)) => *length += bases,
+                None if bases == 0 => {}
+                None => return Err(format!("Missing header before line #{i} {line}").into()),
+            }
+        }
+    }
+
+    let mut checked = Vec::with_capacity(sequences.len());
+    for (name, length) in sequences {
+        let length = length
+            .to_string()
+            .parse()
+            .map_err(|_| format!("Cannot represent length {length} of sequence {name:?}"))?;
+        checked.push((name, Some(length)));
+    }
+    Ok(build.check_sequences(checked))
+}
+
+/// Check the sequences of a FASTA index (`.fai`) against the `build`.
+///
+/// See [`check_fasta`] for more info.
+///
+/// ## Errors
+///
+/// * I/O or decompression error of the underlying [`BufRead`]
+/// * Missing or unparsable sequence length
+pub fn check_fai<C, R>(build: &GenomeBuild<C>, read: R) -> Result<SequenceCheck<C>, Box<dyn Error>>
+where
+    C: Copy + FromStr + PartialEq,
+    R: BufRead,
+{
+    let mut sequences = vec![];
+    for (i, line) in decompressed(read)?.lines().enumerate() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\t');
+        let name = fields.next().unwrap_or_default().to_string();
+        match fields.next().map(str::parse) {
+            Some(Ok(length)) => sequences.push((name, Some(length))),
+            _ => {
+                return Err(
+                    format!("Missing or invalid sequence length in line #{i} {line}").into(),
+                )
+            }
+        }
+    }
+    Ok(build.check_sequences(sequences))
+}
+
+/// Write the contig names and lengths in the `chrom.sizes` format,
+/// one tab-separated line per contig.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::{GenomeBuild, NameStyle};
+/// use dabuild::builds::get_grch38_p13;
+/// use dabuild::formats::write_chrom_sizes;
+///
+/// let build: GenomeBuild<u32> = get_grch38_p13();
+/// let mut out = vec![];
+/// write_chrom_sizes(&build, NameStyle::Ucsc, &mut out).expect("No I/O issues");
+///
+/// let sizes = String::from_utf8(out).expect("UTF-8");
+/// assert_eq!(sizes.lines().next(), Some("chr1\t248956422"));
+/// ```
+///
+/// ## Errors
+///
+/// I/O error of the underlying [`Write`].
+pub fn write_chrom_sizes<C, W>(
+    build: &GenomeBuild<C>,
+    style: NameStyle,
+    mut write: W,
+) -> io::Result<()>
+where
+    C: Display,
+    W: Write,
+{
+    for contig in build.contigs() {
+        writeln!(write, "{}\t{}", styled_name(contig, style), contig.length())?;
+    }
+    Ok(())
+}
+
+/// Write a sequence dictionary (`.dict`) with one `@SQ` line per contig.
+///
+/// The other names of the contig are written into the `AN` tag
+/// and the MD5 digest, if known, into the `M5` tag.
+/// Hence, the dictionary can be read back by [`parse_dict`].
+///
+/// ## Errors
+///
+/// I/O error of the underlying [`Write`].
+pub fn write_dict<C, W>(build: &GenomeBuild<C>, style: NameStyle, mut write: W) -> io::Result<()>
+where
+    C: Display,
+    W: Write,
+{
+    writeln!(write, "@HD\tVN:1.6")?;
+    for contig in build.contigs() {
+        let name = styled_name(contig, style);
+        write!(write, "@SQ\tSN:{name}\tLN:{}", contig.length())?;
+        let alt_names: Vec<_> = other_names(contig, name).collect();
+        if let Some(md5) = contig.md5() {
+            write!(write, "\tM5:{md5}")?;
+        }
+        if !alt_names.is_empty() {
+            write!(write, "\tAN:{}", alt_names.join(","))?;
+        }
+        writeln!(write)?;
+    }
+    Ok(())
+}
+
+/// Write the `regions` of the `build` into a Picard interval list (`.interval_list`),
+/// e.g. a shard of [`GenomeBuild::partition`] for a GATK scatter.
+///
+/// The header is the sequence dictionary of the build, as written by [`write_dict`],
+/// followed by one line per region with the contig name, the 1-based start and end,
+/// the strand (`+`), and the interval name (`.`).
+/// The interval list can be read back by [`parse_interval_list`].
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::{GenomeBuild, NameStyle};
+/// use dabuild::builds::get_grch38_p13;
+/// use dabuild::formats::write_interval_list;
+///
+/// let build: GenomeBuild<u32> = get_grch38_p13();
+/// let shards = build.partition(10);
+/// let mut out = vec![];
+/// write_interval_list(&build, NameStyle::Ucsc, &shards[0], &mut out).expect("No I/O issues");
+///
+/// let intervals = String::from_utf8(out).expect("UTF-8");
+/// assert!(intervals.starts_with("@HD\tVN:1.6\n@SQ\tSN:chr1\tLN:248956422"));
+/// assert!(intervals.contains("\nchr1\t1\t248956422\t+\t.\n"));
+/// ```
+///
+/// ## Errors
+///
+/// I/O error of the underlying [`Write`].
+pub fn write_interval_list<'a, C, W, I>(
+    build: &GenomeBuild<C>,
+    style: NameStyle,
+    regions: I,
+    mut write: W,
+) -> io::Result<()>
+where
+    C: Display + Copy + One + Add<Output = C> + 'a,
+    W: Write,
+    I: IntoIterator<Item = &'a GenomicRegion<C>>,
+{
+    write_dict(build, style, &mut write)?;
+    for region in regions {
+        writeln!(
+            write,
+            "{}\t{}\t{}\t+\t.",
+            styled_name(region.contig(), style),
+            *region.start() + C::one(),
+            region.end()
+        )?;
+    }
+    Ok(())
+}
+
+/// Write a FASTA index (`.fai`) stub with the contig names and lengths.
+///
+/// The stub is meant for the tools that only read the contig names and lengths from the index.
+/// The sequence offsets and line lengths are unknown, and they are written as `0`.
+///
+/// ## Errors
+///
+/// I/O error of the underlying [`Write`].
+pub fn write_fai_stub<C, W>(
+    build: &GenomeBuild<C>,
+    style: NameStyle,
+    mut write: W,
+) -> io::Result<()>
+where
+    C: Display,
+    W: Write,
+{
+    for contig in build.contigs() {
+        writeln!(
+            write,
+            "{}\t{}\t0\t0\t0",
+            styled_name(contig, style),
+            contig.length()
+        )?;
+    }
+    Ok(())
+}
+
+/// Write a VCF header with the `##fileformat` line, one `##contig` line per contig,
+/// and the `#CHROM` line.
+///
+/// ## Errors
+///
+/// I/O error of the underlying [`Write`].
+pub fn write_vcf_header<C, W>(
+    build: &GenomeBuild<C>,
+    style: NameStyle,
+    mut write: W,
+) -> io::Result<()>
+where
+    C: Display,
+    W: Write,
+{
+    writeln!(write, "##fileformat=VCFv4.2")?;
+    for contig in build.contigs() {
+        writeln!(
+            write,
+            "##contig=<ID={},length={},assembly={}>",
+            styled_name(contig, style),
+            contig.length(),
+            build.id()
+        )?;
+    }
+    writeln!(write, "#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO")
+}
+
+/// Write a chain file with the identity mappings of the contigs of the `source` build
+/// that have the same sequence in the `target` build, e.g. to drive UCSC `liftOver`.
+///
+/// The contigs are paired as in [`GenomeBuild::liftability`], and the pairs of contigs
+/// with the same sequence, either [`Liftability::Identical`] or [`Liftability::RenamedOnly`],
+/// get one chain with a single block spanning the whole contig.
+/// The `source` contigs are the chain reference (`t`) sequences, the `target` contigs
+/// are the query (`q`) sequences, and both are named in the `style`.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::{GenomeBuild, NameStyle};
+/// use dabuild::builds::{get_grch37_p13, get_grch38_p13};
+/// use dabuild::formats::write_identity_chain;
+///
+/// let grch37: GenomeBuild<u32> = get_grch37_p13();
+/// let grch38: GenomeBuild<u32> = get_grch38_p13();
+/// let mut out = vec![];
+/// write_identity_chain(&grch37, &grch38, NameStyle::Ucsc, &mut out).expect("No I/O issues");
+///
+/// let chain = String::from_utf8(out).expect("UTF-8");
+/// assert!(chain.contains("chain 16569 chrM 16569 + 0 16569 chrM 16569 + 0 16569"));
+/// assert!(!chain.contains("chr1 "));
+/// ```
+///
+/// ## Errors
+///
+/// I/O error of the underlying [`Write`].
+///
+/// [`Liftability::Identical`]: crate::Liftability::Identical
+/// [`Liftability::RenamedOnly`]: crate::Liftability::RenamedOnly
+pub fn write_identity_chain<C, W>(
+    source: &GenomeBuild<C>,
+    target: &GenomeBuild<C>,
+    style: NameStyle,
+    mut write: W,
+) -> io::Result<()>
+where
+    C: Display + PartialEq,
+    W: Write,
+{
+    let mut id = 0;
+    for contig in source.contigs() {
+        let Some(other) = target
+            .counterpart(contig)
+            .filter(|other| same_sequence(contig, other))
+        else {
+            continue;
+        };
+        id += 1;
+        let length = contig.length();
+        writeln!(
+            write,
+            "chain {length} {} {length} + 0 {length} {} {length} + 0 {length} {id}",
+            styled_name(contig, style),
+            styled_name(other, style),
+        )?;
+        writeln!(write, "{length}\n")?;
+    }
+    Ok(())
+}
+
+/// The version of the JSON schema written by [`write_json`].
+///
+/// The versions are:
+///
+/// * `1` - the build identifier and the contigs, without the `schema_version` field
+/// * `2` - the `schema_version` and the build metadata
+/// * `3` - the organism, the taxid, the submitter, and the date of the metadata
+/// * `4` - the GenBank, RefSeq, and UCSC-style names of the contigs
+pub const JSON_SCHEMA_VERSION: u64 = 4;
+
+/// Write the genome build as a JSON object.
+///
+/// The object includes the schema version (see [`JSON_SCHEMA_VERSION`]),
+/// the build identifier, the metadata, and the contigs:
+///
+/// ```json
+/// {"schema_version":4,"id":{"major_assembly":"GRCh38","patch":"p13"},"metadata":{"organism":"Homo sapiens (human)","taxid":9606,"submitter":"Genome Reference Consortium","date":"2019-02-28","assembly_level":"Chromosome","genome_representation":"full","bioproject":"PRJNA31257","biosample":null,"genbank_accession":"GCA_000001405.28","refseq_accession":"GCF_000001405.39"},"contigs":[{"name":"chr1","alt_names":["1","CM000663.2","NC_000001.11"],"length":248956422,"role":"assembled-molecule","md5":null,"genbank":"CM000663.2","refseq":"NC_000001.11","ucsc":"chr1"}]}
+/// ```
+///
+/// The `patch`, the metadata values, `role`, `md5`, `genbank`, `refseq`, and `ucsc`
+/// are `null` if unknown.
+/// The JSON of any schema version is read back by `from_json_any_version` (requires the `json` feature).
+///
+/// ## Errors
+///
+/// I/O error of the underlying [`Write`].
+pub fn write_json<C, W>(build: &GenomeBuild<C>, style: NameStyle, mut write: W) -> io::Result<()>
+where
+    C: Display,
+    W: Write,
+{
+    write!(
+        write,
+        "{{\"schema_version\":{JSON_SCHEMA_VERSION},\"id\":{{\"major_assembly\":"
+    )?;
+    write_json_str(&mut write, build.id().major_assembly())?;
+    write!(write, ",\"patch\":")?;
+    write_json_opt(&mut write, build.id().patch())?;
+
+    let metadata = build.metadata();
+    let level = metadata.assembly_level().map(|level| level.to_string());
+    let representation = metadata
+        .genome_representation()
+        .map(|representation| representation.to_string());
+    write!(write, "}},\"metadata\":{{\"organism\":")?;
+    write_json_opt(&mut write, metadata.organism())?;
+    match metadata.taxid() {
+        Some(taxid) => write!(write, ",\"taxid\":{taxid}")?,
+        None => write!(write, ",\"taxid\":null")?,
+    }
+    for (key, value) in [
+        ("submitter", metadata.submitter()),
+        ("date", metadata.date()),
+        ("assembly_level", level.as_deref()),
+        ("genome_representation", representation.as_deref()),
+        ("bioproject", metadata.bioproject()),
+        ("biosample", metadata.biosample()),
+        ("genbank_accession", metadata.genbank_accession()),
+        ("refseq_accession", metadata.refseq_accession()),
+    ] {
+        write!(write, ",\"{key}\":")?;
+        write_json_opt(&mut write, value)?;
+    }
+    write!(write, "}},\"contigs\":[")?;
+
+    for (i, contig) in build.contigs().enumerate() {
+        if i > 0 {
+            write!(write, ",")?;
+        }
+        write!(write, "{{")?;
+        write_json_contig(&mut write, contig, style)?;
+        for (key, value) in [
+            ("genbank", contig.genbank_accession()),
+            ("refseq", contig.refseq_accession()),
+            ("ucsc", contig.ucsc_name()),
+        ] {
+            write!(write, ",\"{key}\":")?;
+            write_json_opt(&mut write, value)?;
+        }
+        write!(write, "}}")?;
+    }
+
+    writeln!(write, "]}}")
+}
+
+/// Write the genome build as JSON Lines, one JSON object per contig,
+/// e.g. for an ad-hoc analysis with `jq` or DuckDB.
+///
+/// The object includes the build identifier and the contig fields of [`write_json`]:
+///
+/// ```json
+/// {"major_assembly":"GRCh38","patch":"p13","name":"chr1","alt_names":["1","CM000663.2","NC_000001.11"],"length":248956422,"role":"assembled-molecule","md5":null}
+/// ```
+///
+/// The `patch`, `role`, and `md5` are `null` if unknown.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::{GenomeBuild, NameStyle};
+/// use dabuild::builds::toy;
+/// use dabuild::formats::write_ndjson;
+///
+/// let build: GenomeBuild<u32> = toy();
+/// let mut ndjson = vec![];
+/// write_ndjson(&build, NameStyle::Main, &mut ndjson).expect("No I/O issues");
+///
+/// let ndjson = String::from_utf8(ndjson).expect("UTF-8");
+/// assert_eq!(ndjson.lines().count(), build.contigs().count());
+/// ```
+///
+/// ## Errors
+///
+/// I/O error of the underlying [`Write`].
+pub fn write_ndjson<C, W>(build: &GenomeBuild<C>, style: NameStyle, mut write: W) -> io::Result<()>
+where
+    C: Display,
+    W: Write,
+{
+    for contig in build.contigs() {
+        write!(write, "{{\"major_assembly\":")?;
+        write_json_str(&mut write, build.id().major_assembly())?;
+        write!(write, ",\"patch\":")?;
+        write_json_opt(&mut write, build.id().patch())?;
+        write!(write, ",")?;
+        write_json_contig(&mut write, contig, style)?;
+        writeln!(write, "}}")?;
+    }
+    Ok(())
+}
+
+/// Write the fields of the JSON object of the `contig`, without the braces.
+fn write_json_contig<C, W>(write: &mut W, contig: &Contig<C>, style: NameStyle) -> io::Result<()>
+where
+    C: Display,
+    W: Write,
+{
+    let name = styled_name(contig, style);
+    write!(write, "\"name\":")?;
+    write_json_str(write, name)?;
+    write!(write, ",\"alt_names\":[")?;
+    for (j, alt_name) in other_names(contig, name).enumerate() {
+        if j > 0 {
+            write!(write, ",")?;
+        }
+        write_json_str(write, alt_name)?;
+    }
+    write!(write, "],\"length\":{},\"role\":", contig.length())?;
+    write_json_opt(write, contig.role().map(|role| role.as_str()))?;
+    write!(write, ",\"md5\":")?;
+    write_json_opt(write, contig.md5())
+}
+
+/// Read a genome build from the JSON of [`write_json`] of any schema version.
+///
+/// The JSON of the older versions is migrated to the current version (see [`JSON_SCHEMA_VERSION`]),
+/// hence the caches written by the older versions of the crate keep loading.
+/// The values missing in the older versions, e.g. the metadata of version `1`, are unknown.
+/// The contigs keep the order of the JSON.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::{GenomeBuild, NameStyle};
+/// use dabuild::builds::toy;
+/// use dabuild::formats::{from_json_any_version, write_json};
+///
+/// let build: GenomeBuild<u32> = toy();
+/// let mut json = vec![];
+/// write_json(&build, NameStyle::Main, &mut json).expect("No I/O issues");
+///
+/// let read: GenomeBuild<u32> = from_json_any_version(json.as_slice()).expect("Valid JSON");
+/// assert_eq!(read, build);
+/// assert_eq!(read.metadata(), build.metadata());
+///
+/// // Version 1 had no schema version and no metadata.
+/// let v1 = r#"{"id":{"major_assembly":"toy","patch":null},
+///     "contigs":[{"name":"1","alt_names":["chr1"],"length":10,"role":null,"md5":null}]}"#;
+/// let read: GenomeBuild<u32> = from_json_any_version(v1.as_bytes()).expect("Valid JSON");
+/// assert_eq!(read.contig_by_name("chr1").map(|c| *c.length()), Some(10));
+/// ```
+///
+/// ## Errors
+///
+/// * I/O error of the underlying [`Read`](std::io::Read) or invalid JSON
+/// * Schema version newer than [`JSON_SCHEMA_VERSION`]
+/// * Missing or invalid field, e.g. the contig name or length
+/// * Invalid contig, e.g. a duplicate name or an invalid MD5 digest
+#[cfg(feature = "json")]
+pub fn from_json_any_version<C, R>(read: R) -> Result<GenomeBuild<C>, Box<dyn Error>>
+where
+    C: FromStr + Zero + PartialOrd,
+    R: std::io::Read,
+{
+    use serde_json::Value;
+
+    fn str_field<'a>(value: &'a Value, key: &str) -> Result<Option<&'a str>, String> {
+        match value.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value)),
+            Some(other) => Err(format!("Invalid {key:?} field {other}")),
+        }
+    }
+
+    let value: Value = serde_json::from_reader(read)?;
+    let version = match value.get("schema_version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| format!("Invalid schema version {version}"))?,
+    };
+    if version > JSON_SCHEMA_VERSION {
+        return Err(format!(
+            "Unsupported schema version {version}, the latest is {JSON_SCHEMA_VERSION}"
+        )
+        .into());
+    }
+
+    let id = value.get("id").ok_or("Missing \"id\" field")?;
+    let major_assembly = str_field(id, "major_assembly")?.ok_or("Missing major assembly")?;
+    let id = match str_field(id, "patch")? {
+        Some(patch) => GenomeBuildIdentifier::from((major_assembly, patch)),
+        None => GenomeBuildIdentifier::from_str(major_assembly)?,
+    };
+
+    let mut builder = GenomeBuild::builder()
+        .id(id)
+        .ordering(ContigOrdering::Insertion);
+    let contigs = value
+        .get("contigs")
+        .and_then(Value::as_array)
+        .ok_or("Missing \"contigs\" array")?;
+    for (i, contig) in contigs.iter().enumerate() {
+        let name =
+            str_field(contig, "name")?.ok_or_else(|| format!("Missing name of contig #{i}"))?;
+        let length = contig
+            .get("length")
+            .and_then(|length| length.to_string().parse::<C>().ok())
+            .ok_or_else(|| format!("Missing or invalid length of contig {name:?}"))?;
+        builder = builder.contig(name, length);
+        for alt_name in contig
+            .get("alt_names")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let alt_name = alt_name
+                .as_str()
+                .ok_or_else(|| format!("Invalid alternative name {alt_name} of contig {name:?}"))?;
+            builder = builder.alias(name, alt_name);
+        }
+        if let Some(role) = str_field(contig, "role")? {
+            builder = builder.role(name, role.parse()?);
+        }
+        if let Some(md5) = str_field(contig, "md5")? {
+            builder = builder.md5(name, md5);
+        }
+        let report_names = if version >= 4 {
+            [
+                str_field(contig, "genbank")?,
+                str_field(contig, "refseq")?,
+                str_field(contig, "ucsc")?,
+            ]
+        } else {
+            // Version 3 and older have no report names, hence look them up by the name styles
+            // among the names of the contigs of an assembly report, i.e. the contigs with a role.
+            let names = core::iter::once(name).chain(
+                contig
+                    .get("alt_names")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str),
+            );
+            let is_report = contig.get("role").is_some_and(|role| !role.is_null());
+            [NameStyle::GenBank, NameStyle::RefSeq, NameStyle::Ucsc]
+                .map(|style| names.clone().find(|&n| is_report && style.matches(n)))
+        };
+        if report_names.iter().any(Option::is_some) {
+            builder = builder.report_names(name, report_names.map(|n| n.map(String::from)));
+        }
+    }
+    let build = builder.build()?;
+
+    // Version 1 has no metadata.
+    let mut metadata = BuildMetadata::new();
+    if let Some(values) = value.get("metadata").filter(|_| version >= 2) {
+        // Version 2 has no organism, taxid, submitter, and date, hence unknown.
+        if let Some(organism) = str_field(values, "organism")? {
+            metadata = metadata.with_organism(organism);
+        }
+        match values.get("taxid") {
+            None | Some(Value::Null) => {}
+            Some(taxid) => {
+                let taxid = taxid
+                    .as_u64()
+                    .and_then(|taxid| u32::try_from(taxid).ok())
+                    .ok_or_else(|| format!("Invalid \"taxid\" field {taxid}"))?;
+                metadata = metadata.with_taxid(taxid);
+            }
+        }
+        if let Some(submitter) = str_field(values, "submitter")? {
+            metadata = metadata.with_submitter(submitter);
+        }
+        if let Some(date) = str_field(values, "date")? {
+            metadata = metadata.with_date(date);
+        }
+        if let Some(level) = str_field(values, "assembly_level")? {
+            metadata = metadata.with_assembly_level(level.parse()?);
+        }
+        if let Some(representation) = str_field(values, "genome_representation")? {
+            metadata = metadata.with_genome_representation(representation.parse()?);
+        }
+        if let Some(bioproject) = str_field(values, "bioproject")? {
+            metadata = metadata.with_bioproject(bioproject);
+        }
+        if let Some(biosample) = str_field(values, "biosample")? {
+            metadata = metadata.with_biosample(biosample);
+        }
+        if let Some(accession) = str_field(values, "genbank_accession")? {
+            metadata = metadata.with_genbank_accession(accession);
+        }
+        if let Some(accession) = str_field(values, "refseq_accession")? {
+            metadata = metadata.with_refseq_accession(accession);
+        }
+    }
+
+    Ok(build.with_metadata(metadata))
+}
+
+/// The formats that can be written and parsed back, see [`round_trip_report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundTripFormat {
+    /// Assembly report, see [`crate::builds::write_assembly_report`]
+    /// and [`crate::builds::parse_assembly_report`].
+    AssemblyReport,
+    /// Sequence dictionary, see [`write_dict`] and [`parse_dict`].
+    Dict,
+    /// `chrom.sizes`, see [`write_chrom_sizes`] and [`parse_chrom_sizes`].
+    ChromSizes,
+}
+
+impl RoundTripFormat {
+    /// All round-trip formats.
+    pub const ALL: [RoundTripFormat; 3] = [
+        RoundTripFormat::AssemblyReport,
+        RoundTripFormat::Dict,
+        RoundTripFormat::ChromSizes,
+    ];
+
+    /// Get the format name, e.g. `chrom-sizes`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoundTripFormat::AssemblyReport => "assembly-report",
+            RoundTripFormat::Dict => "dict",
+            RoundTripFormat::ChromSizes => "chrom-sizes",
+        }
+    }
+}
+
+/// Parse the format name, e.g. `dict`, ignoring the case.
+impl FromStr for RoundTripFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RoundTripFormat::ALL
+            .into_iter()
+            .find(|format| format.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown round-trip format {s:?}"))
+    }
+}
+
+impl Display for RoundTripFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A part of a genome build that does not survive a round trip through a format.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Dropped {
+    /// The alternative names of a contig that the format cannot hold.
+    AltNames {
+        /// The main name of the contig.
+        contig: String,
+        /// The dropped names.
+        names: Vec<String>,
+    },
+    /// The alternative names of a contig are kept, but in a different order.
+    AltNameOrder {
+        /// The main name of the contig.
+        contig: String,
+    },
+    /// The sequence role of a contig.
+    Role {
+        /// The main name of the contig.
+        contig: String,
+    },
+    /// The MD5 digest of a contig.
+    Md5 {
+        /// The main name of the contig.
+        contig: String,
+    },
+    /// The build metadata.
+    Metadata,
+    /// The order of the contigs, since the parser sorts the contigs by name.
+    Order,
+}
+
+/// The parts of a genome build dropped by a round trip through a format,
+/// see [`round_trip_report`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RoundTripReport {
+    format: RoundTripFormat,
+    dropped: Vec<Dropped>,
+}
+
+impl RoundTripReport {
+    /// Get the format of the round trip.
+    pub fn format(&self) -> RoundTripFormat {
+        self.format
+    }
+
+    /// Get the dropped parts of the build, in the order of the contigs.
+    pub fn dropped(&self) -> &[Dropped] {
+        &self.dropped
+    }
+
+    /// Test if the round trip keeps the build intact.
+    pub fn is_lossless(&self) -> bool {
+        self.dropped.is_empty()
+    }
+}
+
+/// Find the parts of the `build` that do not survive a round trip through the `format`.
+///
+/// The round trip writes the build with the [`NameStyle::Main`] names
+/// and parses it back using the build identifier.
+/// If the report is lossless, the parsed build is guaranteed to be equal to the `build`,
+/// including the contig order, the alternative names, the roles, and the MD5 digests.
+/// The guarantee needs a build without duplicate names (see [`GenomeBuild::validate`]),
+/// with the lengths that are written and parsed back losslessly by `C`.
+///
+/// The formats keep:
+///
+/// * assembly report - the GenBank, RefSeq, and UCSC-style names, the roles, and the metadata
+/// * `.dict` - all names, the MD5 digests, and the contig order
+/// * `chrom.sizes` - the main names and the contig order
+///
+/// ## Example
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use dabuild::{GenomeBuild, GenomeBuildIdentifier, NameStyle};
+/// use dabuild::builds::toy;
+/// use dabuild::formats::{parse_dict, round_trip_report, write_dict, Dropped, RoundTripFormat};
+///
+/// let build: GenomeBuild<u32> = toy();
+///
+/// let report = round_trip_report(&build, RoundTripFormat::Dict);
+/// assert_eq!(report.dropped()[0], Dropped::Role { contig: "1".into() });
+/// assert!(report.dropped().contains(&Dropped::Metadata));
+///
+/// let report = round_trip_report(&build, RoundTripFormat::AssemblyReport);
+/// assert!(report.is_lossless());
+/// ```
+pub fn round_trip_report<C>(build: &GenomeBuild<C>, format: RoundTripFormat) -> RoundTripReport {
+    let mut dropped = vec![];
+    for contig in build.contigs() {
+        let name = || contig.name().to_string();
+        let alt_names: Vec<_> = contig.alt_names().collect();
+        let kept: Vec<&str> = match format {
+            RoundTripFormat::AssemblyReport => {
+                let mut kept = vec![];
+                for column in contig.report_columns().into_iter().flatten() {
+                    if column != contig.name() && !kept.contains(&column) {
+                        kept.push(column);
+                    }
+                }
+                kept
+            }
+            // The `AN` tag is a comma-separated list.
+            RoundTripFormat::Dict => alt_names
+                .iter()
+                .copied()
+                .filter(|name| !name.contains(','))
+                .collect(),
+            RoundTripFormat::ChromSizes => vec![],
+        };
+        let lost: Vec<_> = alt_names
+            .iter()
+            .filter(|name| !kept.contains(name))
+            .map(|name| name.to_string())
+            .collect();
+        if !lost.is_empty() {
+            dropped.push(Dropped::AltNames {
+                contig: name(),
+                names: lost,
+            });
+        } else if kept != alt_names {
+            dropped.push(Dropped::AltNameOrder { contig: name() });
+        }
+
+        if contig.role().is_some() && format != RoundTripFormat::AssemblyReport {
+            dropped.push(Dropped::Role { contig: name() });
+        }
+        if contig.md5().is_some() && format != RoundTripFormat::Dict {
+            dropped.push(Dropped::Md5 { contig: name() });
+        }
+    }
+
+    if format != RoundTripFormat::AssemblyReport && *build.metadata() != BuildMetadata::default() {
+        dropped.push(Dropped::Metadata);
+    }
+    if format == RoundTripFormat::AssemblyReport && !build.order().is_sorted_by(|l, r| l <= r) {
+        dropped.push(Dropped::Order);
+    }
+
+    RoundTripReport { format, dropped }
+}
+
+fn write_json_opt<W: Write>(write: &mut W, value: Option<&str>) -> io::Result<()> {
+    match value {
+        Some(value) => write_json_str(write, value),
+        None => write!(write, "null"),
+    }
+}
+
+fn write_json_str<W: Write>(write: &mut W, value: &str) -> io::Result<()> {
+    write!(write, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(write, "\\\"")?,
+            '\\' => write!(write, "\\\\")?,
+            c if c.is_control() => write!(write, "\\u{:04x}", c as u32)?,
+            c => write!(write, "{c}")?,
+        }
+    }
+    write!(write, "\"")
+}
+
+/// Get the contig name in the `style`, falling back to the main name.
+pub(crate) fn styled_name<C>(contig: &Contig<C>, style: NameStyle) -> &str {
+    contig.name_in(style).unwrap_or(contig.name())
+}
+
+/// Get the contig names except for the `name`.
+fn other_names<'a, C>(contig: &'a Contig<C>, name: &'a str) -> impl Iterator<Item = &'a str> {
+    contig.names().filter(move |&n| n != name)
+}
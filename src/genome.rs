@@ -6,31 +6,225 @@
  *                                               Contig
  * ***************************************************************************************************************** */
 
-use std::str::FromStr;
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    str::FromStr,
+    sync::atomic::{AtomicBool, AtomicU32, Ordering as AtomicOrdering},
+};
 
-use num_traits::{CheckedSub, Zero};
+use num_traits::{CheckedAdd, CheckedSub, Zero};
+use smallvec::SmallVec;
+
+use crate::{
+    index::{IndexKind, NameIndex},
+    karyotype::{strip_chr, Karyotype, Sex},
+    metadata::BuildMetadata,
+    phf::{self, PerfectIndex},
+    region::{contig_windows, GenomicRegion, RegionSet},
+};
 
 /// The contig data, such as identifiers and its length.
 ///
 /// `C` is the data type to represent the number of contig's base pairs.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// The names are packed into a single reference-counted string,
+/// hence cloning a contig does not copy the names.
+/// The name boundaries of up to three alternative names are stored inline.
+///
+/// The contigs are compared and hashed by their data,
+/// ignoring the [`Contig::source_index`], the [`Contig::introduced_in`],
+/// and which alternative names are the report accessions, e.g. [`Contig::genbank_accession`].
+#[derive(Debug, Clone)]
 pub struct Contig<C> {
-    name: String,
-    alt_names: Vec<String>,
+    /// The main name followed by the alternative names, and the MD5 digest if known.
+    names: Arc<str>,
+    /// The end offsets of the names (and the MD5 digest) in `names`.
+    ends: SmallVec<[u32; 4]>,
     length: C,
+    role: Option<SequenceRole>,
+    /// `true` if the last segment of `names` is the MD5 digest.
+    has_md5: bool,
+    /// The 0-based index of the line or record the contig was parsed from.
+    source_index: Option<usize>,
+    /// The patch release that introduced the contig, `0` for the initial release.
+    introduced_in: Option<u8>,
+    /// The 1-based positions of the GenBank, RefSeq, and UCSC-style names in the names,
+    /// `0` if unknown.
+    report_names: [u8; 3],
 }
 
 impl<C> Contig<C> {
+    /// Get the number of heap bytes of the names and the name boundaries.
+    pub(crate) fn heap_size(&self) -> usize {
+        let ends = if self.ends.spilled() {
+            self.ends.capacity() * core::mem::size_of::<u32>()
+        } else {
+            0
+        };
+        arc_size(self.names.len()) + ends
+    }
+
     /// Get the main name of the contig (e.g. `10`, `X`, `MT`).
     pub fn name(&self) -> &str {
-        &self.name
+        &self.names[..self.ends[0] as usize]
+    }
+
+    /// Test if the `name` is the main name or an alternative name of the contig.
+    ///
+    /// The names are compared exactly, as in [`GenomeBuild::contig_by_name`].
+    /// The contig also compares equal to a string with the same logic.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::Contig;
+    ///
+    /// let contig = Contig::new("1", &["chr1", "NC_000001.11"], 248_956_422u32).unwrap();
+    ///
+    /// assert!(contig.matches("chr1"));
+    /// assert!(!contig.matches("CHR1"));
+    /// assert!(contig == "NC_000001.11");
+    /// ```
+    pub fn matches(&self, name: &str) -> bool {
+        self.name() == name || self.alt_names().any(|alt_name| alt_name == name)
     }
 
     /// Get the alternative contig identifiers.
     ///
     /// For instance, `CM000686.2`, `NC_000024.10`, and `chrY` for chromosome `Y`.
     pub fn alt_names(&self) -> impl Iterator<Item = &str> {
-        self.alt_names.iter().map(AsRef::as_ref)
+        self.ends[..self.ends.len() - usize::from(self.has_md5)]
+            .windows(2)
+            .map(|w| &self.names[w[0] as usize..w[1] as usize])
+    }
+
+    /// Get an iterator over the main name followed by the alternative names.
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        core::iter::once(self.name()).chain(self.alt_names())
+    }
+
+    /// Add the `alt_name` after the alternative names of the contig.
+    pub(crate) fn push_alt_name(&mut self, alt_name: &str) {
+        let idx = self.ends.len() - usize::from(self.has_md5);
+        let at = self.ends[idx - 1] as usize;
+        let mut names = String::with_capacity(self.names.len() + alt_name.len());
+        names.push_str(&self.names[..at]);
+        names.push_str(alt_name);
+        names.push_str(&self.names[at..]);
+        // Check the total length before shifting the offsets.
+        Self::offset(&names);
+
+        let added = Self::offset(alt_name);
+        self.ends.insert(idx, self.ends[idx - 1] + added);
+        for end in &mut self.ends[idx + 1..] {
+            *end += added;
+        }
+        self.names = Arc::from(names);
+    }
+
+    /// Get the first of the contig names that follows the naming `style`,
+    /// or `None` if the contig has no such name.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{Contig, NameStyle};
+    ///
+    /// let contig = Contig::new("Y", &["CM000686.2", "NC_000024.10", "chrY"], 57_227_415u32).unwrap();
+    ///
+    /// assert_eq!(contig.name_in(NameStyle::RefSeq), Some("NC_000024.10"));
+    /// assert_eq!(contig.name_in(NameStyle::Ucsc), Some("chrY"));
+    /// ```
+    pub fn name_in(&self, style: NameStyle) -> Option<&str> {
+        match style {
+            NameStyle::Main => Some(self.name()),
+            _ => self.names().find(|name| style.matches(name)),
+        }
+    }
+
+    /// Get the GenBank accession of the contig, e.g. `CM000663.2`,
+    /// or `None` if unknown.
+    ///
+    /// The accession is known for the contigs of an assembly report, from column #4.
+    /// Use [`Contig::name_in`] to find the name by its shape instead.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let chr1 = build.contig_by_name("chr1").expect("Known contig");
+    ///
+    /// assert_eq!(chr1.genbank_accession(), Some("CM000663.2"));
+    /// assert_eq!(chr1.refseq_accession(), Some("NC_000001.11"));
+    /// assert_eq!(chr1.ucsc_name(), Some("chr1"));
+    /// ```
+    pub fn genbank_accession(&self) -> Option<&str> {
+        self.report_name(0)
+    }
+
+    /// Get the RefSeq accession of the contig, e.g. `NC_000001.11`,
+    /// or `None` if unknown.
+    ///
+    /// The accession is known for the contigs of an assembly report, from column #6.
+    /// See [`Contig::genbank_accession`] for an example.
+    pub fn refseq_accession(&self) -> Option<&str> {
+        self.report_name(1)
+    }
+
+    /// Get the UCSC-style name of the contig, e.g. `chr1`, or `None` if unknown.
+    ///
+    /// The name is known for the contigs of an assembly report, from column #9.
+    /// See [`Contig::genbank_accession`] for an example.
+    pub fn ucsc_name(&self) -> Option<&str> {
+        self.report_name(2)
+    }
+
+    /// Set the GenBank, RefSeq, and UCSC-style names of the contig, if known.
+    ///
+    /// Returns `None` if a name is not among the names of the contig.
+    pub(crate) fn with_report_names(mut self, columns: [Option<&str>; 3]) -> Option<Self> {
+        let mut positions = [0; 3];
+        for (position, column) in positions.iter_mut().zip(columns) {
+            if let Some(column) = column {
+                // The names repeated in several columns are kept once, hence look up their positions.
+                let found = self.names().position(|name| name == column)?;
+                *position = u8::try_from(found + 1).ok()?;
+            }
+        }
+        self.report_names = positions;
+        Some(self)
+    }
+
+    /// Get the GenBank, RefSeq, and UCSC-style names of the assembly report columns,
+    /// the names of the report if known, or the names in the [`NameStyle`] otherwise.
+    #[cfg(feature = "std")]
+    pub(crate) fn report_columns(&self) -> [Option<&str>; 3] {
+        [
+            self.genbank_accession()
+                .or_else(|| self.name_in(NameStyle::GenBank)),
+            self.refseq_accession()
+                .or_else(|| self.name_in(NameStyle::RefSeq)),
+            self.ucsc_name().or_else(|| self.name_in(NameStyle::Ucsc)),
+        ]
+    }
+
+    fn report_name(&self, column: usize) -> Option<&str> {
+        match self.report_names[column] {
+            0 => None,
+            position => self.names().nth(usize::from(position) - 1),
+        }
     }
 
     /// Get the number of bases of the contig
@@ -38,6 +232,190 @@ impl<C> Contig<C> {
         &self.length
     }
 
+    /// Get the number of bases of the contig by value, e.g. for arithmetic
+    /// without dereferencing [`Contig::length`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::Contig;
+    ///
+    /// let contig = Contig::new("MT", &["chrM"], 16_569u32).unwrap();
+    /// let middle = contig.length_copied() / 2;
+    /// assert_eq!(middle, 8_284);
+    /// ```
+    pub fn length_copied(&self) -> C
+    where
+        C: Copy,
+    {
+        self.length
+    }
+
+    /// Get the MD5 digest of the contig sequence as 32 lowercase hexadecimal digits,
+    /// or `None` if the digest is unknown.
+    ///
+    /// The digest is known e.g. for the contigs loaded from a sequence dictionary with `M5` tags.
+    pub fn md5(&self) -> Option<&str> {
+        if self.has_md5 {
+            let start = self.ends[self.ends.len() - 2] as usize;
+            Some(&self.names[start..])
+        } else {
+            None
+        }
+    }
+
+    /// Set the MD5 digest of the contig sequence.
+    ///
+    /// Returns `None` if the `md5` is not 32 hexadecimal digits.
+    pub fn with_md5(mut self, md5: &str) -> Option<Self> {
+        if md5.len() != 32 || !md5.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        if self.has_md5 {
+            self.ends.pop();
+        }
+        let mut names = String::from(&self.names[..self.ends[self.ends.len() - 1] as usize]);
+        names.push_str(&md5.to_ascii_lowercase());
+        self.ends.push(Self::offset(&names));
+        self.names = Arc::from(names);
+        self.has_md5 = true;
+        Some(self)
+    }
+
+    /// Get the role of the contig in the assembly,
+    /// or `None` if the role is unknown (e.g. for contigs loaded from a FASTA index).
+    pub fn role(&self) -> Option<SequenceRole> {
+        self.role
+    }
+
+    /// Set the role of the contig in the assembly.
+    pub fn with_role(mut self, role: SequenceRole) -> Self {
+        self.role = Some(role);
+        self
+    }
+
+    /// Get the category of the contig, or `None` if the role is unknown.
+    ///
+    /// The assembled molecules are classified by name:
+    /// `X`, `Y`, `Z`, and `W` are sex chromosomes, `M` and `MT` are mitochondrial,
+    /// ignoring the case and the `chr` prefix, and the other molecules are autosomes.
+    pub fn category(&self) -> Option<ContigCategory> {
+        Some(match self.role? {
+            SequenceRole::AssembledMolecule => {
+                let is_any = |candidates: &[&str]| {
+                    core::iter::once(self.name())
+                        .chain(self.alt_names())
+                        .map(strip_chr)
+                        .any(|name| {
+                            candidates
+                                .iter()
+                                .any(|candidate| candidate.eq_ignore_ascii_case(name))
+                        })
+                };
+                if is_any(&["X", "Y", "Z", "W"]) {
+                    ContigCategory::SexChromosome
+                } else if is_any(&["M", "MT"]) {
+                    ContigCategory::Mitochondrial
+                } else {
+                    ContigCategory::Autosome
+                }
+            }
+            SequenceRole::UnlocalizedScaffold => ContigCategory::Unlocalized,
+            SequenceRole::UnplacedScaffold => ContigCategory::Unplaced,
+            SequenceRole::AltScaffold => ContigCategory::AltScaffold,
+            SequenceRole::FixPatch | SequenceRole::NovelPatch => ContigCategory::Patch,
+        })
+    }
+
+    /// Test if the contig is a common decoy sequence, added to a reference
+    /// to attract the reads of sequences missing from the assembly.
+    ///
+    /// Any name of the contig is matched against:
+    ///
+    /// * `hs37d5` - the decoy of the 1000 Genomes GRCh37 reference
+    /// * `EBV` or `chrEBV` (`NC_007605`) - the Epstein-Barr virus genome
+    /// * `HLA-*` - the HLA alleles, e.g. `HLA-A*01:01:01:01`
+    /// * `*_decoy` - the decoys of the GRCh38 analysis sets, e.g. `chrUn_JTFH01000001v1_decoy`
+    pub fn is_decoy(&self) -> bool {
+        core::iter::once(self.name())
+            .chain(self.alt_names())
+            .any(|name| {
+                name.eq_ignore_ascii_case("hs37d5")
+                    || strip_chr(name).eq_ignore_ascii_case("EBV")
+                    || name.starts_with("NC_007605")
+                    || name.starts_with("HLA-")
+                    || name.ends_with("_decoy")
+            })
+    }
+
+    /// Get the 0-based index of the line (or record) of the input file
+    /// the contig was parsed from, or `None` if the contig was not parsed from a file.
+    ///
+    /// The index counts all lines, including the header lines,
+    /// and it matches the line numbers reported in the parsing errors.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use dabuild::{GenomeBuild, GenomeBuildIdentifier};
+    /// use dabuild::formats::parse_chrom_sizes;
+    ///
+    /// let sizes = "chr1\t248956422\n\nchr2\t242193529\n";
+    /// let build: GenomeBuild<u32> = parse_chrom_sizes(
+    ///     GenomeBuildIdentifier::from_str("hg38").expect("Infallible"),
+    ///     sizes.as_bytes(),
+    /// ).expect("Valid chrom.sizes");
+    ///
+    /// let chr2 = build.contig_by_name("chr2").expect("Known contig");
+    /// assert_eq!(chr2.source_index(), Some(2));
+    /// ```
+    pub fn source_index(&self) -> Option<usize> {
+        self.source_index
+    }
+
+    /// Set the 0-based index of the line (or record) the contig was parsed from.
+    pub fn with_source_index(mut self, index: usize) -> Self {
+        self.source_index = Some(index);
+        self
+    }
+
+    /// Get the number of the patch release of the assembly that introduced the contig,
+    /// `0` for the initial release, e.g. `13` for a patch of *GRCh38.p13*,
+    /// or `None` if unknown.
+    ///
+    /// The contigs of the bundled *GRCh38* build are annotated from the GRC patch releases,
+    /// e.g. to warn when data references contigs newer than the declared patch level.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let declared = 5; // GRCh38.p5
+    ///
+    /// let chr1 = build.contig_by_name("chr1").expect("Known contig");
+    /// assert_eq!(chr1.introduced_in(), Some(0));
+    /// let patch = build.contig_by_name("KZ208904.1").expect("Known contig");
+    /// assert_eq!(patch.introduced_in(), Some(11));
+    /// assert!(patch.introduced_in().is_some_and(|patch| patch > declared));
+    /// ```
+    pub fn introduced_in(&self) -> Option<u8> {
+        self.introduced_in
+    }
+
+    /// Set the number of the patch release that introduced the contig, `0` for the initial release.
+    pub fn with_introduced_in(mut self, patch: u8) -> Self {
+        self.introduced_in = Some(patch);
+        self
+    }
+
+    fn offset(names: &str) -> u32 {
+        u32::try_from(names.len()).expect("Contig names should be shorter than 4GiB")
+    }
+
     /// Transpose coordinate on a double-stranded sequence to the opposite strand.
     ///
     /// Returns `None` if the operation would lead to underflow.
@@ -53,6 +431,15 @@ impl<C> Contig<C>
 where
     C: Zero + PartialOrd,
 {
+    /// Create a contig from the main name, alternative names, and the length.
+    ///
+    /// The alternative names that repeat the main name or a preceding alternative name are dropped.
+    ///
+    /// Returns `None` if the length is negative.
+    ///
+    /// ## Panics
+    ///
+    /// If the names are longer than [`u32::MAX`] bytes in total.
     pub fn new<T, U>(name: T, alt_names: &[U], length: C) -> Option<Self>
     where
         T: ToString,
@@ -61,18 +448,367 @@ where
         if length < C::zero() {
             None
         } else {
-            Some(Self {
-                name: name.to_string(),
-                alt_names: alt_names.iter().map(ToString::to_string).collect(),
-                length,
-            })
+            let mut names = name.to_string();
+            let mut ends = SmallVec::with_capacity(alt_names.len() + 1);
+            ends.push(Self::offset(&names));
+            for alt_name in alt_names {
+                Self::push_name(&mut names, &mut ends, &alt_name.to_string());
+            }
+
+            Some(Self::packed(names, ends, length))
+        }
+    }
+
+    /// Create a contig from borrowed names, without allocating the names one by one.
+    ///
+    /// Returns `None` if the length is negative.
+    pub(crate) fn from_names(name: &str, alt_names: &[&str], length: C) -> Option<Self> {
+        if length < C::zero() {
+            return None;
+        }
+        let capacity = name.len() + alt_names.iter().map(|name| name.len()).sum::<usize>();
+        let mut names = String::with_capacity(capacity);
+        names.push_str(name);
+        let mut ends = SmallVec::new();
+        ends.push(Self::offset(&names));
+        for alt_name in alt_names {
+            Self::push_name(&mut names, &mut ends, alt_name);
+        }
+
+        Some(Self::packed(names, ends, length))
+    }
+
+    /// Append the `name` to the packed `names`, unless it is already there.
+    ///
+    /// Some assembly reports repeat an accession in several columns,
+    /// hence the duplicates are dropped to keep each name once.
+    fn push_name(names: &mut String, ends: &mut SmallVec<[u32; 4]>, name: &str) {
+        let mut start = 0;
+        for &end in ends.iter() {
+            if &names[start..end as usize] == name {
+                return;
+            }
+            start = end as usize;
+        }
+        names.push_str(name);
+        ends.push(Self::offset(names));
+    }
+
+    fn packed(names: String, ends: SmallVec<[u32; 4]>, length: C) -> Self {
+        Self {
+            names: Arc::from(names),
+            ends,
+            length,
+            role: None,
+            has_md5: false,
+            source_index: None,
+            introduced_in: None,
+            report_names: [0; 3],
+        }
+    }
+}
+
+impl<C> PartialEq for Contig<C>
+where
+    C: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.names == other.names
+            && self.ends == other.ends
+            && self.length == other.length
+            && self.role == other.role
+            && self.has_md5 == other.has_md5
+    }
+}
+
+impl<C> Eq for Contig<C> where C: Eq {}
+
+impl<C> Hash for Contig<C>
+where
+    C: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.names.hash(state);
+        self.ends.hash(state);
+        self.length.hash(state);
+        self.role.hash(state);
+        self.has_md5.hash(state);
+    }
+}
+
+/// A contig equals a string if the string is any of the contig names, see [`Contig::matches`].
+impl<C> PartialEq<str> for Contig<C> {
+    fn eq(&self, other: &str) -> bool {
+        self.matches(other)
+    }
+}
+
+impl<C> PartialEq<&str> for Contig<C> {
+    fn eq(&self, other: &&str) -> bool {
+        self.matches(other)
+    }
+}
+
+/// Contigs are ordered by the main name, the alternative names, the length, the role,
+/// and the MD5 digest.
+impl<C> PartialOrd for Contig<C>
+where
+    C: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.names().cmp(other.names()) {
+            Ordering::Equal => match self.length.partial_cmp(&other.length) {
+                Some(Ordering::Equal) => Some(
+                    self.role
+                        .cmp(&other.role)
+                        .then_with(|| self.md5().cmp(&other.md5())),
+                ),
+                ordering => ordering,
+            },
+            ordering => Some(ordering),
+        }
+    }
+}
+
+impl<C> Ord for Contig<C>
+where
+    C: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.names()
+            .cmp(other.names())
+            .then_with(|| self.length.cmp(&other.length))
+            .then_with(|| self.role.cmp(&other.role))
+            .then_with(|| self.md5().cmp(&other.md5()))
+    }
+}
+
+/// The role of a sequence in the assembly, as reported in the `Sequence-Role` column
+/// of the assembly report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SequenceRole {
+    /// A chromosome or an organelle genome (e.g. `1`, `X`, `MT`).
+    AssembledMolecule,
+    /// A scaffold with a known chromosome, but unknown location and orientation.
+    UnlocalizedScaffold,
+    /// A scaffold with an unknown chromosome.
+    UnplacedScaffold,
+    /// An alternate locus.
+    AltScaffold,
+    /// A patch that corrects an error of the assembly.
+    FixPatch,
+    /// A patch that adds a new alternate locus.
+    NovelPatch,
+}
+
+impl SequenceRole {
+    /// All sequence roles.
+    pub const ALL: [SequenceRole; 6] = [
+        SequenceRole::AssembledMolecule,
+        SequenceRole::UnlocalizedScaffold,
+        SequenceRole::UnplacedScaffold,
+        SequenceRole::AltScaffold,
+        SequenceRole::FixPatch,
+        SequenceRole::NovelPatch,
+    ];
+
+    /// Get the role as written in the assembly report, e.g. `assembled-molecule`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SequenceRole::AssembledMolecule => "assembled-molecule",
+            SequenceRole::UnlocalizedScaffold => "unlocalized-scaffold",
+            SequenceRole::UnplacedScaffold => "unplaced-scaffold",
+            SequenceRole::AltScaffold => "alt-scaffold",
+            SequenceRole::FixPatch => "fix-patch",
+            SequenceRole::NovelPatch => "novel-patch",
+        }
+    }
+}
+
+/// Parse the role from the `Sequence-Role` value of the assembly report.
+impl FromStr for SequenceRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "assembled-molecule" => Ok(SequenceRole::AssembledMolecule),
+            "unlocalized-scaffold" => Ok(SequenceRole::UnlocalizedScaffold),
+            "unplaced-scaffold" => Ok(SequenceRole::UnplacedScaffold),
+            "alt-scaffold" => Ok(SequenceRole::AltScaffold),
+            "fix-patch" => Ok(SequenceRole::FixPatch),
+            "novel-patch" => Ok(SequenceRole::NovelPatch),
+            _ => Err(format!("Unknown sequence role {s:?}")),
+        }
+    }
+}
+
+impl core::fmt::Display for SequenceRole {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The category of a contig, derived from its [`SequenceRole`] and names,
+/// see [`Contig::category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ContigCategory {
+    /// An assembled molecule other than a sex chromosome or a mitochondrion (e.g. `1`).
+    Autosome,
+    /// A sex chromosome (e.g. `X`).
+    SexChromosome,
+    /// The mitochondrial genome (e.g. `MT`).
+    Mitochondrial,
+    /// A scaffold with a known chromosome, but unknown location and orientation.
+    Unlocalized,
+    /// A scaffold with an unknown chromosome.
+    Unplaced,
+    /// An alternate locus.
+    AltScaffold,
+    /// A fix or a novel patch.
+    Patch,
+}
+
+/// The naming style of contig names.
+///
+/// The style of a name is recognized from its shape,
+/// e.g. `NC_000001.11` is a RefSeq accession and `chr1` is a UCSC-style name.
+/// See [`Contig::name_in`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NameStyle {
+    /// The main name of the contig, e.g. the `Sequence-Name` of the assembly report (`1`, `X`, `MT`).
+    Main,
+    /// GenBank accession, e.g. `CM000663.2`.
+    GenBank,
+    /// RefSeq accession, e.g. `NC_000001.11`.
+    RefSeq,
+    /// UCSC-style name, e.g. `chr1`.
+    Ucsc,
+}
+
+impl NameStyle {
+    /// All naming styles.
+    pub const ALL: [NameStyle; 4] = [
+        NameStyle::Main,
+        NameStyle::GenBank,
+        NameStyle::RefSeq,
+        NameStyle::Ucsc,
+    ];
+
+    /// Get the style name, e.g. `refseq`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NameStyle::Main => "main",
+            NameStyle::GenBank => "genbank",
+            NameStyle::RefSeq => "refseq",
+            NameStyle::Ucsc => "ucsc",
+        }
+    }
+
+    /// Test if the `name` follows the style.
+    ///
+    /// Any name follows the [`NameStyle::Main`] style.
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            NameStyle::Main => true,
+            NameStyle::GenBank => {
+                matches!(accession_prefix(name), Some(prefix) if !prefix.contains('_'))
+            }
+            NameStyle::RefSeq => {
+                matches!(accession_prefix(name), Some(prefix) if prefix.len() == 3 && prefix.ends_with('_'))
+            }
+            NameStyle::Ucsc => name.starts_with("chr"),
         }
     }
 }
 
+/// Get the number of bytes of a reference-counted allocation with `size` bytes of data.
+pub(crate) fn arc_size(size: usize) -> usize {
+    2 * core::mem::size_of::<usize>() + size
+}
+
+/// Get the prefix of a versioned accession (e.g. `NC_` of `NC_000001.11`),
+/// or `None` if the `name` is not an accession.
+fn accession_prefix(name: &str) -> Option<&str> {
+    let (accession, version) = name.split_once('.')?;
+    let digits = accession.trim_start_matches(|c: char| c.is_ascii_uppercase() || c == '_');
+    let prefix = &accession[..accession.len() - digits.len()];
+
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    (!prefix.is_empty() && is_number(digits) && is_number(version)).then_some(prefix)
+}
+
+/// Parse the style name, e.g. `refseq`, ignoring the case.
+impl FromStr for NameStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NameStyle::ALL
+            .into_iter()
+            .find(|style| style.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown name style {s:?}"))
+    }
+}
+
+impl core::fmt::Display for NameStyle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Contig;
+    use alloc::{vec, vec::Vec};
+
+    use super::{Contig, NameStyle};
+
+    #[test]
+    fn test_duplicate_alt_names() {
+        let contig = Contig::new("Y", &["CM000686.2", "Y", "chrY", "CM000686.2"], 10u8).unwrap();
+        assert_eq!(
+            contig.alt_names().collect::<Vec<_>>(),
+            ["CM000686.2", "chrY"]
+        );
+
+        let contig = Contig::from_names("chrM", &["NC_012920.1", "chrM", "NC_012920.1"], 10u8)
+            .unwrap()
+            .with_md5("c68f52674c9fb33aef52dcf399755519")
+            .unwrap();
+        assert_eq!(contig.alt_names().collect::<Vec<_>>(), ["NC_012920.1"]);
+        assert_eq!(contig.md5(), Some("c68f52674c9fb33aef52dcf399755519"));
+    }
+
+    #[test]
+    fn test_matches() {
+        let contig = Contig::new("Y", &["chrY", "NC_000024.10"], 10u8)
+            .unwrap()
+            .with_md5("ce3e31103314a704255f3cd90369ecce")
+            .unwrap();
+
+        assert!(contig.matches("Y"));
+        assert!(contig.matches("NC_000024.10"));
+        assert!(!contig.matches("ce3e31103314a704255f3cd90369ecce"));
+        assert!(!contig.matches("chr"));
+        assert_eq!(contig, "chrY");
+        assert_ne!(contig, *"X");
+    }
+
+    #[test]
+    fn test_is_decoy() {
+        for name in [
+            "hs37d5",
+            "chrEBV",
+            "NC_007605.1",
+            "HLA-DRB1*15:01:01:01",
+            "chrUn_JTFH01000001v1_decoy",
+        ] {
+            assert!(Contig::new(name, &[] as &[&str], 10u8).unwrap().is_decoy());
+        }
+
+        let chr1 = Contig::new("1", &["chr1", "NC_000001.11"], 10u8).unwrap();
+        assert!(!chr1.is_decoy());
+        let ebv = Contig::new("NC_007605", &["chrEBV"], 10u8).unwrap();
+        assert!(ebv.is_decoy());
+    }
 
     #[test]
     fn test_transpose_coordinate() {
@@ -88,6 +824,55 @@ mod tests {
 
         assert!(contig.transpose_coordinate(&11).is_none())
     }
+
+    #[test]
+    fn test_ordering_by_names() {
+        let one = Contig::new("1", &["chr1"], 10u8).unwrap();
+        let ten = Contig::new("10", &[] as &[&str], 5u8).unwrap();
+        let two = Contig::new("2", &["chr2"], 20u8).unwrap();
+
+        assert!(one < ten);
+        assert!(ten < two);
+        assert_eq!(ten.alt_names().count(), 0);
+    }
+
+    #[test]
+    fn test_md5() {
+        let contig = Contig::new("chrM", &["MT"], 16_569u32).unwrap();
+        assert_eq!(contig.md5(), None);
+
+        let contig = contig
+            .with_md5("C68F52674C9FB33AEF52DCF399755519")
+            .unwrap()
+            .with_md5("c68f52674c9fb33aef52dcf399755519")
+            .unwrap();
+        assert_eq!(contig.md5(), Some("c68f52674c9fb33aef52dcf399755519"));
+        assert_eq!(contig.alt_names().collect::<Vec<_>>(), vec!["MT"]);
+        assert!(contig.clone().with_md5("c68f").is_none());
+    }
+
+    #[test]
+    fn test_name_styles() {
+        let cases = [
+            ("CM000663.2", NameStyle::GenBank),
+            ("J01415.2", NameStyle::GenBank),
+            ("NC_000001.11", NameStyle::RefSeq),
+            ("NT_187361.1", NameStyle::RefSeq),
+            ("chr1_KI270706v1_random", NameStyle::Ucsc),
+        ];
+        for (name, style) in cases {
+            let matching: Vec<_> = NameStyle::ALL
+                .into_iter()
+                .filter(|s| *s != NameStyle::Main && s.matches(name))
+                .collect();
+            assert_eq!(matching, vec![style], "{name}");
+        }
+
+        for name in ["1", "MT", "HSCHR1_CTG1_UNLOCALIZED", "CM000663", "NC_.1"] {
+            assert!(!NameStyle::GenBank.matches(name), "{name}");
+            assert!(!NameStyle::RefSeq.matches(name), "{name}");
+        }
+    }
 }
 
 /* ***************************************************************************************************************** *
@@ -102,20 +887,58 @@ pub struct GenomeBuildIdentifier {
 }
 
 /// Create [`GenomeBuildIdentifier`] from a `&str`,
-/// using it as a major assembly.
+/// splitting a `.pN` suffix into the patch, e.g. `GRCh38` and `p13` from `GRCh38.p13`.
+/// A `&str` without the suffix is used as the major assembly.
 ///
 /// Infallible.
+///
+/// ## Example
+///
+/// ```
+/// use std::str::FromStr;
+/// use dabuild::GenomeBuildIdentifier;
+///
+/// let id = GenomeBuildIdentifier::from_str("GRCh38.p13").expect("Infallible");
+/// assert_eq!(id.major_assembly(), "GRCh38");
+/// assert_eq!(id.patch(), Some("p13"));
+/// assert_eq!(id.to_string(), "GRCh38.p13");
+///
+/// let id = GenomeBuildIdentifier::from_str("T2T-CHM13v2.0").expect("Infallible");
+/// assert_eq!(id.major_assembly(), "T2T-CHM13v2.0");
+/// assert_eq!(id.patch(), None);
+/// ```
 impl FromStr for GenomeBuildIdentifier {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(GenomeBuildIdentifier {
-            major_assembly: s.to_string(),
-            patch: None,
+        Ok(match s.rsplit_once('.') {
+            Some((major, patch))
+                if !major.is_empty()
+                    && patch.len() > 1
+                    && patch.starts_with('p')
+                    && patch[1..].bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                GenomeBuildIdentifier::from((major, patch))
+            }
+            _ => GenomeBuildIdentifier {
+                major_assembly: s.to_string(),
+                patch: None,
+            },
         })
     }
 }
 
+/// Write the major assembly and the patch, if any, separated by a dot, e.g. `GRCh38.p13`.
+impl core::fmt::Display for GenomeBuildIdentifier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.major_assembly)?;
+        if let Some(patch) = &self.patch {
+            write!(f, ".{patch}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Create [`GenomeBuildIdentifier`] from a tuple.
 ///
 /// The tuple must contain two items:
@@ -147,38 +970,1485 @@ impl GenomeBuildIdentifier {
     pub fn patch(&self) -> Option<&str> {
         self.patch.as_deref()
     }
+
+    fn heap_size(&self) -> usize {
+        self.major_assembly.capacity() + self.patch.as_ref().map_or(0, String::capacity)
+    }
+}
+
+/// The strategy for resolving contig clashes when merging genome builds.
+///
+/// Two contigs clash if they share the main name or any of the alternative names.
+///
+/// See [`GenomeBuild::merge`] for more info.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConflictPolicy {
+    /// Fail on the first clash.
+    Error,
+    /// Keep the contig of the build we are merging into.
+    PreferSelf,
+    /// Keep the contig of the build that is being merged.
+    PreferOther,
 }
 
 /// Genome build includes the contigs and genome build metadata.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// The contigs are shared between the clones of the build, hence cloning is cheap.
+///
+/// The contigs are indexed by their names and alternative names for fast lookup.
+/// See [`IndexKind`] for the available indexes.
+///
+/// The build is [`Send`] and [`Sync`] if `C` is, hence it can be shared between threads,
+/// e.g. as a [`SharedGenomeBuild`].
+#[derive(Debug, Clone)]
 pub struct GenomeBuild<C> {
     id: GenomeBuildIdentifier,
-    contigs: Vec<Contig<C>>,
+    contigs: Arc<[Contig<C>]>,
+    index: Arc<NameIndex>,
+    metadata: Arc<BuildMetadata>,
+    ordering: ContigOrdering,
+    /// The fingerprint of the identifier and the contigs, see [`GenomeBuild::fingerprint`].
+    fingerprint: FingerprintCell,
 }
 
-impl<C> GenomeBuild<C> {
-    pub fn new<I>(id: GenomeBuildIdentifier, contigs: I) -> Self
-    where
-        I: IntoIterator<Item = Contig<C>>,
-    {
-        let mut contigs: Vec<_> = contigs.into_iter().collect();
-        contigs.sort_by(|l, r| l.name().cmp(r.name()));
-        GenomeBuild { id, contigs }
-    }
+/// The fingerprint of a build, computed on first use.
+///
+/// The halves are stored separately, hence the cell needs no 64-bit atomics.
+#[derive(Debug, Default)]
+struct FingerprintCell {
+    ready: AtomicBool,
+    high: AtomicU32,
+    low: AtomicU32,
+}
 
-    /// Get the genome build identifiers.
-    pub fn id(&self) -> &GenomeBuildIdentifier {
-        &self.id
+impl FingerprintCell {
+    fn get_or_init(&self, init: impl FnOnce() -> u64) -> u64 {
+        if self.ready.load(AtomicOrdering::Acquire) {
+            let high = u64::from(self.high.load(AtomicOrdering::Relaxed));
+            return high << 32 | u64::from(self.low.load(AtomicOrdering::Relaxed));
+        }
+        // The racing threads compute and store the same fingerprint.
+        let fingerprint = init();
+        self.high
+            .store((fingerprint >> 32) as u32, AtomicOrdering::Relaxed);
+        self.low.store(fingerprint as u32, AtomicOrdering::Relaxed);
+        self.ready.store(true, AtomicOrdering::Release);
+        fingerprint
     }
+}
 
-    /// Get an iterator with all contigs.
-    pub fn contigs(&self) -> impl Iterator<Item = &Contig<C>> {
-        self.contigs.iter()
+impl Clone for FingerprintCell {
+    fn clone(&self) -> Self {
+        let ready = self.ready.load(AtomicOrdering::Acquire);
+        FingerprintCell {
+            ready: AtomicBool::new(ready),
+            high: AtomicU32::new(self.high.load(AtomicOrdering::Relaxed)),
+            low: AtomicU32::new(self.low.load(AtomicOrdering::Relaxed)),
+        }
     }
+}
 
+/// A lightweight key of a contig of a genome build, the build fingerprint and the contig index.
+///
+/// Unlike the contig names, the key is [`Copy`] and does not borrow the build,
+/// hence it is cheap to use as a key of the per-contig accumulators in a hash map.
+/// See [`GenomeBuild::contig_key`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ContigKey {
+    build: u64,
+    index: u32,
+}
+
+impl ContigKey {
+    pub(crate) fn new(build: u64, index: u32) -> Self {
+        ContigKey { build, index }
+    }
+
+    /// Get the fingerprint of the build of the contig, see [`GenomeBuild::fingerprint`].
+    pub fn build(&self) -> u64 {
+        self.build
+    }
+
+    /// Get the index of the contig in the order of [`GenomeBuild::contigs`].
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+}
+
+/// Compute the fingerprint of the build `id` and of all fields of the `contigs`.
+fn fingerprint<C: Hash>(id: &GenomeBuildIdentifier, contigs: &[Contig<C>]) -> u64 {
+    let mut fingerprint =
+        phf::hash(id.major_assembly(), 0) ^ phf::hash(id.patch().unwrap_or(""), 1);
+    for contig in contigs {
+        fingerprint = fingerprint.rotate_left(5) ^ phf::hash(contig.name(), 2);
+        for alt_name in contig.alt_names() {
+            fingerprint = fingerprint.rotate_left(3) ^ phf::hash(alt_name, 4);
+        }
+        if let Some(md5) = contig.md5() {
+            fingerprint ^= phf::hash(md5, 3);
+        }
+        if let Some(role) = contig.role() {
+            fingerprint ^= phf::hash(role.as_str(), 5);
+        }
+        let report_names = [
+            contig.genbank_accession(),
+            contig.refseq_accession(),
+            contig.ucsc_name(),
+        ];
+        for (seed, name) in (6..).zip(report_names) {
+            if let Some(name) = name {
+                fingerprint ^= phf::hash(name, seed);
+            }
+        }
+        let mut hasher = StableHasher::default();
+        contig.length().hash(&mut hasher);
+        contig.source_index().hash(&mut hasher);
+        contig.introduced_in().hash(&mut hasher);
+        fingerprint ^= hasher.finish().rotate_left(7);
+    }
+    fingerprint
+}
+
+/// An FNV-1a hasher of the contig lengths and indices, independent of the platform and of the process.
+///
+/// The integers are hashed as little-endian bytes, and `usize` as `u64`.
+struct StableHasher(u64);
+
+impl StableHasher {
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
+/// A handle of a genome build shared between threads or owners.
+///
+/// Cloning the handle copies neither the contigs nor the identifier and the name index.
+pub type SharedGenomeBuild<C> = Arc<GenomeBuild<C>>;
+
+// The build must stay `Send` and `Sync` to be shared between the worker threads.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<GenomeBuild<u32>>();
+    assert_send_sync::<SharedGenomeBuild<u64>>();
+};
+
+/// Genome builds are equal if they have equal identifiers and contigs,
+/// regardless of the [`IndexKind`], the [`BuildMetadata`], and the [`ContigOrdering`].
+impl<C> PartialEq for GenomeBuild<C>
+where
+    C: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.contigs == other.contigs
+    }
+}
+
+impl<C> Eq for GenomeBuild<C> where C: Eq {}
+
+impl<C> GenomeBuild<C> {
+    /// Create a genome build with the contigs sorted by name.
+    ///
+    /// Use [`GenomeBuild::new_ordered`] to choose a different [`ContigOrdering`],
+    /// e.g. [`ContigOrdering::Natural`], or [`GenomeBuildBuilder`] to choose an [`IndexKind`].
+    pub fn new<I>(id: GenomeBuildIdentifier, contigs: I) -> Self
+    where
+        I: IntoIterator<Item = Contig<C>>,
+    {
+        GenomeBuild::assemble(
+            id,
+            contigs,
+            ContigOrdering::Lexicographic,
+            IndexKind::default(),
+        )
+    }
+
+    /// Create a genome build with the contigs in the `ordering`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use dabuild::{Contig, ContigOrdering, GenomeBuild, GenomeBuildIdentifier};
+    ///
+    /// let contigs = ["chrUn_KI270302v1", "chrM", "chr10", "chrX", "chr2", "chr1"]
+    ///     .map(|name| Contig::new(name, &[] as &[&str], 1_000u32).unwrap());
+    /// let build = GenomeBuild::new_ordered(
+    ///     GenomeBuildIdentifier::from_str("hg38").expect("Infallible"),
+    ///     contigs,
+    ///     ContigOrdering::Natural,
+    /// );
+    ///
+    /// let names: Vec<_> = build.contigs().map(|c| c.name()).collect();
+    /// assert_eq!(names, ["chr1", "chr2", "chr10", "chrX", "chrM", "chrUn_KI270302v1"]);
+    /// ```
+    pub fn new_ordered<I>(id: GenomeBuildIdentifier, contigs: I, ordering: ContigOrdering) -> Self
+    where
+        I: IntoIterator<Item = Contig<C>>,
+    {
+        GenomeBuild::assemble(id, contigs, ordering, IndexKind::default())
+    }
+
+    pub(crate) fn assemble<I>(
+        id: GenomeBuildIdentifier,
+        contigs: I,
+        ordering: ContigOrdering,
+        index: IndexKind,
+    ) -> Self
+    where
+        I: IntoIterator<Item = Contig<C>>,
+    {
+        let mut contigs: Vec<_> = contigs.into_iter().collect();
+        ordering.sort(&mut contigs);
+        let index = NameIndex::new(index, &contigs);
+        GenomeBuild {
+            id,
+            contigs: contigs.into(),
+            index: Arc::new(index),
+            metadata: Arc::default(),
+            ordering,
+            fingerprint: FingerprintCell::default(),
+        }
+    }
+
+    /// Create a bundled build, sorted by name, with the perfect hash `index` of its names.
+    ///
+    /// The index must be generated for the contigs sorted by name.
+    pub(crate) fn bundled(
+        id: GenomeBuildIdentifier,
+        mut contigs: Vec<Contig<C>>,
+        index: &'static PerfectIndex,
+    ) -> Self {
+        let ordering = ContigOrdering::Lexicographic;
+        ordering.sort(&mut contigs);
+        GenomeBuild {
+            id,
+            contigs: contigs.into(),
+            index: Arc::new(NameIndex::Perfect(index)),
+            metadata: Arc::default(),
+            ordering,
+            fingerprint: FingerprintCell::default(),
+        }
+    }
+
+    /// Create a build with the `contigs` in the order of this build.
+    fn derive<I>(&self, contigs: I) -> Self
+    where
+        I: IntoIterator<Item = Contig<C>>,
+    {
+        let mut build = GenomeBuild::assemble(
+            self.id.clone(),
+            contigs,
+            ContigOrdering::Insertion,
+            self.index_kind(),
+        );
+        build.metadata = Arc::clone(&self.metadata);
+        build.ordering = self.ordering;
+        build
+    }
+
+    /// Get a copy of the build with the contigs sorted in the `ordering`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{ContigOrdering, GenomeBuild};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let karyotype = build.karyotype().expect("Human build");
+    /// let sorted = build.sorted(ContigOrdering::Karyotype(karyotype));
+    ///
+    /// let names: Vec<_> = sorted.contigs().take(3).map(|c| c.name()).collect();
+    /// assert_eq!(names, ["1", "2", "3"]);
+    /// ```
+    pub fn sorted(&self, ordering: ContigOrdering) -> GenomeBuild<C>
+    where
+        C: Clone,
+    {
+        let mut contigs: Vec<_> = self.contigs().cloned().collect();
+        ordering.sort(&mut contigs);
+        let mut build = self.derive(contigs);
+        build.ordering = ordering;
+        build
+    }
+
+    /// Get the ordering of the contigs of the build, i.e. the order of [`GenomeBuild::contigs`].
+    ///
+    /// The ordering is set when the build is created (see [`GenomeBuildBuilder::ordering`])
+    /// or sorted (see [`GenomeBuild::sorted`]), and it is kept by the subsets of the build.
+    /// The merged builds and the builds with contigs in the input order,
+    /// such as the builds loaded from a FASTA index, have [`ContigOrdering::Insertion`].
+    pub fn ordering(&self) -> ContigOrdering {
+        self.ordering
+    }
+
+    /// Get the kind of the index of the contig names.
+    pub(crate) fn index_kind(&self) -> IndexKind {
+        self.index.kind()
+    }
+
+    /// Get the karyotype preset of the build organism, chosen by the major assembly.
+    ///
+    /// See [`Karyotype::for_assembly`].
+    pub fn karyotype(&self) -> Option<Karyotype> {
+        Karyotype::for_assembly(self.id.major_assembly())
+    }
+
+    /// Get a builder for assembling a genome build programmatically.
+    pub fn builder() -> GenomeBuildBuilder<C> {
+        GenomeBuildBuilder::default()
+    }
+
+    /// Get the genome build identifiers.
+    pub fn id(&self) -> &GenomeBuildIdentifier {
+        &self.id
+    }
+
+    /// Replace the genome build identifier, keeping the contigs.
+    ///
+    /// For instance, to label a build loaded from a FASTA index:
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use dabuild::{GenomeBuild, GenomeBuildIdentifier};
+    /// use dabuild::formats::parse_fai;
+    ///
+    /// let fai = "chr1\t248956422\t112\t70\t71\n";
+    /// let build: GenomeBuild<u32> = parse_fai(
+    ///     GenomeBuildIdentifier::from_str("unknown").expect("Infallible"),
+    ///     fai.as_bytes(),
+    /// ).expect("Valid FASTA index");
+    ///
+    /// let build = build.with_id(GenomeBuildIdentifier::from(("GRCh38", "p13")));
+    /// assert_eq!(build.id().major_assembly(), "GRCh38");
+    /// ```
+    pub fn with_id(mut self, id: GenomeBuildIdentifier) -> Self {
+        self.set_id(id);
+        self
+    }
+
+    /// Replace the genome build identifier in place, keeping the contigs.
+    ///
+    /// See [`GenomeBuild::with_id`].
+    pub fn set_id(&mut self, id: GenomeBuildIdentifier) {
+        self.fingerprint = FingerprintCell::default();
+        self.id = id;
+    }
+
+    /// Get the assembly-level metadata, such as the assembly level.
+    pub fn metadata(&self) -> &BuildMetadata {
+        &self.metadata
+    }
+
+    /// Replace the build metadata.
+    pub fn with_metadata(mut self, metadata: BuildMetadata) -> Self {
+        self.metadata = Arc::new(metadata);
+        self
+    }
+
+    /// Get an iterator with all contigs in the build order, see [`GenomeBuild::ordering`].
+    ///
+    /// Use [`GenomeBuild::contigs_in`] to iterate in an explicit order.
+    pub fn contigs(&self) -> impl Iterator<Item = &Contig<C>> {
+        self.contigs.iter()
+    }
+
+    /// Get an iterator with the main names of the contigs in the build order.
+    ///
+    /// The order is the canonical order of the build, used by [`GenomeBuild::contigs`],
+    /// the writers, and the digests. The order of the bundled builds
+    /// (see [`crate::builds`]) is stable across the crate versions.
+    pub fn order(&self) -> impl Iterator<Item = &str> {
+        self.contigs.iter().map(Contig::name)
+    }
+
+    /// Get an iterator with all contigs in the `order`.
+    ///
+    /// Unlike [`GenomeBuild::contigs`], the order is explicit at the call site,
+    /// hence the results do not depend on how the build was created.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{GenomeBuild, IterationOrder, Karyotype};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    ///
+    /// let names: Vec<_> = build.contigs_in(IterationOrder::Build).take(3).map(|c| c.name()).collect();
+    /// assert_eq!(names, ["1", "10", "11"]);
+    ///
+    /// let order = IterationOrder::Karyotype(Karyotype::Human);
+    /// let names: Vec<_> = build.contigs_in(order).take(3).map(|c| c.name()).collect();
+    /// assert_eq!(names, ["1", "2", "3"]);
+    ///
+    /// let names: Vec<_> = build.contigs_in(IterationOrder::Length).take(3).map(|c| c.name()).collect();
+    /// assert_eq!(names, ["1", "2", "3"]);
+    /// ```
+    pub fn contigs_in(&self, order: IterationOrder) -> impl Iterator<Item = &Contig<C>>
+    where
+        C: PartialOrd,
+    {
+        let mut contigs: Vec<_> = self.contigs.iter().collect();
+        match order {
+            IterationOrder::Build => {}
+            IterationOrder::Karyotype(karyotype) => contigs
+                .sort_by_cached_key(|contig| karyotype.position(contig).unwrap_or(usize::MAX)),
+            IterationOrder::Length => contigs.sort_by(|l, r| {
+                r.length()
+                    .partial_cmp(l.length())
+                    .unwrap_or(Ordering::Equal)
+            }),
+        }
+        contigs.into_iter()
+    }
+
+    /// Get an iterator with the contigs of the `category`, see [`Contig::category`].
+    pub fn contigs_of(&self, category: ContigCategory) -> impl Iterator<Item = &Contig<C>> {
+        self.contigs()
+            .filter(move |contig| contig.category() == Some(category))
+    }
+
+    /// Get an iterator with the autosomes (e.g. `1`, `2`, ...).
+    pub fn autosomes(&self) -> impl Iterator<Item = &Contig<C>> {
+        self.contigs_of(ContigCategory::Autosome)
+    }
+
+    /// Get an iterator with the assembled molecules, the primary chromosomes
+    /// and the organelle genomes (e.g. `1`, `X`, and `MT`),
+    /// excluding the unlocalized and the unplaced scaffolds, the alternate loci, and the patches.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    ///
+    /// assert_eq!(build.chromosomes().count(), 25);
+    /// assert!(build.chromosomes().all(|c| !c.name().contains('_')));
+    /// ```
+    pub fn chromosomes(&self) -> impl Iterator<Item = &Contig<C>> {
+        self.contigs()
+            .filter(|contig| contig.role() == Some(SequenceRole::AssembledMolecule))
+    }
+
+    /// Get an iterator with the sex chromosomes (e.g. `X` and `Y`).
+    pub fn sex_chromosomes(&self) -> impl Iterator<Item = &Contig<C>> {
+        self.contigs_of(ContigCategory::SexChromosome)
+    }
+
+    /// Get an iterator with the mitochondrial genome (e.g. `MT`).
+    pub fn mitochondrial(&self) -> impl Iterator<Item = &Contig<C>> {
+        self.contigs_of(ContigCategory::Mitochondrial)
+    }
+
+    /// Get an iterator with the alternate loci.
+    pub fn alt_scaffolds(&self) -> impl Iterator<Item = &Contig<C>> {
+        self.contigs_of(ContigCategory::AltScaffold)
+    }
+
+    /// Get an iterator with the unplaced scaffolds.
+    pub fn unplaced(&self) -> impl Iterator<Item = &Contig<C>> {
+        self.contigs_of(ContigCategory::Unplaced)
+    }
+
+    /// Get an iterator with the chromosomes expected to carry the reads of a sample
+    /// of the `sex`, e.g. to check the coverage or to set up ploidy-aware variant calling.
+    ///
+    /// The chromosomes are the autosomes, the mitochondrial genome, and the sex chromosomes
+    /// carried by the sex, hence `Y` is excluded for [`Sex::Female`].
+    /// The scaffolds, the alternate loci, and the patches are excluded.
+    /// The chromosomes of a contig with an unknown role are recognized by the name
+    /// if the build has a [`Karyotype`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{GenomeBuild, Sex};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    ///
+    /// assert_eq!(build.expected_contigs(Sex::Male).count(), 25);
+    /// let female: Vec<_> = build.expected_contigs(Sex::Female).map(|c| c.name()).collect();
+    /// assert_eq!(female.len(), 24);
+    /// assert!(!female.contains(&"Y"));
+    /// ```
+    pub fn expected_contigs(&self, sex: Sex) -> impl Iterator<Item = &Contig<C>> {
+        let karyotype = self.karyotype();
+        self.contigs().filter(move |contig| {
+            let is_chromosome = match contig.category() {
+                Some(category) => matches!(
+                    category,
+                    ContigCategory::Autosome
+                        | ContigCategory::SexChromosome
+                        | ContigCategory::Mitochondrial
+                ),
+                None => karyotype.is_some_and(|karyotype| karyotype.position(contig).is_some()),
+            };
+            is_chromosome && contig.names().all(|name| sex.carries(name))
+        })
+    }
+
+    /// Count the contigs by the sequence role.
+    ///
+    /// The contigs with an unknown role are counted under `None`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{GenomeBuild, SequenceRole};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let counts = build.counts_by_role();
+    ///
+    /// assert_eq!(counts[&Some(SequenceRole::AssembledMolecule)], 25);
+    /// assert_eq!(counts[&Some(SequenceRole::UnplacedScaffold)], 127);
+    /// ```
+    pub fn counts_by_role(&self) -> BTreeMap<Option<SequenceRole>, usize> {
+        let mut counts = BTreeMap::new();
+        for contig in self.contigs() {
+            *counts.entry(contig.role()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Count the contigs by the molecule category, e.g. the autosomes or the sex chromosomes,
+    /// see [`Contig::category`].
+    ///
+    /// The contigs with an unknown role are counted under `None`.
+    pub fn counts_by_molecule(&self) -> BTreeMap<Option<ContigCategory>, usize> {
+        let mut counts = BTreeMap::new();
+        for contig in self.contigs() {
+            *counts.entry(contig.category()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Get an iterator over the contigs with the `length`, in the order of the build.
+    ///
+    /// The length is often the only reliable key of anonymous sequences,
+    /// e.g. `>seq1` of a FASTA file. See [`GenomeBuild::contig_by_unique_length`].
+    pub fn contigs_with_length<'a>(&'a self, length: &'a C) -> impl Iterator<Item = &'a Contig<C>>
+    where
+        C: PartialEq,
+    {
+        self.contigs()
+            .filter(move |contig| contig.length() == length)
+    }
+
+    /// Get the contig with the `length`,
+    /// or `None` if no contig or more than one contig has the length.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    ///
+    /// assert_eq!(build.contig_by_unique_length(&16_569).map(|c| c.name()), Some("MT"));
+    /// assert!(build.contig_by_unique_length(&1).is_none());
+    /// ```
+    pub fn contig_by_unique_length(&self, length: &C) -> Option<&Contig<C>>
+    where
+        C: PartialEq,
+    {
+        let mut contigs = self.contigs().filter(|contig| contig.length() == length);
+        match (contigs.next(), contigs.next()) {
+            (Some(contig), None) => Some(contig),
+            _ => None,
+        }
+    }
+
+    /// Get a contig by its main name or by any of its alternative names.
+    ///
+    /// If the name is shared by several contigs, the first contig is returned.
     pub fn contig_by_name(&self, name: &str) -> Option<&Contig<C>> {
+        self.contig_index_by_name(name).map(|i| &self.contigs[i])
+    }
+
+    /// Get the index of a contig, known by its main name or by any of its alternative names,
+    /// in the order of [`GenomeBuild::contigs`].
+    ///
+    /// Unlike [`GenomeBuild::contig_by_name`], the index does not borrow the build,
+    /// hence it can be stored, e.g. as a compact contig key of a variant record,
+    /// and resolved later using [`GenomeBuild::contig_at`].
+    ///
+    /// If the name is shared by several contigs, the index of the first contig is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    ///
+    /// let idx = build.contig_index_by_name("chrY").expect("Known contig");
+    /// assert_eq!(build.contig_index_by_name("NC_000024.10"), Some(idx));
+    /// assert_eq!(build.contig_at(idx).map(|c| c.name()), Some("Y"));
+    /// assert_eq!(build.contig_index_by_name("chrZ"), None);
+    /// ```
+    pub fn contig_index_by_name(&self, name: &str) -> Option<usize> {
+        self.index.get(&self.contigs, name)
+    }
+
+    /// Get the contig at the `index` in the order of [`GenomeBuild::contigs`],
+    /// or `None` if the index is out of bounds.
+    pub fn contig_at(&self, index: usize) -> Option<&Contig<C>> {
+        self.contigs.get(index)
+    }
+
+    /// Get the approximate number of bytes used by the build,
+    /// including the contig names, the alternative names, the name index, and the metadata.
+    ///
+    /// The estimate counts the allocations but not the allocator overhead.
+    /// The data shared with other builds or contigs, e.g. after [`GenomeBuild::subset`]
+    /// or cloning, is counted in full, and the heap data of `C`, if any, is not counted.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let subset = build.subset(["chr1", "chr2"]).unwrap();
+    ///
+    /// assert!(subset.approx_memory_usage() < build.approx_memory_usage());
+    /// ```
+    pub fn approx_memory_usage(&self) -> usize {
+        core::mem::size_of::<Self>()
+            + self.id.heap_size()
+            + arc_size(self.contigs.len() * core::mem::size_of::<Contig<C>>())
+            + self.contigs.iter().map(Contig::heap_size).sum::<usize>()
+            + arc_size(core::mem::size_of::<NameIndex>() + self.index.heap_size())
+            + arc_size(core::mem::size_of::<BuildMetadata>() + self.metadata.heap_size())
+    }
+
+    /// Add the `alias` to the alternative names of the contig known under the `name`.
+    ///
+    /// The name index is updated in place instead of being rebuilt,
+    /// hence aliases can be registered on the fly.
+    /// The contigs and the index shared with the clones of the build are copied once,
+    /// and the index of a bundled build is replaced by the default index once.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let mut build: GenomeBuild<u32> = get_grch38_p13();
+    /// build.add_alias("chrM", "mito").expect("Unique alias");
+    ///
+    /// assert_eq!(build.contig_by_name("mito").map(|c| c.name()), Some("MT"));
+    /// assert!(build.add_alias("chr1", "mito").is_err());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// If no contig is known under the `name` or if the `alias` is already a name of another contig.
+    /// Adding a name the contig already has is a no-op.
+    pub fn add_alias(&mut self, name: &str, alias: &str) -> Result<(), String>
+    where
+        C: Clone,
+    {
+        let i = self
+            .contig_index_by_name(name)
+            .ok_or_else(|| format!("Cannot add alias {alias:?} to unknown contig {name:?}"))?;
+        if let Some(j) = self.contig_index_by_name(alias) {
+            if i == j {
+                return Ok(());
+            }
+            let other = &self.contigs[j];
+            return Err(format!(
+                "Alias {alias:?} is already a name of contig {:?}",
+                other.name()
+            ));
+        }
+
+        let contig = &mut Arc::make_mut(&mut self.contigs)[i];
+        contig.push_alt_name(alias);
+        let j = contig.alt_names().count();
+        Arc::make_mut(&mut self.index).insert(&self.contigs, (i as u32, j as u32));
+        Ok(())
+    }
+
+    /// Get a new build with the contigs selected by `names`.
+    ///
+    /// The names are resolved by [`GenomeBuild::contig_by_name`],
+    /// hence the contig name or any of its alternative names can be used.
+    /// A contig is included only once, even if it is selected by several names,
+    /// and the contigs keep the order of this build.
+    ///
+    /// ## Errors
+    ///
+    /// If any of the `names` does not correspond to a contig of the build.
+    pub fn subset<I, S>(&self, names: I) -> Result<GenomeBuild<C>, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        C: Clone,
+    {
+        let mut selected = vec![false; self.contigs.len()];
+        for name in names {
+            let name = name.as_ref();
+            match self.contig_index_by_name(name) {
+                Some(i) => selected[i] = true,
+                None => return Err(format!("No contig found for name {name:?}")),
+            }
+        }
+
+        Ok(self.derive(
+            self.contigs
+                .iter()
+                .zip(selected)
+                .filter(|&(_, selected)| selected)
+                .map(|(contig, _)| contig.clone()),
+        ))
+    }
+
+    /// Get a new build with the contigs of this and the `other` build.
+    ///
+    /// The clashes of contig names are resolved using the `policy`.
+    /// The new build uses the identifier of this build,
+    /// and it includes the contigs of this build followed by the contigs of the `other` build.
+    /// Unless contigs of this build are dropped by the `policy`,
+    /// the name index of this build is extended by the new contigs instead of being rebuilt.
+    ///
+    /// For instance, to add a spike-in contig to *GRCh38.p13*:
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use dabuild::{ConflictPolicy, Contig, GenomeBuild, GenomeBuildIdentifier};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let spike_ins = GenomeBuild::new(
+    ///     GenomeBuildIdentifier::from_str("spike-ins").expect("Infallible"),
+    ///     [Contig::new("phiX174", &["NC_001422.1"], 5_386).unwrap()],
+    /// );
+    ///
+    /// let merged = build.merge(&spike_ins, ConflictPolicy::Error).expect("No clashes");
+    ///
+    /// assert_eq!(merged.contigs().count(), 641);
+    /// assert!(merged.contig_by_name("phiX174").is_some());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// If the `policy` is [`ConflictPolicy::Error`] and the builds include clashing contigs.
+    pub fn merge(
+        &self,
+        other: &GenomeBuild<C>,
+        policy: ConflictPolicy,
+    ) -> Result<GenomeBuild<C>, String>
+    where
+        C: Clone,
+    {
+        let mut ours: Vec<&Contig<C>> = self.contigs.iter().collect();
+        let mut theirs: Vec<&Contig<C>> = vec![];
+
+        for contig in other.contigs() {
+            let clashing: Vec<_> = contig
+                .names()
+                .filter_map(|name| self.contig_by_name(name).map(|c| (name, c)))
+                .collect();
+
+            if clashing.is_empty() {
+                theirs.push(contig);
+                continue;
+            }
+
+            match policy {
+                ConflictPolicy::Error => {
+                    let (name, ours) = clashing[0];
+                    return Err(format!(
+                        "Contig {:?} clashes with contig {:?} on name {name:?}",
+                        contig.name(),
+                        ours.name()
+                    ));
+                }
+                ConflictPolicy::PreferSelf => {}
+                ConflictPolicy::PreferOther => {
+                    ours.retain(|&c| !clashing.iter().any(|&(_, x)| core::ptr::eq(c, x)));
+                    theirs.push(contig);
+                }
+            }
+        }
+
+        if ours.len() < self.contigs.len() {
+            let mut merged = self.derive(ours.into_iter().chain(theirs).cloned());
+            merged.ordering = ContigOrdering::Insertion;
+            return Ok(merged);
+        }
+
+        // The contigs are only appended, hence the index is extended instead of rebuilt.
+        let contigs: Vec<_> = self.contigs.iter().chain(theirs).cloned().collect();
+        let mut index = NameIndex::clone(&self.index);
+        index.extend(&contigs, self.contigs.len());
+        Ok(GenomeBuild {
+            id: self.id.clone(),
+            contigs: contigs.into(),
+            index: Arc::new(index),
+            metadata: Arc::clone(&self.metadata),
+            ordering: ContigOrdering::Insertion,
+            fingerprint: FingerprintCell::default(),
+        })
+    }
+
+    /// Get a new build with the contigs for which the `predicate` returns `true`.
+    ///
+    /// For instance, to keep the contigs that are at least 40Mb long:
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    ///
+    /// let long = build.retain(|contig| *contig.length() >= 40_000_000);
+    /// assert_eq!(long.contigs().count(), 24);
+    /// ```
+    pub fn retain<F>(&self, mut predicate: F) -> GenomeBuild<C>
+    where
+        F: FnMut(&Contig<C>) -> bool,
+        C: Clone,
+    {
+        self.derive(self.contigs.iter().filter(|&c| predicate(c)).cloned())
+    }
+
+    /// Get a new build with the contigs that are at least `min_length` bases long,
+    /// e.g. to drop the scaffolds under 1 kb before an analysis.
+    ///
+    /// The contigs can be dropped already when parsing an assembly report,
+    /// see [`crate::builds::ParseOptions::min_length`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    ///
+    /// let long = build.filter_min_length(40_000_000);
+    /// assert_eq!(long.contigs().count(), 24);
+    /// ```
+    pub fn filter_min_length(&self, min_length: C) -> GenomeBuild<C>
+    where
+        C: Clone + PartialOrd,
+    {
+        self.retain(|contig| *contig.length() >= min_length)
+    }
+}
+
+impl<C: Hash> GenomeBuild<C> {
+    /// Get the fingerprint of the build identifier and of the contigs,
+    /// in the order of [`GenomeBuild::contigs`].
+    ///
+    /// The fingerprint covers all fields of the contigs, such as the names, the lengths,
+    /// the roles, the MD5 digests, the source indices, and the report names.
+    ///
+    /// The fingerprint is computed on first use, and it does not depend on the platform
+    /// or on the process, hence it tells apart the [`ContigKey`]s of the builds.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+            .get_or_init(|| fingerprint(&self.id, &self.contigs))
+    }
+
+    /// Get the key of a contig, known by its main name or by any of its alternative names.
+    ///
+    /// The key is [`Copy`] and does not borrow the build, hence it can key the per-contig
+    /// accumulators in a hash map, and it is resolved with [`GenomeBuild::contig_by_key`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::{get_grch37_p13, get_grch38_p13};
+    ///
+    /// let grch38: GenomeBuild<u32> = get_grch38_p13();
+    /// let grch37: GenomeBuild<u32> = get_grch37_p13();
+    ///
+    /// let mut counts = HashMap::new();
+    /// for name in ["chr1", "1", "NC_000001.11", "chrX"] {
+    ///     let key = grch38.contig_key(name).expect("Known contig");
+    ///     *counts.entry(key).or_insert(0) += 1;
+    /// }
+    ///
+    /// let chr1 = grch38.contig_key("chr1").expect("Known contig");
+    /// assert_eq!(counts[&chr1], 3);
+    /// assert_eq!(grch38.contig_by_key(chr1).map(|c| c.name()), Some("1"));
+    ///
+    /// // The keys of another build are not resolved.
+    /// assert_ne!(grch37.contig_key("chr1"), Some(chr1));
+    /// assert!(grch37.contig_by_key(chr1).is_none());
+    /// ```
+    pub fn contig_key(&self, name: &str) -> Option<ContigKey> {
+        self.contig_index_by_name(name)
+            .map(|i| ContigKey::new(self.fingerprint(), i as u32))
+    }
+
+    /// Iterate over the keys and the contigs in the order of [`GenomeBuild::contigs`].
+    pub fn contig_keys(&self) -> impl Iterator<Item = (ContigKey, &Contig<C>)> {
+        let fingerprint = self.fingerprint();
+        self.contigs
+            .iter()
+            .enumerate()
+            .map(move |(i, contig)| (ContigKey::new(fingerprint, i as u32), contig))
+    }
+
+    /// Get the contig of the `key`, or `None` if the key belongs to another build.
+    pub fn contig_by_key(&self, key: ContigKey) -> Option<&Contig<C>> {
+        if key.build == self.fingerprint() {
+            self.contigs.get(key.index as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl<C> GenomeBuild<C>
+where
+    C: Copy + Zero + PartialOrd + CheckedAdd,
+{
+    /// Tile the contigs with windows of `size` bases, starting each `step` bases.
+    ///
+    /// The windows are reported in the order of the contigs.
+    /// The last window of a contig is truncated at the contig end.
+    ///
+    /// For instance, to tile a 25bp contig with non-overlapping 10bp windows:
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use dabuild::{GenomeBuild, GenomeBuildIdentifier};
+    ///
+    /// let build = GenomeBuild::builder()
+    ///     .id(GenomeBuildIdentifier::from_str("toy").expect("Infallible"))
+    ///     .contig("1", 25u32)
+    ///     .build()
+    ///     .expect("Valid build");
+    ///
+    /// let windows: Vec<_> = build.windows(10, 10).map(|w| (*w.start(), *w.end())).collect();
+    /// assert_eq!(windows, vec![(0, 10), (10, 20), (20, 25)]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// If `size` or `step` is not positive.
+    pub fn windows(&self, size: C, step: C) -> impl Iterator<Item = GenomicRegion<C>> + '_ {
+        assert!(size > C::zero(), "Window size must be positive");
+        assert!(step > C::zero(), "Window step must be positive");
+        self.contigs
+            .iter()
+            .flat_map(move |contig| contig_windows(contig, size, step))
+    }
+
+    /// Tile the contigs with windows of `size` bases, starting each `step` bases,
+    /// and skip or trim the windows that overlap the `gaps`, such as the assembly gaps
+    /// and centromeres, e.g. to leave the all-N regions out of genome-wide statistics.
+    ///
+    /// The windows are tiled as in [`GenomeBuild::windows`]. With [`GapHandling::Trim`],
+    /// a window is replaced by its parts outside the gaps, in the order of the start coordinate.
+    /// The gaps can be loaded from a BED file with [`crate::formats::parse_exclusion_list`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use dabuild::{GapHandling, GenomeBuild, GenomeBuildIdentifier, GenomicRegion, RegionSet};
+    ///
+    /// let build = GenomeBuild::builder()
+    ///     .id(GenomeBuildIdentifier::from_str("toy").expect("Infallible"))
+    ///     .contig("1", 25u32)
+    ///     .build()
+    ///     .expect("Valid build");
+    /// let contig = build.contig_by_name("1").expect("Known contig");
+    /// let gaps: RegionSet<u32> = [GenomicRegion::new(contig, 5, 12).expect("Valid region")]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let windows: Vec<_> = build
+    ///     .windows_around_gaps(10, 10, &gaps, GapHandling::Skip)
+    ///     .map(|w| (*w.start(), *w.end()))
+    ///     .collect();
+    /// assert_eq!(windows, vec![(20, 25)]);
+    ///
+    /// let windows: Vec<_> = build
+    ///     .windows_around_gaps(10, 10, &gaps, GapHandling::Trim)
+    ///     .map(|w| (*w.start(), *w.end()))
+    ///     .collect();
+    /// assert_eq!(windows, vec![(0, 5), (12, 20), (20, 25)]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// If `size` or `step` is not positive.
+    pub fn windows_around_gaps<'a>(
+        &'a self,
+        size: C,
+        step: C,
+        gaps: &'a RegionSet<C>,
+        handling: GapHandling,
+    ) -> impl Iterator<Item = GenomicRegion<C>> + 'a {
+        self.windows(size, step)
+            .flat_map(move |window| match handling {
+                GapHandling::Skip if gaps.overlaps(&window) => vec![],
+                GapHandling::Skip => vec![window],
+                GapHandling::Trim => gaps.mask(&window),
+            })
+    }
+}
+
+/// The handling of the windows that overlap gaps, see [`GenomeBuild::windows_around_gaps`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GapHandling {
+    /// Leave out the windows that overlap any gap.
+    #[default]
+    Skip,
+    /// Keep the parts of the windows outside the gaps.
+    Trim,
+}
+
+#[cfg(feature = "rayon")]
+impl<C> GenomeBuild<C>
+where
+    C: Send + Sync,
+{
+    /// Get a parallel iterator with all contigs.
+    pub fn par_contigs(&self) -> impl rayon::iter::IndexedParallelIterator<Item = &Contig<C>> {
+        use rayon::prelude::*;
+
+        self.contigs.par_iter()
+    }
+
+    /// Tile the contigs with windows in parallel.
+    ///
+    /// See [`GenomeBuild::windows`] for more info.
+    ///
+    /// ## Panics
+    ///
+    /// If `size` or `step` is not positive.
+    pub fn par_windows(
+        &self,
+        size: C,
+        step: C,
+    ) -> impl rayon::iter::ParallelIterator<Item = GenomicRegion<C>> + '_
+    where
+        C: Copy + Zero + PartialOrd + CheckedAdd,
+    {
+        use rayon::prelude::*;
+
+        assert!(size > C::zero(), "Window size must be positive");
+        assert!(step > C::zero(), "Window step must be positive");
         self.contigs
+            .par_iter()
+            .flat_map_iter(move |contig| contig_windows(contig, size, step))
+    }
+}
+
+/// The order of contigs in a genome build.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContigOrdering {
+    /// Sort the contigs by their main name.
+    #[default]
+    Lexicographic,
+    /// Keep the order in which the contigs were provided.
+    Insertion,
+    /// Put the chromosomes of the karyotype first, in the conventional order,
+    /// and keep the order of the other contigs.
+    Karyotype(Karyotype),
+    /// Sort the contigs in the karyotypic order without a preset, e.g. for VCF or dict headers:
+    /// the assembled molecules first, the numbered chromosomes in the numeric order,
+    /// then the other chromosomes by name, and the mitochondrial genome last,
+    /// followed by the other contigs by [`SequenceRole`] and by name.
+    ///
+    /// The contigs with an unknown role are assembled molecules if named like a chromosome,
+    /// e.g. `chr2`, `X`, or `MT`, and they follow the contigs with a known role otherwise.
+    Natural,
+}
+
+/// The order of iterating over the contigs of a genome build, see [`GenomeBuild::contigs_in`].
+///
+/// The ties are kept in the build order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IterationOrder {
+    /// The order of the build, see [`GenomeBuild::ordering`].
+    #[default]
+    Build,
+    /// The chromosomes of the karyotype first, in the conventional order,
+    /// followed by the other contigs in the build order.
+    Karyotype(Karyotype),
+    /// The longest contigs first.
+    Length,
+}
+
+impl ContigOrdering {
+    fn sort<C>(&self, contigs: &mut [Contig<C>]) {
+        match self {
+            ContigOrdering::Lexicographic => contigs.sort_by(|l, r| l.name().cmp(r.name())),
+            ContigOrdering::Insertion => {}
+            ContigOrdering::Karyotype(karyotype) => contigs
+                .sort_by_cached_key(|contig| karyotype.position(contig).unwrap_or(usize::MAX)),
+            ContigOrdering::Natural => contigs.sort_by(|l, r| {
+                natural_key(l)
+                    .cmp(&natural_key(r))
+                    .then_with(|| l.name().cmp(r.name()))
+            }),
+        }
+    }
+}
+
+/// Get the key of the `contig` in the [`ContigOrdering::Natural`]:
+/// the rank of the role, and the chromosome number, or `u64::MAX` for the other names,
+/// and `true` for the mitochondrial genome.
+fn natural_key<C>(contig: &Contig<C>) -> (u8, bool, u64) {
+    let name = strip_chr(contig.name());
+    let is_mito = ["M", "MT"].iter().any(|m| name.eq_ignore_ascii_case(m));
+    let number = name.parse::<u64>().ok();
+    let rank = match contig.role() {
+        Some(role) => role as u8,
+        None if number.is_some() || is_mito || name.len() == 1 => {
+            SequenceRole::AssembledMolecule as u8
+        }
+        None => u8::MAX,
+    };
+    (rank, is_mito, number.unwrap_or(u64::MAX))
+}
+
+/* ***************************************************************************************************************** *
+ *                                               Genome Build Builder
+ * ***************************************************************************************************************** */
+
+/// Assemble a [`GenomeBuild`] programmatically, e.g. for test fixtures or synthetic genomes.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use dabuild::{ContigOrdering, GenomeBuild, GenomeBuildIdentifier};
+///
+/// let build: GenomeBuild<u32> = GenomeBuild::builder()
+///     .id(GenomeBuildIdentifier::from_str("toy").expect("Infallible"))
+///     .contig("plasmid", 5_000)
+///     .alias("plasmid", "pUC19")
+///     .contig("chromosome", 1_000_000)
+///     .ordering(ContigOrdering::Insertion)
+///     .build()
+///     .expect("Valid build");
+///
+/// let names: Vec<_> = build.contigs().map(|c| c.name()).collect();
+/// assert_eq!(names, vec!["plasmid", "chromosome"]);
+/// assert!(build.contig_by_name("pUC19").is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct GenomeBuildBuilder<C> {
+    id: Option<GenomeBuildIdentifier>,
+    contigs: Vec<(String, Vec<String>, C)>,
+    aliases: Vec<(String, String)>,
+    md5s: Vec<(String, String)>,
+    roles: Vec<(String, SequenceRole)>,
+    source_indices: Vec<(String, usize)>,
+    report_names: Vec<(String, [Option<String>; 3])>,
+    ordering: ContigOrdering,
+    index: IndexKind,
+}
+
+impl<C> Default for GenomeBuildBuilder<C> {
+    fn default() -> Self {
+        GenomeBuildBuilder {
+            id: None,
+            contigs: vec![],
+            aliases: vec![],
+            md5s: vec![],
+            roles: vec![],
+            source_indices: vec![],
+            report_names: vec![],
+            ordering: ContigOrdering::default(),
+            index: IndexKind::default(),
+        }
+    }
+}
+
+impl<C> GenomeBuildBuilder<C> {
+    /// Set the genome build identifier.
+    pub fn id(mut self, id: GenomeBuildIdentifier) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Add a contig with a `name` and `length`.
+    pub fn contig<T>(mut self, name: T, length: C) -> Self
+    where
+        T: ToString,
+    {
+        self.contigs.push((name.to_string(), vec![], length));
+        self
+    }
+
+    /// Add an `alias` to a contig that is known under the `name`.
+    ///
+    /// The `name` is resolved when building, hence the contig can be added later.
+    /// An alias that the contig already has is ignored.
+    pub fn alias<T, U>(mut self, name: T, alias: U) -> Self
+    where
+        T: ToString,
+        U: ToString,
+    {
+        self.aliases.push((name.to_string(), alias.to_string()));
+        self
+    }
+
+    /// Set the MD5 digest of the sequence of a contig that is known under the `name`.
+    ///
+    /// The `name` is resolved when building, hence the contig can be added later.
+    pub fn md5<T, U>(mut self, name: T, md5: U) -> Self
+    where
+        T: ToString,
+        U: ToString,
+    {
+        self.md5s.push((name.to_string(), md5.to_string()));
+        self
+    }
+
+    /// Set the sequence role of a contig that is known under the `name`.
+    ///
+    /// The `name` is resolved when building, hence the contig can be added later.
+    pub fn role<T>(mut self, name: T, role: SequenceRole) -> Self
+    where
+        T: ToString,
+    {
+        self.roles.push((name.to_string(), role));
+        self
+    }
+
+    /// Set the 0-based index of the line (or record) of the input file
+    /// a contig that is known under the `name` was parsed from.
+    ///
+    /// The `name` is resolved when building, hence the contig can be added later.
+    pub fn source_index<T>(mut self, name: T, index: usize) -> Self
+    where
+        T: ToString,
+    {
+        self.source_indices.push((name.to_string(), index));
+        self
+    }
+
+    /// Set the GenBank, RefSeq, and UCSC-style names of a contig that is known under the `name`,
+    /// see [`Contig::genbank_accession`].
+    ///
+    /// The `name` is resolved when building, hence the contig can be added later.
+    #[cfg(feature = "json")]
+    pub(crate) fn report_names<T>(mut self, name: T, columns: [Option<String>; 3]) -> Self
+    where
+        T: ToString,
+    {
+        self.report_names.push((name.to_string(), columns));
+        self
+    }
+
+    /// Set the order of the contigs in the build.
+    pub fn ordering(mut self, ordering: ContigOrdering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    /// Set the index for looking up the contigs by name.
+    pub fn index(mut self, index: IndexKind) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Validate the input and build the [`GenomeBuild`].
+    ///
+    /// ## Errors
+    ///
+    /// The validation fails if:
+    ///
+    /// * the identifier was not set
+    /// * no contigs were added
+    /// * an alias, an MD5 digest, a role, or a source index was added to an unknown contig
+    /// * an MD5 digest is not 32 hexadecimal digits
+    /// * a GenBank, RefSeq, or UCSC-style name is not a name of its contig
+    /// * a contig length is negative
+    /// * a contig name or an alias is used more than once
+    pub fn build(self) -> Result<GenomeBuild<C>, String>
+    where
+        C: Zero + PartialOrd,
+    {
+        let id = self.id.ok_or("Missing genome build identifier")?;
+        if self.contigs.is_empty() {
+            return Err(format!("No contigs in build {}", id.major_assembly()));
+        }
+
+        let mut contigs = self.contigs;
+        let mut positions: BTreeMap<String, usize> = BTreeMap::new();
+        for (i, (name, _, _)) in contigs.iter().enumerate() {
+            positions.entry(name.clone()).or_insert(i);
+        }
+        for (name, alias) in self.aliases {
+            match positions.get(&name) {
+                Some(&i) => {
+                    let (main, alt_names, _) = &mut contigs[i];
+                    if *main != alias && !alt_names.contains(&alias) {
+                        positions.entry(alias.clone()).or_insert(i);
+                        alt_names.push(alias);
+                    }
+                }
+                None => {
+                    return Err(format!(
+                        "Cannot add alias {alias:?} to unknown contig {name:?}"
+                    ))
+                }
+            }
+        }
+
+        let mut seen = BTreeSet::new();
+        let mut duplicates = vec![];
+        for name in contigs
             .iter()
-            .find(|&c| c.name().eq(name) || c.alt_names().any(|alt_name| alt_name.eq(name)))
+            .flat_map(|(name, alt_names, _)| core::iter::once(name).chain(alt_names))
+        {
+            if !seen.insert(name) && !duplicates.contains(&name) {
+                duplicates.push(name);
+            }
+        }
+        if !duplicates.is_empty() {
+            return Err(format!("Duplicate contig names: {duplicates:?}"));
+        }
+
+        let mut md5s = BTreeMap::new();
+        for (name, md5) in self.md5s {
+            match positions.get(&name) {
+                Some(&i) => md5s.insert(i, md5),
+                None => return Err(format!("Cannot add MD5 {md5:?} to unknown contig {name:?}")),
+            };
+        }
+
+        let mut roles = BTreeMap::new();
+        for (name, role) in self.roles {
+            match positions.get(&name) {
+                Some(&i) => roles.insert(i, role),
+                None => return Err(format!("Cannot add role {role} to unknown contig {name:?}")),
+            };
+        }
+
+        let mut source_indices = BTreeMap::new();
+        for (name, index) in self.source_indices {
+            match positions.get(&name) {
+                Some(&i) => source_indices.insert(i, index),
+                None => {
+                    return Err(format!(
+                        "Cannot add source index {index} to unknown contig {name:?}"
+                    ))
+                }
+            };
+        }
+
+        let mut report_names = BTreeMap::new();
+        for (name, columns) in self.report_names {
+            match positions.get(&name) {
+                Some(&i) => report_names.insert(i, columns),
+                None => {
+                    return Err(format!(
+                        "Cannot add report names {columns:?} to unknown contig {name:?}"
+                    ))
+                }
+            };
+        }
+
+        let mut built = Vec::with_capacity(contigs.len());
+        for (i, (name, alt_names, length)) in contigs.into_iter().enumerate() {
+            let mut contig = match Contig::new(&name, &alt_names, length) {
+                Some(contig) => match source_indices.get(&i) {
+                    Some(&index) => contig.with_source_index(index),
+                    None => contig,
+                },
+                None => return Err(format!("Negative length of contig {name:?}")),
+            };
+            if let Some(&role) = roles.get(&i) {
+                contig = contig.with_role(role);
+            }
+            if let Some(columns) = report_names.get(&i) {
+                contig = contig
+                    .with_report_names(columns.each_ref().map(Option::as_deref))
+                    .ok_or_else(|| {
+                        format!("Report names {columns:?} are not the names of contig {name:?}")
+                    })?;
+            }
+            built.push(match md5s.get(&i) {
+                Some(md5) => contig
+                    .with_md5(md5)
+                    .ok_or_else(|| format!("Invalid MD5 {md5:?} of contig {name:?}"))?,
+                None => contig,
+            });
+        }
+
+        Ok(GenomeBuild::assemble(id, built, self.ordering, self.index))
     }
 }
@@ -0,0 +1,241 @@
+//! # Contig name index
+//!
+//! The index maps the contig names and alternative names to the contig positions in a genome build.
+
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{phf::PerfectIndex, Contig};
+
+/// The data structure for looking up contigs by name.
+///
+/// The trade-offs are:
+///
+/// * [`IndexKind::Hash`] offers constant-time lookups, but it copies all names into a hash map,
+///   roughly doubling the memory needed to store the names.
+/// * [`IndexKind::Sorted`] needs 8 bytes per name and logarithmic-time lookups by binary search.
+///   It does not copy the names and it needs no hashing.
+///
+/// If a name is shared by several contigs, both indexes resolve the name
+/// to the first contig in the build order.
+///
+/// The hash index needs the `std` feature.
+/// The sorted index is the default if the `std` feature is disabled.
+///
+/// The bundled builds (see [`crate::builds`]) use a perfect hash index generated at compile time,
+/// with constant-time lookups and no hashing of the names at runtime.
+/// The builds derived from the bundled builds, e.g. their subsets, use the default index.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexKind {
+    /// Index the names in a hash map.
+    #[cfg(feature = "std")]
+    #[default]
+    Hash,
+    /// Index the names in a sorted slice.
+    #[cfg_attr(not(feature = "std"), default)]
+    Sorted,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum NameIndex {
+    #[cfg(feature = "std")]
+    Hash(HashMap<Box<str>, u32>),
+    /// The contig and name positions, sorted by the name and the contig position.
+    Sorted(Box<[(u32, u32)]>),
+    /// The perfect hash table of a bundled build.
+    Perfect(&'static PerfectIndex),
+}
+
+impl NameIndex {
+    pub(crate) fn new<C>(kind: IndexKind, contigs: &[Contig<C>]) -> Self {
+        match kind {
+            #[cfg(feature = "std")]
+            IndexKind::Hash => {
+                let mut index = HashMap::new();
+                for (i, contig) in contigs.iter().enumerate() {
+                    for name in contig.names() {
+                        index.entry(Box::from(name)).or_insert(position(i));
+                    }
+                }
+                NameIndex::Hash(index)
+            }
+            IndexKind::Sorted => {
+                let mut index: Vec<_> = contigs
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, contig)| {
+                        (0..contig.names().count()).map(move |j| (position(i), position(j)))
+                    })
+                    .collect();
+                index
+                    .sort_by(|&l, &r| name_at(contigs, l).cmp(name_at(contigs, r)).then(l.cmp(&r)));
+                NameIndex::Sorted(index.into())
+            }
+        }
+    }
+
+    pub(crate) fn kind(&self) -> IndexKind {
+        match self {
+            #[cfg(feature = "std")]
+            NameIndex::Hash(_) => IndexKind::Hash,
+            NameIndex::Sorted(_) => IndexKind::Sorted,
+            NameIndex::Perfect(_) => IndexKind::default(),
+        }
+    }
+
+    /// Index the name at the `entry` (the contig and name position) of the `contigs`,
+    /// e.g. after adding an alternative name to a contig.
+    ///
+    /// The positions of the indexed names must not change.
+    /// The perfect hash index cannot be updated, hence it is replaced by the default index.
+    pub(crate) fn insert<C>(&mut self, contigs: &[Contig<C>], entry: (u32, u32)) {
+        match self {
+            #[cfg(feature = "std")]
+            NameIndex::Hash(index) => {
+                index
+                    .entry(Box::from(name_at(contigs, entry)))
+                    .or_insert(entry.0);
+            }
+            NameIndex::Sorted(index) => {
+                let name = name_at(contigs, entry);
+                let idx = index
+                    .partition_point(|&other| (name_at(contigs, other), other) < (name, entry));
+                let mut entries = core::mem::take(index).into_vec();
+                entries.insert(idx, entry);
+                *index = entries.into();
+            }
+            NameIndex::Perfect(_) => *self = NameIndex::new(IndexKind::default(), contigs),
+        }
+    }
+
+    /// Index the names of the contigs from the position `start` onwards,
+    /// e.g. after appending the contigs of another build.
+    ///
+    /// The names of the preceding contigs keep their positions, and so their precedence.
+    /// The perfect hash index cannot be updated, hence it is replaced by the default index.
+    pub(crate) fn extend<C>(&mut self, contigs: &[Contig<C>], start: usize) {
+        match self {
+            #[cfg(feature = "std")]
+            NameIndex::Hash(index) => {
+                for (i, contig) in contigs.iter().enumerate().skip(start) {
+                    for name in contig.names() {
+                        index.entry(Box::from(name)).or_insert(position(i));
+                    }
+                }
+            }
+            NameIndex::Sorted(index) => {
+                let mut entries = core::mem::take(index).into_vec();
+                for (i, contig) in contigs.iter().enumerate().skip(start) {
+                    entries.extend((0..contig.names().count()).map(|j| (position(i), position(j))));
+                }
+                // The stable sort merges the new entries into the sorted run of the old ones.
+                entries
+                    .sort_by(|&l, &r| name_at(contigs, l).cmp(name_at(contigs, r)).then(l.cmp(&r)));
+                *index = entries.into();
+            }
+            NameIndex::Perfect(_) => *self = NameIndex::new(IndexKind::default(), contigs),
+        }
+    }
+
+    /// Get the number of heap bytes of the index.
+    ///
+    /// The hash map size is estimated from its capacity, ignoring the control bytes.
+    pub(crate) fn heap_size(&self) -> usize {
+        match self {
+            #[cfg(feature = "std")]
+            NameIndex::Hash(index) => {
+                index.capacity() * core::mem::size_of::<(Box<str>, u32)>()
+                    + index.keys().map(|name| name.len()).sum::<usize>()
+            }
+            NameIndex::Sorted(index) => core::mem::size_of_val(&**index),
+            // The table is static.
+            NameIndex::Perfect(_) => 0,
+        }
+    }
+
+    /// Get the position of the contig with the `name`.
+    pub(crate) fn get<C>(&self, contigs: &[Contig<C>], name: &str) -> Option<usize> {
+        match self {
+            #[cfg(feature = "std")]
+            NameIndex::Hash(index) => index.get(name).map(|&i| i as usize),
+            NameIndex::Sorted(index) => {
+                let idx = index.partition_point(|&entry| name_at(contigs, entry) < name);
+                index
+                    .get(idx)
+                    .filter(|&&entry| name_at(contigs, entry) == name)
+                    .map(|&(i, _)| i as usize)
+            }
+            NameIndex::Perfect(index) => {
+                let entry = index.get(name);
+                (name_at(contigs, entry) == name).then_some(entry.0 as usize)
+            }
+        }
+    }
+}
+
+fn position(i: usize) -> u32 {
+    u32::try_from(i).expect("Genome build should have less than 2^32 contigs")
+}
+
+fn name_at<C>(contigs: &[Contig<C>], (i, j): (u32, u32)) -> &str {
+    contigs[i as usize]
+        .names()
+        .nth(j as usize)
+        .expect("Name position should be valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::{IndexKind, NameIndex};
+    use crate::Contig;
+
+    fn contigs() -> Vec<Contig<u8>> {
+        vec![
+            Contig::new("1", &["chr1", "NC_000001.11"], 10).unwrap(),
+            Contig::new("2", &["chr2", "shared"], 20).unwrap(),
+            Contig::new("MT", &["chrM", "shared"], 30).unwrap(),
+        ]
+    }
+
+    fn check_index(kind: IndexKind) {
+        let contigs = contigs();
+        let index = NameIndex::new(kind, &contigs);
+
+        assert_eq!(index.kind(), kind);
+        assert_eq!(index.get(&contigs, "1"), Some(0));
+        assert_eq!(index.get(&contigs, "NC_000001.11"), Some(0));
+        assert_eq!(index.get(&contigs, "chr2"), Some(1));
+        assert_eq!(index.get(&contigs, "chrM"), Some(2));
+        assert_eq!(index.get(&contigs, "shared"), Some(1));
+        assert_eq!(index.get(&contigs, "chr3"), None);
+        assert_eq!(index.get(&contigs, ""), None);
+
+        let mut contigs = contigs;
+        let mut index = index;
+        contigs[0].push_alt_name("chr3");
+        index.insert(&contigs, (0, 3));
+        contigs.push(Contig::new("3", &["shared", "chrY"], 40).unwrap());
+        index.extend(&contigs, 3);
+
+        assert_eq!(index.get(&contigs, "chr3"), Some(0));
+        assert_eq!(index.get(&contigs, "NC_000001.11"), Some(0));
+        assert_eq!(index.get(&contigs, "3"), Some(3));
+        assert_eq!(index.get(&contigs, "chrY"), Some(3));
+        assert_eq!(index.get(&contigs, "shared"), Some(1));
+        assert_eq!(index.get(&contigs, "chrX"), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash_index() {
+        check_index(IndexKind::Hash);
+    }
+
+    #[test]
+    fn test_sorted_index() {
+        check_index(IndexKind::Sorted);
+    }
+}
@@ -0,0 +1,292 @@
+//! # Karyotype presets
+//!
+//! The module includes [`Karyotype`], the conventional chromosome order of common organisms,
+//! e.g. `1`-`22`, `X`, `Y`, `MT` for human.
+
+use alloc::{format, string::String};
+use core::str::FromStr;
+
+use crate::Contig;
+
+/// The conventional chromosome order of an organism.
+///
+/// The preset is chosen by the organism name (see [`FromStr`])
+/// or by the major assembly of a build (see [`Karyotype::for_assembly`]).
+/// Use [`crate::ContigOrdering::Karyotype`] to sort the contigs of a build in the preset order.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::Karyotype;
+///
+/// let karyotype = Karyotype::for_assembly("GRCm39").expect("Known assembly");
+/// assert_eq!(karyotype, Karyotype::Mouse);
+/// assert_eq!(karyotype.chromosomes().len(), 22);
+///
+/// assert_eq!("Homo sapiens".parse(), Ok(Karyotype::Human));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Karyotype {
+    /// *Homo sapiens*: `1`-`22`, `X`, `Y`, `MT`.
+    Human,
+    /// *Mus musculus*: `1`-`19`, `X`, `Y`, `MT`.
+    Mouse,
+    /// *Rattus norvegicus*: `1`-`20`, `X`, `Y`, `MT`.
+    Rat,
+    /// *Danio rerio*: `1`-`25`, `MT`.
+    Zebrafish,
+    /// *Saccharomyces cerevisiae*: `I`-`XVI`, `MT`.
+    Yeast,
+}
+
+impl Karyotype {
+    /// All karyotype presets.
+    pub const ALL: [Karyotype; 5] = [
+        Karyotype::Human,
+        Karyotype::Mouse,
+        Karyotype::Rat,
+        Karyotype::Zebrafish,
+        Karyotype::Yeast,
+    ];
+
+    /// Get the common name of the organism, e.g. `human`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Karyotype::Human => "human",
+            Karyotype::Mouse => "mouse",
+            Karyotype::Rat => "rat",
+            Karyotype::Zebrafish => "zebrafish",
+            Karyotype::Yeast => "yeast",
+        }
+    }
+
+    /// Get the scientific name of the organism, e.g. `Homo sapiens`.
+    pub fn organism(&self) -> &'static str {
+        match self {
+            Karyotype::Human => "Homo sapiens",
+            Karyotype::Mouse => "Mus musculus",
+            Karyotype::Rat => "Rattus norvegicus",
+            Karyotype::Zebrafish => "Danio rerio",
+            Karyotype::Yeast => "Saccharomyces cerevisiae",
+        }
+    }
+
+    /// Get the chromosome names in the conventional order.
+    pub fn chromosomes(&self) -> &'static [&'static str] {
+        #[rustfmt::skip]
+        const HUMAN: &[&str] = &[
+            "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+            "13", "14", "15", "16", "17", "18", "19", "20", "21", "22", "X", "Y", "MT",
+        ];
+        #[rustfmt::skip]
+        const MOUSE: &[&str] = &[
+            "1", "2", "3", "4", "5", "6", "7", "8", "9", "10",
+            "11", "12", "13", "14", "15", "16", "17", "18", "19", "X", "Y", "MT",
+        ];
+        #[rustfmt::skip]
+        const RAT: &[&str] = &[
+            "1", "2", "3", "4", "5", "6", "7", "8", "9", "10",
+            "11", "12", "13", "14", "15", "16", "17", "18", "19", "20", "X", "Y", "MT",
+        ];
+        #[rustfmt::skip]
+        const ZEBRAFISH: &[&str] = &[
+            "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13",
+            "14", "15", "16", "17", "18", "19", "20", "21", "22", "23", "24", "25", "MT",
+        ];
+        #[rustfmt::skip]
+        const YEAST: &[&str] = &[
+            "I", "II", "III", "IV", "V", "VI", "VII", "VIII",
+            "IX", "X", "XI", "XII", "XIII", "XIV", "XV", "XVI", "MT",
+        ];
+
+        match self {
+            Karyotype::Human => HUMAN,
+            Karyotype::Mouse => MOUSE,
+            Karyotype::Rat => RAT,
+            Karyotype::Zebrafish => ZEBRAFISH,
+            Karyotype::Yeast => YEAST,
+        }
+    }
+
+    /// Get the plausible maximum contig length of the organism in base pairs,
+    /// with a margin above the longest chromosome.
+    ///
+    /// The length is meant for the sanity checks, see [`crate::GenomeBuild::validate_lengths`].
+    pub fn max_contig_length(&self) -> u64 {
+        match self {
+            // chr1: 249 Mb
+            Karyotype::Human => 300_000_000,
+            // chr1: 195 Mb
+            Karyotype::Mouse => 250_000_000,
+            // chr1: 260 Mb in mRatBN7.2
+            Karyotype::Rat => 300_000_000,
+            // chr4: 78 Mb in GRCz11
+            Karyotype::Zebrafish => 100_000_000,
+            // chrXII: 1.08 Mb, chrIV: 1.53 Mb
+            Karyotype::Yeast => 2_000_000,
+        }
+    }
+
+    /// Get the preset of the major assembly, e.g. `GRCh38` or `hg38`,
+    /// or `None` if the assembly is unknown.
+    pub fn for_assembly(major_assembly: &str) -> Option<Karyotype> {
+        const PREFIXES: [(&str, Karyotype); 12] = [
+            ("GRCh", Karyotype::Human),
+            ("hg", Karyotype::Human),
+            ("NCBI3", Karyotype::Human),
+            ("GRCm", Karyotype::Mouse),
+            ("mm", Karyotype::Mouse),
+            ("mRatBN", Karyotype::Rat),
+            ("Rnor", Karyotype::Rat),
+            ("rn", Karyotype::Rat),
+            ("GRCz", Karyotype::Zebrafish),
+            ("danRer", Karyotype::Zebrafish),
+            ("R64", Karyotype::Yeast),
+            ("sacCer", Karyotype::Yeast),
+        ];
+
+        PREFIXES.iter().find_map(|(prefix, karyotype)| {
+            major_assembly
+                .get(..prefix.len())
+                .filter(|start| start.eq_ignore_ascii_case(prefix))
+                .map(|_| *karyotype)
+        })
+    }
+
+    /// Get the position of the `contig` in the conventional order,
+    /// or `None` if the contig is not a chromosome of the preset.
+    ///
+    /// Any name of the contig is matched, ignoring the case and the `chr` prefix.
+    /// The mitochondrion matches both `M` and `MT`.
+    pub fn position<C>(&self, contig: &Contig<C>) -> Option<usize> {
+        let chromosomes = self.chromosomes();
+        core::iter::once(contig.name())
+            .chain(contig.alt_names())
+            .map(strip_chr)
+            .find_map(|name| {
+                let name = if name.eq_ignore_ascii_case("M") {
+                    "MT"
+                } else {
+                    name
+                };
+                chromosomes
+                    .iter()
+                    .position(|chromosome| chromosome.eq_ignore_ascii_case(name))
+            })
+    }
+}
+
+/// The chromosomal sex of a sample, see [`crate::GenomeBuild::expected_contigs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Sex {
+    /// Two copies of `X` (`XX`), or `Z` and `W` (`ZW`) in birds.
+    Female,
+    /// `X` and `Y` (`XY`), or two copies of `Z` (`ZZ`) in birds.
+    Male,
+}
+
+impl Sex {
+    /// All sexes.
+    pub const ALL: [Sex; 2] = [Sex::Female, Sex::Male];
+
+    /// Get the sex name, e.g. `female`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Sex::Female => "female",
+            Sex::Male => "male",
+        }
+    }
+
+    /// Test if a sample of the sex carries the sex chromosome with the `name`,
+    /// ignoring the case and the `chr` prefix.
+    pub(crate) fn carries(&self, name: &str) -> bool {
+        let absent = match self {
+            Sex::Female => "Y",
+            Sex::Male => "W",
+        };
+        !strip_chr(name).eq_ignore_ascii_case(absent)
+    }
+}
+
+/// Parse the sex name (e.g. `female`) or the sex chromosomes (e.g. `XX`), ignoring the case.
+impl FromStr for Sex {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("XX") {
+            return Ok(Sex::Female);
+        } else if s.eq_ignore_ascii_case("XY") {
+            return Ok(Sex::Male);
+        }
+        Sex::ALL
+            .into_iter()
+            .find(|sex| sex.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown sex {s:?}"))
+    }
+}
+
+impl core::fmt::Display for Sex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parse the organism, either the common name (e.g. `mouse`)
+/// or the scientific name (e.g. `Mus musculus`), ignoring the case.
+impl FromStr for Karyotype {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Karyotype::ALL
+            .into_iter()
+            .find(|karyotype| {
+                karyotype.as_str().eq_ignore_ascii_case(s)
+                    || karyotype.organism().eq_ignore_ascii_case(s)
+            })
+            .ok_or_else(|| format!("Unknown organism {s:?}"))
+    }
+}
+
+impl core::fmt::Display for Karyotype {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Strip the `chr` prefix from the `name`, ignoring the case.
+pub(crate) fn strip_chr(name: &str) -> &str {
+    match name.get(..3) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("chr") => &name[3..],
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{strip_chr, Karyotype};
+    use crate::Contig;
+
+    #[test]
+    fn test_position() {
+        let contig = |name: &str| Contig::new(name, &[] as &[&str], 10u32).unwrap();
+
+        assert_eq!(Karyotype::Human.position(&contig("chr2")), Some(1));
+        assert_eq!(Karyotype::Human.position(&contig("chrM")), Some(24));
+        assert_eq!(Karyotype::Mouse.position(&contig("MT")), Some(21));
+        assert_eq!(
+            Karyotype::Human.position(&contig("chr1_KI270706v1_random")),
+            None
+        );
+        assert_eq!(Karyotype::Yeast.position(&contig("chrIV")), Some(3));
+    }
+
+    #[test]
+    fn test_for_assembly() {
+        assert_eq!(Karyotype::for_assembly("GRCh37"), Some(Karyotype::Human));
+        assert_eq!(Karyotype::for_assembly("hg19"), Some(Karyotype::Human));
+        assert_eq!(Karyotype::for_assembly("mRatBN7.2"), Some(Karyotype::Rat));
+        assert_eq!(Karyotype::for_assembly("T2T-CHM13"), None);
+        assert_eq!(strip_chr("CHRX"), "X");
+        assert_eq!(strip_chr("ch"), "ch");
+    }
+}
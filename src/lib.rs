@@ -77,8 +77,90 @@
 //! let y = build.contig_by_name("chrY");
 //! assert!(y.is_some());
 //! ```
+//!
+//! ## Features
+//!
+//! * `std` (default) - parsing assembly reports and other formats (see [`formats`])
+//!   from [`std::io::BufRead`], auditing GTF/GFF3 annotations (see [`annotation`]),
+//!   and the hash-based contig name index.
+//!   Without `std`, the crate only needs `alloc`.
+//! * `rayon` - parallel iteration over the contigs and windows of a genome build.
+//! * `wasm` - [`wasm_bindgen`](https://docs.rs/wasm-bindgen) wrappers for the genome build
+//!   and contig lookup.
+//! * `python` - Python bindings, see the `python` module.
+//! * `ffi` - C bindings, see the `ffi` module.
+//! * `bgzf` - reading BGZF-compressed files, such as the BAM header (see [`formats`]),
+//!   and gzip or BGZF-compressed inputs of all text parsers.
+//! * `fetch` - downloading assembly reports from NCBI, see the `fetch` module.
+//! * `tokio` - parsing assembly reports from [`tokio::io::AsyncBufRead`](https://docs.rs/tokio)
+//!   and, along with `fetch`, downloading them without blocking the async runtime.
+//! * `refget` - verifying the contig digests at GA4GH refget and seqcol servers, see the `refget` module.
+//! * `cli` - the `dabuild` command-line tool.
+//! * `watch` - loading the genome builds of a reference directory into a registry
+//!   and reloading them on change, see the `manager` module.
+//! * `json` - reading the genome builds back from JSON of any schema version (see [`formats`]).
+//! * `testing` - [proptest](https://docs.rs/proptest) strategies for property-testing
+//!   against valid contigs, genome builds, and regions, see the `testing` module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+#[cfg(feature = "std")]
+pub mod annotation;
+mod browser;
 pub mod builds;
+mod consistency;
+mod contig_map;
+mod cytoband;
+#[cfg(feature = "std")]
+mod decompress;
+pub mod digest;
+#[cfg(feature = "fetch")]
+pub mod fetch;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod formats;
 mod genome;
+mod index;
+mod karyotype;
+mod liftover;
+#[cfg(feature = "watch")]
+pub mod manager;
+mod metadata;
+mod phf;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "refget")]
+pub mod refget;
+mod region;
+mod spike_in;
+mod stats;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod traits;
+mod units;
+mod validation;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
-pub use genome::{Contig, GenomeBuild, GenomeBuildIdentifier};
+pub use browser::Browser;
+pub use consistency::{LengthMismatch, RenamePlan, SequenceCheck, StyleDetection};
+pub use contig_map::ContigMap;
+pub use cytoband::{Cytoband, Cytobands};
+pub use genome::{
+    ConflictPolicy, Contig, ContigCategory, ContigKey, ContigOrdering, GapHandling, GenomeBuild,
+    GenomeBuildBuilder, GenomeBuildIdentifier, IterationOrder, NameStyle, SequenceRole,
+    SharedGenomeBuild,
+};
+pub use index::IndexKind;
+pub use karyotype::{Karyotype, Sex};
+pub use liftover::{AltPlacement, AltPlacements, Liftability};
+pub use metadata::{AssemblyLevel, BuildMetadata, GenomeRepresentation};
+pub use region::{partition_regions, GenomicRegion, RegionCmp, RegionKey, RegionSet, Strand};
+pub use spike_in::SpikeIn;
+pub use stats::LengthBin;
+pub use traits::{ContigLike, GenomeBuildLike};
+pub use units::format_bp;
+pub use validation::{BuildProblem, ValidationReport};
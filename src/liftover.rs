@@ -0,0 +1,244 @@
+//! # Liftover between genome builds
+//!
+//! The module includes [`Liftability`], the way of moving the coordinates on a contig
+//! onto another genome build, see [`GenomeBuild::liftability`],
+//! and [`AltPlacements`], the placements of the alt scaffolds and patches
+//! onto the primary chromosomes, see [`AltPlacements::project_to_primary`].
+
+use alloc::{collections::BTreeMap, string::String};
+
+use num_traits::{FromPrimitive, ToPrimitive, Zero};
+
+use crate::{Contig, GenomeBuild, GenomicRegion};
+
+/// The way of moving the coordinates on a contig onto another genome build,
+/// see [`GenomeBuild::liftability`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Liftability {
+    /// The target build has the same sequence under the same name,
+    /// hence the coordinates are valid as they are.
+    Identical,
+    /// The target build has the same sequence under another name,
+    /// hence renaming the contig is enough.
+    RenamedOnly,
+    /// The target build has the contig, but with another sequence,
+    /// hence the coordinates must be lifted over with a chain file.
+    RequiresChain,
+    /// The target build has no such contig.
+    NotPresent,
+}
+
+impl<C> GenomeBuild<C>
+where
+    C: PartialEq,
+{
+    /// Find how to move the coordinates on the `contig` of the build onto the `target` build,
+    /// e.g. to decide per chromosome if renaming is enough or if liftover is required.
+    ///
+    /// The contig is looked up in the target build by any of its names,
+    /// or, failing that, by its MD5 digest. Two contigs have the same sequence
+    /// if they have the same MD5 digest, or the same length if any digest is unknown.
+    ///
+    /// Returns `None` if the build has no `contig`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use dabuild::{GenomeBuild, GenomeBuildIdentifier, Liftability};
+    /// use dabuild::builds::{get_grch37_p13, get_grch38_p13};
+    ///
+    /// let grch37: GenomeBuild<u32> = get_grch37_p13();
+    /// let grch38: GenomeBuild<u32> = get_grch38_p13();
+    ///
+    /// assert_eq!(grch37.liftability("MT", &grch38), Some(Liftability::Identical));
+    /// assert_eq!(grch37.liftability("chr1", &grch38), Some(Liftability::RequiresChain));
+    /// assert_eq!(grch37.liftability("HLA-A", &grch38), None);
+    ///
+    /// let mito: GenomeBuild<u32> = GenomeBuild::builder()
+    ///     .id(GenomeBuildIdentifier::from_str("mito").expect("Infallible"))
+    ///     .contig("rCRS", 16_569)
+    ///     .alias("rCRS", "NC_012920.1")
+    ///     .build()
+    ///     .expect("Valid build");
+    /// assert_eq!(grch37.liftability("MT", &mito), Some(Liftability::RenamedOnly));
+    /// assert_eq!(grch37.liftability("1", &mito), Some(Liftability::NotPresent));
+    /// ```
+    pub fn liftability(&self, contig: &str, target: &GenomeBuild<C>) -> Option<Liftability> {
+        let contig = self.contig_by_name(contig)?;
+        Some(match target.counterpart(contig) {
+            Some(other) if !same_sequence(contig, other) => Liftability::RequiresChain,
+            Some(other) if other.matches(contig.name()) => Liftability::Identical,
+            Some(_) => Liftability::RenamedOnly,
+            None => Liftability::NotPresent,
+        })
+    }
+
+    /// Get the contig of the build that corresponds to the `contig` of another build.
+    ///
+    /// The contig is looked up by any of its names, or, failing that, by its MD5 digest.
+    pub(crate) fn counterpart(&self, contig: &Contig<C>) -> Option<&Contig<C>> {
+        contig
+            .names()
+            .find_map(|name| self.contig_by_name(name))
+            .or_else(|| {
+                let md5 = contig.md5()?;
+                self.contigs().find(|other| other.md5() == Some(md5))
+            })
+    }
+}
+
+/// Test if the contigs have the same sequence, judging by the MD5 digests if both are known,
+/// and by the lengths otherwise.
+pub(crate) fn same_sequence<C: PartialEq>(left: &Contig<C>, right: &Contig<C>) -> bool {
+    match (left.md5(), right.md5()) {
+        (Some(l), Some(r)) => l == r,
+        _ => left.length() == right.length(),
+    }
+}
+
+/// The placement of an alt scaffold or a patch onto its parent, usually a primary chromosome,
+/// as in the NCBI `alt_scaffold_placement.txt` files.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AltPlacement<C> {
+    alt: GenomicRegion<C>,
+    parent: GenomicRegion<C>,
+    reverse: bool,
+}
+
+impl<C> AltPlacement<C> {
+    /// Create a placement of the `alt` region, the aligned part of an alt scaffold,
+    /// onto the `parent` region, on the reverse strand if `reverse`.
+    pub fn new(alt: GenomicRegion<C>, parent: GenomicRegion<C>, reverse: bool) -> Self {
+        AltPlacement {
+            alt,
+            parent,
+            reverse,
+        }
+    }
+
+    /// Get the aligned part of the alt scaffold.
+    pub fn alt(&self) -> &GenomicRegion<C> {
+        &self.alt
+    }
+
+    /// Get the part of the parent aligned to the alt scaffold.
+    pub fn parent(&self) -> &GenomicRegion<C> {
+        &self.parent
+    }
+
+    /// Test if the alt scaffold is placed on the reverse strand of the parent.
+    pub fn is_reverse(&self) -> bool {
+        self.reverse
+    }
+}
+
+/// The placements of the alt scaffolds and patches of a genome build,
+/// e.g. parsed by [`crate::formats::parse_alt_placements`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AltPlacements<C> {
+    /// The placements by the main name of the alt scaffold.
+    placements: BTreeMap<String, AltPlacement<C>>,
+}
+
+impl<C> Default for AltPlacements<C> {
+    fn default() -> Self {
+        AltPlacements {
+            placements: BTreeMap::new(),
+        }
+    }
+}
+
+impl<C> AltPlacements<C> {
+    /// Create empty placements.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of the placed alt scaffolds.
+    pub fn len(&self) -> usize {
+        self.placements.len()
+    }
+
+    /// Test if no alt scaffold is placed.
+    pub fn is_empty(&self) -> bool {
+        self.placements.is_empty()
+    }
+
+    /// Add the `placement`, replacing the previous placement of its alt scaffold.
+    pub fn insert(&mut self, placement: AltPlacement<C>) {
+        let name = placement.alt.contig().name().into();
+        self.placements.insert(name, placement);
+    }
+
+    /// Get the placement of the alt scaffold `contig`.
+    pub fn get(&self, contig: &Contig<C>) -> Option<&AltPlacement<C>> {
+        self.placements.get(contig.name())
+    }
+
+    /// Project the `region` on an alt scaffold or a patch onto its parent,
+    /// e.g. to report the variants called on the HLA alt scaffolds on the primary chromosome.
+    ///
+    /// The projection is approximate. The region is clipped to the aligned part
+    /// of the alt scaffold, and its coordinates are scaled linearly
+    /// onto the aligned part of the parent, hence the indels between the two are not accounted for.
+    ///
+    /// Returns `None` if the contig of the region is not placed,
+    /// or if the region is outside the aligned part of the alt scaffold.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{AltPlacement, AltPlacements, Contig, GenomicRegion};
+    ///
+    /// let chr6 = Contig::new("chr6", &["6"], 1_000u32).unwrap();
+    /// let alt = Contig::new("chr6_alt", &[] as &[&str], 100).unwrap();
+    /// let mut placements = AltPlacements::new();
+    /// placements.insert(AltPlacement::new(
+    ///     GenomicRegion::new(&alt, 0, 100).unwrap(),
+    ///     GenomicRegion::new(&chr6, 500, 700).unwrap(),
+    ///     false,
+    /// ));
+    ///
+    /// let region = GenomicRegion::new(&alt, 10, 20).unwrap();
+    /// let projected = placements.project_to_primary(&region).unwrap();
+    /// assert_eq!(projected.contig().name(), "chr6");
+    /// assert_eq!((*projected.start(), *projected.end()), (520, 540));
+    /// ```
+    pub fn project_to_primary(&self, region: &GenomicRegion<C>) -> Option<GenomicRegion<C>>
+    where
+        C: Clone + Zero + PartialOrd + ToPrimitive + FromPrimitive,
+    {
+        let placement = self.get(region.contig())?;
+        let (alt_start, alt_end) = (
+            placement.alt.start().to_u64()?,
+            placement.alt.end().to_u64()?,
+        );
+        let (parent_start, parent_end) = (
+            placement.parent.start().to_u64()?,
+            placement.parent.end().to_u64()?,
+        );
+        let (start, end) = (region.start().to_u64()?, region.end().to_u64()?);
+        if end < alt_start || alt_end < start || alt_start == alt_end {
+            return None;
+        }
+
+        // The offset on the parent of a coordinate on the alt scaffold.
+        let scale = |x: u64| {
+            let offset = u128::from(x.clamp(alt_start, alt_end) - alt_start)
+                * u128::from(parent_end - parent_start)
+                / u128::from(alt_end - alt_start);
+            offset as u64
+        };
+        let (start, end) = if placement.reverse {
+            (parent_end - scale(end), parent_end - scale(start))
+        } else {
+            (parent_start + scale(start), parent_start + scale(end))
+        };
+        GenomicRegion::new(
+            placement.parent.contig(),
+            C::from_u64(start)?,
+            C::from_u64(end)?,
+        )
+    }
+}
@@ -0,0 +1,366 @@
+//! # Manage the genome builds of a reference directory
+//!
+//! [`BuildManager`] loads the reference files of a directory into a registry
+//! of [`SharedGenomeBuild`]s, keyed by the file name.
+//! [`BuildManager::poll`] reloads the files that changed since the last poll,
+//! and [`BuildManager::watch`] polls in a background thread.
+//!
+//! The format of a file is guessed from its extension, ignoring a `.gz` suffix:
+//!
+//! * `.txt` - assembly report
+//! * `.fai` - FASTA index
+//! * `.dict` - sequence dictionary
+//! * `.sizes` - `chrom.sizes`
+//! * `.vcf` - VCF header
+//! * `.bam` - BAM header, with the `bgzf` feature
+//!
+//! The other files are ignored. The build identifier is the file name without the extensions
+//! and without the `_assembly_report` suffix, e.g. `GRCh38.p14` for `GRCh38.p14_assembly_report.txt`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use std::{fs, sync::Arc};
+//! use dabuild::manager::{BuildEvent, BuildManager};
+//!
+//! # let dir = std::env::temp_dir().join("dabuild-doc-manager");
+//! # let _ = fs::remove_dir_all(&dir);
+//! fs::create_dir_all(&dir).expect("Writable temporary directory");
+//! fs::write(dir.join("toy.sizes"), "chr1\t1000\nchr2\t500\n").expect("Writable file");
+//!
+//! let manager = Arc::new(BuildManager::<u32>::new(&dir));
+//! let events = manager.poll().expect("Readable directory");
+//! assert_eq!(events, [BuildEvent::Added("toy.sizes".to_string())]);
+//!
+//! let build = manager.get("toy.sizes").expect("Loaded build");
+//! assert_eq!(build.contigs().count(), 2);
+//!
+//! // Poll every second until the handle is dropped.
+//! let handle = manager.watch(std::time::Duration::from_secs(1));
+//! handle.stop();
+//! # fs::remove_dir_all(&dir).expect("Removable directory");
+//! ```
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::{self, File},
+    hash::Hash,
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{mpsc, Arc, Mutex, PoisonError, RwLock},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use num_traits::Zero;
+
+use crate::{
+    builds::parse_assembly_report,
+    formats::{parse_chrom_sizes, parse_dict, parse_fai, parse_vcf_header},
+    GenomeBuild, GenomeBuildIdentifier, SharedGenomeBuild,
+};
+
+/// The change of the registry of a [`BuildManager`] found by [`BuildManager::poll`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BuildEvent {
+    /// A build was loaded from a new file.
+    Added(String),
+    /// A build was reloaded from a changed file, and its contigs or metadata changed.
+    Updated(String),
+    /// The file of a build was removed, and so was the build.
+    Removed(String),
+    /// A new or changed file could not be loaded, with the error message.
+    /// The previously loaded build, if any, is kept.
+    Failed(String, String),
+}
+
+impl BuildEvent {
+    /// Get the file name of the build.
+    pub fn name(&self) -> &str {
+        match self {
+            BuildEvent::Added(name)
+            | BuildEvent::Updated(name)
+            | BuildEvent::Removed(name)
+            | BuildEvent::Failed(name, _) => name,
+        }
+    }
+}
+
+/// The modification time and the size of a file.
+type Stamp = (Option<SystemTime>, u64);
+
+struct Entry<C> {
+    stamp: Stamp,
+    build: Option<SharedGenomeBuild<C>>,
+}
+
+/// A registry of the genome builds loaded from the reference files of a directory.
+///
+/// See the [module](crate::manager) documentation for the supported files.
+pub struct BuildManager<C> {
+    dir: PathBuf,
+    registry: RwLock<HashMap<String, Entry<C>>>,
+    /// Held for the whole poll, hence the polls do not race to load the same files.
+    poll_guard: Mutex<()>,
+}
+
+impl<C> BuildManager<C> {
+    /// Create a manager of the reference files in the `dir`.
+    ///
+    /// No file is loaded until the first [`BuildManager::poll`].
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        BuildManager {
+            dir: dir.as_ref().to_path_buf(),
+            registry: RwLock::default(),
+            poll_guard: Mutex::default(),
+        }
+    }
+
+    /// Get the managed directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get a shared handle of the build loaded from the file `name`,
+    /// or `None` if no such build is loaded.
+    ///
+    /// The handle stays valid after the build is reloaded or removed.
+    pub fn get(&self, name: &str) -> Option<SharedGenomeBuild<C>> {
+        self.registry
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .and_then(|entry| entry.build.clone())
+    }
+
+    /// Get the file names of the loaded builds, in lexicographic order.
+    pub fn names(&self) -> Vec<String> {
+        let registry = self.registry.read().unwrap_or_else(PoisonError::into_inner);
+        let mut names: Vec<_> = registry
+            .iter()
+            .filter(|(_, entry)| entry.build.is_some())
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl<C> BuildManager<C>
+where
+    C: Clone + Hash + FromStr + Zero + PartialOrd,
+{
+    /// Load the new files and reload the files whose modification time or size changed
+    /// since the last poll, and drop the builds of the removed files.
+    ///
+    /// A reloaded build replaces the previous build only if it changed,
+    /// as by [`GenomeBuild::refresh_from`], hence the unchanged builds keep their handles.
+    /// Returns the changes in the lexicographic order of the file names.
+    ///
+    /// The concurrent polls run one after another, hence each change is reported once.
+    /// The readers are not blocked while the files are loaded.
+    ///
+    /// ## Errors
+    ///
+    /// The directory cannot be read. A file that cannot be loaded is reported
+    /// as [`BuildEvent::Failed`] instead.
+    pub fn poll(&self) -> io::Result<Vec<BuildEvent>> {
+        let _guard = self
+            .poll_guard
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut files = HashMap::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str() {
+                if Kind::guess(name).is_some() {
+                    files.insert(name.to_string(), (metadata.modified().ok(), metadata.len()));
+                }
+            }
+        }
+
+        let mut events = Vec::new();
+        {
+            let mut registry = self
+                .registry
+                .write()
+                .unwrap_or_else(PoisonError::into_inner);
+            registry.retain(|name, entry| {
+                let kept = files.contains_key(name);
+                if !kept && entry.build.is_some() {
+                    events.push(BuildEvent::Removed(name.clone()));
+                }
+                kept
+            });
+        }
+
+        for (name, stamp) in files {
+            let previous = {
+                let registry = self.registry.read().unwrap_or_else(PoisonError::into_inner);
+                match registry.get(&name) {
+                    Some(entry) if entry.stamp == stamp => continue,
+                    Some(entry) => entry.build.clone(),
+                    None => None,
+                }
+            };
+
+            // The files are loaded without holding the lock, hence the readers are not blocked.
+            let (build, event) = match previous {
+                Some(previous) => {
+                    let mut build = GenomeBuild::clone(&previous);
+                    match self.reload(&name, &mut build) {
+                        Ok(true) => (
+                            Some(Arc::new(build)),
+                            Some(BuildEvent::Updated(name.clone())),
+                        ),
+                        Ok(false) => (Some(previous), None),
+                        Err(e) => (
+                            Some(previous),
+                            Some(BuildEvent::Failed(name.clone(), e.to_string())),
+                        ),
+                    }
+                }
+                None => match self.load(&name) {
+                    Ok(fresh) => (Some(Arc::new(fresh)), Some(BuildEvent::Added(name.clone()))),
+                    Err(e) => (None, Some(BuildEvent::Failed(name.clone(), e.to_string()))),
+                },
+            };
+            events.extend(event);
+            self.registry
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(name, Entry { stamp, build });
+        }
+
+        events.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(events)
+    }
+
+    /// Reload the `build` from the file `name`, see [`GenomeBuild::refresh_from`].
+    ///
+    /// Returns `true` if the build changed.
+    fn reload(&self, name: &str, build: &mut GenomeBuild<C>) -> Result<bool, Box<dyn Error>> {
+        if Kind::guess(name) == Some(Kind::AssemblyReport) {
+            let (_, _, read) = self.open(name)?;
+            return build.refresh_from(read);
+        }
+        let fresh = self.load(name)?;
+        Ok(build.refresh_with(fresh.contigs().cloned().collect(), fresh.metadata().clone()))
+    }
+
+    fn load(&self, name: &str) -> Result<GenomeBuild<C>, Box<dyn Error>> {
+        let (kind, id, read) = self.open(name)?;
+        match kind {
+            Kind::AssemblyReport => parse_assembly_report(id, read),
+            Kind::Fai => parse_fai(id, read),
+            Kind::Dict => parse_dict(id, read),
+            Kind::ChromSizes => parse_chrom_sizes(id, read),
+            Kind::Vcf => parse_vcf_header(id, read),
+            #[cfg(feature = "bgzf")]
+            Kind::Bam => crate::formats::parse_bam_header(id, read),
+        }
+    }
+
+    /// Open the file `name` and get its kind and the build identifier.
+    fn open(
+        &self,
+        name: &str,
+    ) -> Result<(Kind, GenomeBuildIdentifier, BufReader<File>), Box<dyn Error>> {
+        let kind = Kind::guess(name).ok_or_else(|| format!("Unsupported file {name}"))?;
+        let id = GenomeBuildIdentifier::from_str(kind.build_name(name))?;
+        let path = self.dir.join(name);
+        let read = BufReader::new(
+            File::open(&path).map_err(|e| format!("Cannot open {}: {e}", path.display()))?,
+        );
+        Ok((kind, id, read))
+    }
+}
+
+impl<C> BuildManager<C>
+where
+    C: Clone + Hash + FromStr + Zero + PartialOrd + Send + Sync + 'static,
+{
+    /// Poll the directory every `interval` in a background thread,
+    /// until the returned [`WatchHandle`] is stopped or dropped.
+    ///
+    /// The errors of reading the directory are ignored, and the directory is polled again
+    /// after the `interval`.
+    pub fn watch(self: &Arc<Self>, interval: Duration) -> WatchHandle {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let manager = Arc::clone(self);
+        let thread = thread::spawn(move || loop {
+            let _ = manager.poll();
+            match stopped.recv_timeout(interval) {
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                _ => break,
+            }
+        });
+
+        WatchHandle { stop, thread }
+    }
+}
+
+/// The handle of the background polling started by [`BuildManager::watch`].
+///
+/// Dropping the handle stops the polling without waiting for the thread.
+pub struct WatchHandle {
+    stop: mpsc::Sender<()>,
+    thread: thread::JoinHandle<()>,
+}
+
+impl WatchHandle {
+    /// Stop the polling and wait for the ongoing poll to finish.
+    pub fn stop(self) {
+        let _ = self.stop.send(());
+        let _ = self.thread.join();
+    }
+}
+
+/// The kind of a reference file, guessed from its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    AssemblyReport,
+    Fai,
+    Dict,
+    ChromSizes,
+    Vcf,
+    #[cfg(feature = "bgzf")]
+    Bam,
+}
+
+impl Kind {
+    fn guess(name: &str) -> Option<Kind> {
+        let name = name.strip_suffix(".gz").unwrap_or(name);
+        match name.rsplit_once('.').map(|(_, ext)| ext) {
+            Some("txt") => Some(Kind::AssemblyReport),
+            Some("fai") => Some(Kind::Fai),
+            Some("dict") => Some(Kind::Dict),
+            Some("sizes") => Some(Kind::ChromSizes),
+            Some("vcf") => Some(Kind::Vcf),
+            #[cfg(feature = "bgzf")]
+            Some("bam") => Some(Kind::Bam),
+            _ => None,
+        }
+    }
+
+    /// Get the build name from the file `name`, e.g. `hg38` from `hg38.fa.fai`.
+    fn build_name(self, name: &str) -> &str {
+        let name = name.strip_suffix(".gz").unwrap_or(name);
+        let name = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+        match self {
+            Kind::AssemblyReport => name.strip_suffix("_assembly_report").unwrap_or(name),
+            Kind::Fai => [".fa", ".fasta", ".fna"]
+                .iter()
+                .find_map(|ext| name.strip_suffix(ext))
+                .unwrap_or(name),
+            Kind::ChromSizes => name.strip_suffix(".chrom").unwrap_or(name),
+            _ => name,
+        }
+    }
+}
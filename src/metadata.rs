@@ -0,0 +1,358 @@
+//! # Build metadata
+//!
+//! The module includes [`BuildMetadata`], the assembly-level information
+//! read from the header of an assembly report, such as the assembly level
+//! or the BioProject and BioSample accessions for the provenance tracking.
+//! The GenBank (`GCA_`) and RefSeq (`GCF_`) accessions of the assembly are kept in pairs,
+//! hence the assembly can be looked up by either.
+
+use alloc::{format, string::String};
+use core::str::FromStr;
+
+/// The level of the most contiguous sequences of an assembly, as reported in the
+/// `Assembly level` header line of the assembly report.
+///
+/// The levels are ordered from the least to the most contiguous, e.g. a chromosome-level
+/// analysis can require `level >= AssemblyLevel::Chromosome`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AssemblyLevel {
+    /// Contigs only.
+    Contig,
+    /// Scaffolds, but no chromosomes.
+    Scaffold,
+    /// At least one chromosome.
+    Chromosome,
+    /// All chromosomes are gapless.
+    CompleteGenome,
+}
+
+impl AssemblyLevel {
+    /// All assembly levels, from the least to the most contiguous.
+    pub const ALL: [AssemblyLevel; 4] = [
+        AssemblyLevel::Contig,
+        AssemblyLevel::Scaffold,
+        AssemblyLevel::Chromosome,
+        AssemblyLevel::CompleteGenome,
+    ];
+
+    /// Get the level as written in the assembly report, e.g. `Chromosome`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AssemblyLevel::Contig => "Contig",
+            AssemblyLevel::Scaffold => "Scaffold",
+            AssemblyLevel::Chromosome => "Chromosome",
+            AssemblyLevel::CompleteGenome => "Complete Genome",
+        }
+    }
+}
+
+/// Parse the assembly level, e.g. `Chromosome`, ignoring the case.
+impl FromStr for AssemblyLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AssemblyLevel::ALL
+            .into_iter()
+            .find(|level| level.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown assembly level {s:?}"))
+    }
+}
+
+impl core::fmt::Display for AssemblyLevel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The extent of the genome represented by an assembly, as reported in the
+/// `Genome representation` header line of the assembly report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GenomeRepresentation {
+    /// The assembly represents the whole genome.
+    Full,
+    /// The assembly represents a part of the genome, e.g. a single chromosome.
+    Partial,
+}
+
+impl GenomeRepresentation {
+    /// Get the representation as written in the assembly report, e.g. `full`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GenomeRepresentation::Full => "full",
+            GenomeRepresentation::Partial => "partial",
+        }
+    }
+}
+
+/// Parse the genome representation, e.g. `full`, ignoring the case.
+impl FromStr for GenomeRepresentation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [GenomeRepresentation::Full, GenomeRepresentation::Partial]
+            .into_iter()
+            .find(|representation| representation.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown genome representation {s:?}"))
+    }
+}
+
+impl core::fmt::Display for GenomeRepresentation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The assembly-level information of a genome build.
+///
+/// The metadata are read from the header of an assembly report,
+/// e.g. `# Assembly level: Chromosome`. The values missing from the header,
+/// or the builds loaded from other formats, have `None`.
+/// The assembly name, e.g. `# Assembly name: GRCh38.p13`, is the build identifier,
+/// see [`crate::GenomeBuild::id`].
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::{AssemblyLevel, GenomeBuild, GenomeRepresentation};
+/// use dabuild::builds::get_grch38_p13;
+///
+/// let build: GenomeBuild<u32> = get_grch38_p13();
+/// let metadata = build.metadata();
+///
+/// assert!(metadata.assembly_level() >= Some(AssemblyLevel::Chromosome));
+/// assert_eq!(metadata.genome_representation(), Some(GenomeRepresentation::Full));
+/// assert_eq!(metadata.bioproject(), Some("PRJNA31257"));
+/// assert_eq!(metadata.organism(), Some("Homo sapiens (human)"));
+/// assert_eq!(metadata.taxid(), Some(9606));
+/// assert_eq!(metadata.date(), Some("2019-02-28"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BuildMetadata {
+    organism: Option<String>,
+    taxid: Option<u32>,
+    submitter: Option<String>,
+    date: Option<String>,
+    assembly_level: Option<AssemblyLevel>,
+    genome_representation: Option<GenomeRepresentation>,
+    bioproject: Option<String>,
+    biosample: Option<String>,
+    genbank_accession: Option<String>,
+    refseq_accession: Option<String>,
+}
+
+impl BuildMetadata {
+    /// Create metadata with no values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of heap bytes of the metadata values.
+    pub(crate) fn heap_size(&self) -> usize {
+        [
+            &self.organism,
+            &self.submitter,
+            &self.date,
+            &self.bioproject,
+            &self.biosample,
+            &self.genbank_accession,
+            &self.refseq_accession,
+        ]
+        .into_iter()
+        .flatten()
+        .map(String::capacity)
+        .sum()
+    }
+
+    /// Get the organism name, e.g. `Homo sapiens (human)`.
+    pub fn organism(&self) -> Option<&str> {
+        self.organism.as_deref()
+    }
+
+    /// Set the organism name.
+    pub fn with_organism(mut self, organism: impl Into<String>) -> Self {
+        self.organism = Some(organism.into());
+        self
+    }
+
+    /// Get the NCBI Taxonomy identifier of the organism, e.g. `9606`.
+    pub fn taxid(&self) -> Option<u32> {
+        self.taxid
+    }
+
+    /// Set the NCBI Taxonomy identifier.
+    pub fn with_taxid(mut self, taxid: u32) -> Self {
+        self.taxid = Some(taxid);
+        self
+    }
+
+    /// Get the submitter of the assembly, e.g. `Genome Reference Consortium`.
+    pub fn submitter(&self) -> Option<&str> {
+        self.submitter.as_deref()
+    }
+
+    /// Set the submitter.
+    pub fn with_submitter(mut self, submitter: impl Into<String>) -> Self {
+        self.submitter = Some(submitter.into());
+        self
+    }
+
+    /// Get the submission date as written in the report header, e.g. `2019-02-28`.
+    pub fn date(&self) -> Option<&str> {
+        self.date.as_deref()
+    }
+
+    /// Set the submission date.
+    pub fn with_date(mut self, date: impl Into<String>) -> Self {
+        self.date = Some(date.into());
+        self
+    }
+
+    /// Get the assembly level.
+    pub fn assembly_level(&self) -> Option<AssemblyLevel> {
+        self.assembly_level
+    }
+
+    /// Set the assembly level.
+    pub fn with_assembly_level(mut self, level: AssemblyLevel) -> Self {
+        self.assembly_level = Some(level);
+        self
+    }
+
+    /// Get the genome representation.
+    pub fn genome_representation(&self) -> Option<GenomeRepresentation> {
+        self.genome_representation
+    }
+
+    /// Set the genome representation.
+    pub fn with_genome_representation(mut self, representation: GenomeRepresentation) -> Self {
+        self.genome_representation = Some(representation);
+        self
+    }
+
+    /// Get the accession of the BioProject of the assembly, e.g. `PRJNA31257`.
+    pub fn bioproject(&self) -> Option<&str> {
+        self.bioproject.as_deref()
+    }
+
+    /// Set the BioProject accession.
+    pub fn with_bioproject(mut self, accession: impl Into<String>) -> Self {
+        self.bioproject = Some(accession.into());
+        self
+    }
+
+    /// Get the accession of the BioSample of the sequenced sample, e.g. `SAMN03255769`.
+    pub fn biosample(&self) -> Option<&str> {
+        self.biosample.as_deref()
+    }
+
+    /// Set the BioSample accession.
+    pub fn with_biosample(mut self, accession: impl Into<String>) -> Self {
+        self.biosample = Some(accession.into());
+        self
+    }
+
+    /// Get the GenBank assembly accession, e.g. `GCA_000001405.28`.
+    pub fn genbank_accession(&self) -> Option<&str> {
+        self.genbank_accession.as_deref()
+    }
+
+    /// Set the GenBank assembly accession.
+    pub fn with_genbank_accession(mut self, accession: impl Into<String>) -> Self {
+        self.genbank_accession = Some(accession.into());
+        self
+    }
+
+    /// Get the RefSeq assembly accession, e.g. `GCF_000001405.39`.
+    pub fn refseq_accession(&self) -> Option<&str> {
+        self.refseq_accession.as_deref()
+    }
+
+    /// Set the RefSeq assembly accession.
+    pub fn with_refseq_accession(mut self, accession: impl Into<String>) -> Self {
+        self.refseq_accession = Some(accession.into());
+        self
+    }
+
+    /// Test if the `accession` is the GenBank or the RefSeq accession of the assembly.
+    pub fn has_accession(&self, accession: &str) -> bool {
+        self.genbank_accession() == Some(accession) || self.refseq_accession() == Some(accession)
+    }
+
+    /// Read a header `line` of an assembly report, e.g. `# Assembly level: Chromosome`.
+    ///
+    /// The unknown keys and values are ignored.
+    pub(crate) fn read_header_line(&mut self, line: &str) {
+        let Some((key, value)) = line
+            .strip_prefix('#')
+            .filter(|line| !line.starts_with('#'))
+            .and_then(|line| line.split_once(':'))
+        else {
+            return;
+        };
+
+        let value = value.trim();
+        match key.trim() {
+            "Organism name" => self.organism = non_empty(value),
+            "Taxid" => self.taxid = value.parse().ok(),
+            "Submitter" => self.submitter = non_empty(value),
+            "Date" => self.date = non_empty(value),
+            "Assembly level" => self.assembly_level = value.parse().ok(),
+            "Genome representation" => self.genome_representation = value.parse().ok(),
+            "BioProject" => self.bioproject = non_empty(value),
+            "BioSample" => self.biosample = non_empty(value),
+            "GenBank assembly accession" => self.genbank_accession = non_empty(value),
+            "RefSeq assembly accession" => self.refseq_accession = non_empty(value),
+            _ => {}
+        }
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    (!value.is_empty()).then(|| String::from(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AssemblyLevel, BuildMetadata, GenomeRepresentation};
+
+    #[test]
+    fn test_read_header_line() {
+        let mut metadata = BuildMetadata::new();
+        for line in [
+            "# Assembly name:  GRCm39",
+            "# Organism name:  Mus musculus (house mouse)",
+            "# Taxid:          10090",
+            "# Submitter:      Genome Reference Consortium",
+            "# Date:           2020-06-24",
+            "# Assembly level: Complete Genome",
+            "# Genome representation: partial",
+            "# BioProject:     PRJNA20689",
+            "# BioSample:      ",
+            "# GenBank assembly accession: GCA_000001635.9",
+            "# RefSeq assembly accession: GCF_000001635.27",
+            "## Assembly level: Contig",
+            "# Sequence-Name\tSequence-Role",
+        ] {
+            metadata.read_header_line(line);
+        }
+
+        assert_eq!(
+            metadata.assembly_level(),
+            Some(AssemblyLevel::CompleteGenome)
+        );
+        assert_eq!(
+            metadata.genome_representation(),
+            Some(GenomeRepresentation::Partial)
+        );
+        assert_eq!(metadata.organism(), Some("Mus musculus (house mouse)"));
+        assert_eq!(metadata.taxid(), Some(10090));
+        assert_eq!(metadata.submitter(), Some("Genome Reference Consortium"));
+        assert_eq!(metadata.date(), Some("2020-06-24"));
+        assert_eq!(metadata.bioproject(), Some("PRJNA20689"));
+        assert_eq!(metadata.biosample(), None);
+        assert!(metadata.has_accession("GCA_000001635.9"));
+        assert!(metadata.has_accession("GCF_000001635.27"));
+        assert!(!metadata.has_accession("GCF_000001635.26"));
+        assert!(AssemblyLevel::Scaffold < AssemblyLevel::Chromosome);
+    }
+}
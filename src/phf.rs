@@ -0,0 +1,44 @@
+//! # Perfect hashing
+//!
+//! The perfect hash tables of the contig names of the bundled builds,
+//! generated at compile time by the build script, which shares this module.
+//!
+//! The names are assigned to buckets by a hash with the seed `0`,
+//! and each bucket has a seed that maps its names to distinct slots of the table.
+//! Hence, a lookup takes two hashes and a name comparison.
+
+/// A perfect hash table of the contig names of a bundled build.
+#[derive(Debug)]
+pub(crate) struct PerfectIndex {
+    /// The seed of each bucket.
+    pub(crate) seeds: &'static [u32],
+    /// The contig and name positions in the slots of the table, one slot per distinct name.
+    pub(crate) entries: &'static [(u32, u32)],
+}
+
+impl PerfectIndex {
+    /// Get the contig and name position in the slot of the `name`.
+    ///
+    /// The slot of a name that is not in the table holds another name,
+    /// hence the caller must compare the names.
+    pub(crate) fn get(&self, name: &str) -> (u32, u32) {
+        let bucket = hash(name, 0) % self.seeds.len() as u64;
+        let slot = hash(name, self.seeds[bucket as usize]) % self.entries.len() as u64;
+        self.entries[slot as usize]
+    }
+}
+
+/// Hash the `name` with the `seed` using FNV-1a and a final avalanche.
+pub(crate) fn hash(name: &str, seed: u32) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET ^ u64::from(seed).wrapping_mul(PRIME);
+    for &byte in name.as_bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^ (hash >> 33)
+}
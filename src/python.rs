@@ -0,0 +1,145 @@
+//! # Python bindings
+//!
+//! The `python` feature exposes the genome builds, contigs, and the assembly report parser
+//! as a Python module `dabuild`. The module can be built and installed with
+//! [maturin](https://www.maturin.rs), which builds the crate as a `cdylib`:
+//!
+//! ```shell
+//! maturin develop
+//! ```
+//!
+//! The contigs can then be looked up by any of their names:
+//!
+//! ```python
+//! import dabuild
+//!
+//! build = dabuild.get_grch38_p13()
+//!
+//! y = build.contig_by_name("NC_000024.10")
+//! assert y.name == "Y"
+//! assert "chrY" in y.alt_names
+//! ```
+
+use std::{fs::File, io::BufReader, path::PathBuf, str::FromStr};
+
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{builds, Contig, GenomeBuild, GenomeBuildIdentifier};
+
+/// A contig with its names and length.
+#[pyclass(name = "Contig", module = "dabuild", frozen)]
+pub struct PyContig(Contig<u64>);
+
+#[pymethods]
+impl PyContig {
+    /// The main name of the contig (e.g. `10`, `X`, `MT`).
+    #[getter]
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    /// The alternative contig identifiers.
+    #[getter]
+    fn alt_names(&self) -> Vec<&str> {
+        self.0.alt_names().collect()
+    }
+
+    /// The number of bases of the contig.
+    #[getter]
+    fn length(&self) -> u64 {
+        *self.0.length()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Contig(name={:?}, alt_names={:?}, length={})",
+            self.0.name(),
+            self.0.alt_names().collect::<Vec<_>>(),
+            self.0.length()
+        )
+    }
+}
+
+/// A genome build with its identifiers and contigs.
+#[pyclass(name = "GenomeBuild", module = "dabuild", frozen)]
+pub struct PyGenomeBuild(GenomeBuild<u64>);
+
+#[pymethods]
+impl PyGenomeBuild {
+    /// The major assembly, e.g. `GRCh38`.
+    #[getter]
+    fn major_assembly(&self) -> &str {
+        self.0.id().major_assembly()
+    }
+
+    /// The patch, e.g. `p13`, or `None` if the build has no patch info.
+    #[getter]
+    fn patch(&self) -> Option<&str> {
+        self.0.id().patch()
+    }
+
+    /// All contigs of the build.
+    #[getter]
+    fn contigs(&self) -> Vec<PyContig> {
+        self.0.contigs().cloned().map(PyContig).collect()
+    }
+
+    /// Get a contig by its main name or by any of its alternative names.
+    fn contig_by_name(&self, name: &str) -> Option<PyContig> {
+        self.0.contig_by_name(name).cloned().map(PyContig)
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.contigs().count()
+    }
+
+    fn __contains__(&self, name: &str) -> bool {
+        self.0.contig_by_name(name).is_some()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GenomeBuild(major_assembly={:?}, patch={:?}, contigs={})",
+            self.0.id().major_assembly(),
+            self.0.id().patch(),
+            self.0.contigs().count()
+        )
+    }
+}
+
+/// Get the *GRCh37.p13* build.
+#[pyfunction]
+fn get_grch37_p13() -> PyGenomeBuild {
+    PyGenomeBuild(builds::get_grch37_p13())
+}
+
+/// Get the *GRCh38.p13* build.
+#[pyfunction]
+fn get_grch38_p13() -> PyGenomeBuild {
+    PyGenomeBuild(builds::get_grch38_p13())
+}
+
+/// Parse an assembly report file into a genome build.
+#[pyfunction]
+#[pyo3(signature = (path, major_assembly, patch = None))]
+fn parse_assembly_report(
+    path: PathBuf,
+    major_assembly: &str,
+    patch: Option<&str>,
+) -> PyResult<PyGenomeBuild> {
+    let id = match patch {
+        Some(patch) => GenomeBuildIdentifier::from((major_assembly, patch)),
+        None => GenomeBuildIdentifier::from_str(major_assembly).map_err(PyValueError::new_err)?,
+    };
+    let read = BufReader::new(File::open(path)?);
+    builds::parse_assembly_report(id, read)
+        .map(PyGenomeBuild)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Access genome build metadata.
+#[pymodule(name = "dabuild")]
+pub mod module {
+    #[pymodule_export]
+    use super::{get_grch37_p13, get_grch38_p13, parse_assembly_report, PyContig, PyGenomeBuild};
+}
@@ -0,0 +1,433 @@
+//! # GA4GH refget client
+//!
+//! A minimal client of the [refget](https://samtools.github.io/hts-specs/refget.html) API
+//! for checking that the sequence digests of a genome build, such as the contig MD5s,
+//! resolve to sequences known to a refget server.
+//!
+//! Only the sequence metadata are retrieved, the sequences are not downloaded.
+//!
+//! [`SeqColClient`] looks up the sequence collections registered at a
+//! [seqcol](https://ga4gh.github.io/refget/seqcols/) server
+//! that match the names and lengths of a genome build.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use dabuild::refget::RefgetClient;
+//!
+//! let client = RefgetClient::new("https://www.ebi.ac.uk/ena/cram");
+//! let metadata = client
+//!     .metadata("6aef897c3d6ff0c78aff06ac189178dd")
+//!     .expect("No network error")
+//!     .expect("Known sequence");
+//!
+//! assert_eq!(metadata.length, 248_956_422);
+//! ```
+
+use std::{error::Error, fmt::Display};
+
+use num_traits::ToPrimitive;
+
+use crate::{Contig, GenomeBuild};
+
+/// The metadata of a sequence known to a refget server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceMetadata {
+    /// The MD5 digest of the sequence.
+    pub md5: Option<String>,
+    /// The truncated SHA-512 digest of the sequence (`trunc512`).
+    pub trunc512: Option<String>,
+    /// The GA4GH identifier of the sequence (`SQ.` followed by the `sha512t24u` digest).
+    pub ga4gh: Option<String>,
+    /// The length of the sequence.
+    pub length: u64,
+    /// The aliases of the sequence, such as `chr1`.
+    pub aliases: Vec<String>,
+}
+
+/// The outcome of resolving the digest of a contig at a refget server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefgetStatus {
+    /// The digest resolves to a sequence of the contig length.
+    Resolved,
+    /// The digest is unknown to the server.
+    NotFound,
+    /// The digest resolves to a sequence of a different length.
+    LengthMismatch { expected: u64, found: u64 },
+}
+
+/// A contig along with the outcome of resolving its digest.
+pub type Verification<'a, C> = (&'a Contig<C>, RefgetStatus);
+
+/// A client of a refget server.
+#[derive(Debug, Clone)]
+pub struct RefgetClient {
+    server: String,
+}
+
+impl RefgetClient {
+    /// Create a client of the refget server at the `server` base URL,
+    /// e.g. `https://www.ebi.ac.uk/ena/cram`.
+    pub fn new(server: impl Into<String>) -> Self {
+        Self {
+            server: trim_server(server.into()),
+        }
+    }
+
+    /// Get the base URL of the server.
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+
+    /// Get the metadata of the sequence with the `digest`, such as the MD5.
+    ///
+    /// Returns `None` if the digest is unknown to the server.
+    ///
+    /// ## Errors
+    ///
+    /// * Network error
+    /// * Invalid response
+    pub fn metadata(&self, digest: &str) -> Result<Option<SequenceMetadata>, Box<dyn Error>> {
+        get_json(&format!("{}/sequence/{digest}/metadata", self.server))?
+            .map(|body| parse_metadata(&body))
+            .transpose()
+    }
+
+    /// Check if the sequence with the `digest` is known to the server.
+    ///
+    /// ## Errors
+    ///
+    /// See [`RefgetClient::metadata`].
+    pub fn exists(&self, digest: &str) -> Result<bool, Box<dyn Error>> {
+        Ok(self.metadata(digest)?.is_some())
+    }
+
+    /// Resolve the MD5s of the `build` contigs, skipping the contigs with no MD5.
+    ///
+    /// ## Errors
+    ///
+    /// See [`RefgetClient::metadata`].
+    pub fn verify<'a, C>(
+        &self,
+        build: &'a GenomeBuild<C>,
+    ) -> Result<Vec<Verification<'a, C>>, Box<dyn Error>>
+    where
+        C: ToPrimitive,
+    {
+        let mut statuses = Vec::new();
+        for contig in build.contigs() {
+            if let Some(md5) = contig.md5() {
+                let metadata = self.metadata(md5)?;
+                statuses.push((contig, status(contig, metadata.as_ref())));
+            }
+        }
+        Ok(statuses)
+    }
+}
+
+/// A sequence collection registered at a seqcol server.
+///
+/// The attributes are the level 1 digests of the collection,
+/// `None` if the server does not provide the attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeqCollection {
+    /// The top-level digest of the collection.
+    pub digest: String,
+    /// The digest of the names array.
+    pub names: Option<String>,
+    /// The digest of the lengths array.
+    pub lengths: Option<String>,
+    /// The digest of the sequences array.
+    pub sequences: Option<String>,
+    /// The order-independent digest of the name-length pairs.
+    pub sorted_name_length_pairs: Option<String>,
+}
+
+/// A client of a seqcol server.
+///
+/// ## Example
+///
+/// ```no_run
+/// use dabuild::GenomeBuild;
+/// use dabuild::builds::get_grch38_p13;
+/// use dabuild::refget::SeqColClient;
+///
+/// let build: GenomeBuild<u32> = get_grch38_p13();
+/// let client = SeqColClient::new("https://seqcolapi.databio.org");
+///
+/// for collection in client.find(&build).expect("No network error") {
+///     println!("{}", collection.digest);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SeqColClient {
+    server: String,
+}
+
+impl SeqColClient {
+    /// Create a client of the seqcol server at the `server` base URL.
+    pub fn new(server: impl Into<String>) -> Self {
+        Self {
+            server: trim_server(server.into()),
+        }
+    }
+
+    /// Get the base URL of the server.
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+
+    /// Get the registered collection with the top-level `digest`.
+    ///
+    /// Returns `None` if the digest is unknown to the server.
+    ///
+    /// ## Errors
+    ///
+    /// * Network error
+    /// * Invalid response
+    pub fn collection(&self, digest: &str) -> Result<Option<SeqCollection>, Box<dyn Error>> {
+        get_json(&format!("{}/collection/{digest}?level=1", self.server))?
+            .map(|body| parse_collection(digest, &body))
+            .transpose()
+    }
+
+    /// Find the registered collections with the names and lengths of the `build`,
+    /// see [`GenomeBuild::seqcol_digests`].
+    ///
+    /// ## Errors
+    ///
+    /// * Network error
+    /// * Invalid response
+    pub fn find<C>(&self, build: &GenomeBuild<C>) -> Result<Vec<SeqCollection>, Box<dyn Error>>
+    where
+        C: Display,
+    {
+        let digests = build.seqcol_digests();
+        let url = format!(
+            "{}/list/collection?names={}&lengths={}",
+            self.server, digests.names, digests.lengths
+        );
+        let Some(body) = get_json(&url)? else {
+            return Ok(Vec::new());
+        };
+
+        let mut collections = Vec::new();
+        for digest in parse_collection_list(&body)? {
+            let collection = self
+                .collection(&digest)?
+                .ok_or_else(|| format!("Listed collection {digest} is not found"))?;
+            collections.push(collection);
+        }
+        Ok(collections)
+    }
+}
+
+fn trim_server(mut server: String) -> String {
+    while server.ends_with('/') {
+        server.pop();
+    }
+    server
+}
+
+/// Get the JSON body of the `url`, or `None` if the server responds with 404.
+fn get_json(url: &str) -> Result<Option<String>, Box<dyn Error>> {
+    match ureq::get(url).header("Accept", "application/json").call() {
+        Ok(mut response) => Ok(Some(response.body_mut().read_to_string()?)),
+        Err(ureq::Error::StatusCode(404)) => Ok(None),
+        Err(e) => Err(format!("Cannot download {url}: {e}").into()),
+    }
+}
+
+/// Compare the `contig` with the `metadata` of its sequence.
+fn status<C: ToPrimitive>(contig: &Contig<C>, metadata: Option<&SequenceMetadata>) -> RefgetStatus {
+    match metadata {
+        None => RefgetStatus::NotFound,
+        Some(metadata) => {
+            let expected = contig.length().to_u64().unwrap_or(u64::MAX);
+            if expected == metadata.length {
+                RefgetStatus::Resolved
+            } else {
+                RefgetStatus::LengthMismatch {
+                    expected,
+                    found: metadata.length,
+                }
+            }
+        }
+    }
+}
+
+/// Parse the JSON response of the refget metadata endpoint.
+fn parse_metadata(json: &str) -> Result<SequenceMetadata, Box<dyn Error>> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    let metadata = value
+        .get("metadata")
+        .ok_or("Missing \"metadata\" in the refget response")?;
+
+    let string = |key: &str| {
+        metadata
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .map(String::from)
+    };
+    let length = metadata
+        .get("length")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or("Missing sequence length in the refget response")?;
+    let aliases = metadata
+        .get("aliases")
+        .and_then(serde_json::Value::as_array)
+        .map(|aliases| {
+            aliases
+                .iter()
+                .filter_map(|alias| alias.get("alias").and_then(serde_json::Value::as_str))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(SequenceMetadata {
+        md5: string("md5"),
+        trunc512: string("trunc512"),
+        ga4gh: string("ga4gh"),
+        length,
+        aliases,
+    })
+}
+
+/// Parse the level 1 representation of the collection with the `digest`.
+fn parse_collection(digest: &str, json: &str) -> Result<SeqCollection, Box<dyn Error>> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    if !value.is_object() {
+        return Err("Invalid seqcol collection response".into());
+    }
+    let string = |key: &str| {
+        value
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .map(String::from)
+    };
+
+    Ok(SeqCollection {
+        digest: digest.to_string(),
+        names: string("names"),
+        lengths: string("lengths"),
+        sequences: string("sequences"),
+        sorted_name_length_pairs: string("sorted_name_length_pairs"),
+    })
+}
+
+/// Parse the digests of the `/list/collection` response.
+fn parse_collection_list(json: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    let results = value
+        .get("results")
+        .and_then(serde_json::Value::as_array)
+        .ok_or("Missing \"results\" in the seqcol response")?;
+    results
+        .iter()
+        .map(|digest| {
+            digest
+                .as_str()
+                .map(String::from)
+                .ok_or_else(|| "Invalid collection digest in the seqcol response".into())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_collection, parse_collection_list, parse_metadata, status, RefgetClient,
+        RefgetStatus, SeqColClient,
+    };
+    use crate::Contig;
+
+    const METADATA: &str = r#"{
+        "metadata": {
+            "id": "6aef897c3d6ff0c78aff06ac189178dd",
+            "md5": "6aef897c3d6ff0c78aff06ac189178dd",
+            "trunc512": "2648ae1bacce4ec4b6cf337dcae37816",
+            "ga4gh": "SQ.Ya6Rs7DHhDeg7YaOSX1EP_0BR8-xPrfH",
+            "length": 248956422,
+            "aliases": [
+                {"alias": "chr1", "naming_authority": "ucsc"},
+                {"alias": "NC_000001.11", "naming_authority": "insdc"}
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_metadata() {
+        let metadata = parse_metadata(METADATA).expect("Valid metadata");
+
+        assert_eq!(
+            metadata.md5.as_deref(),
+            Some("6aef897c3d6ff0c78aff06ac189178dd")
+        );
+        assert_eq!(
+            metadata.ga4gh.as_deref(),
+            Some("SQ.Ya6Rs7DHhDeg7YaOSX1EP_0BR8-xPrfH")
+        );
+        assert_eq!(metadata.length, 248_956_422);
+        assert_eq!(metadata.aliases, ["chr1", "NC_000001.11"]);
+
+        assert!(parse_metadata(r#"{"metadata": {"md5": "abc"}}"#).is_err());
+        assert!(parse_metadata("{").is_err());
+    }
+
+    #[test]
+    fn test_status() {
+        let metadata = parse_metadata(METADATA).expect("Valid metadata");
+        let contig = |length: u32| Contig::new("1", &["chr1"], length).expect("Valid contig");
+
+        assert_eq!(
+            status(&contig(248_956_422), Some(&metadata)),
+            RefgetStatus::Resolved
+        );
+        assert_eq!(
+            status(&contig(1_000), Some(&metadata)),
+            RefgetStatus::LengthMismatch {
+                expected: 1_000,
+                found: 248_956_422
+            }
+        );
+        assert_eq!(status(&contig(1_000), None), RefgetStatus::NotFound);
+    }
+
+    #[test]
+    fn test_parse_collection() {
+        let collection = parse_collection(
+            "EiYgJtUfGyad7wf5atL5OG4Fkzohp2qW",
+            r#"{
+                "lengths": "5K4odB173rjao1Cnbk5BnvLt9V7aPAa2",
+                "names": "g04lKdxiYtG3dOGeUC5AdKEifw65G0Wp",
+                "sequences": "EiYgJtUfGyad7wf5atL5OG4Fkzohp2qW"
+            }"#,
+        )
+        .expect("Valid collection");
+
+        assert_eq!(collection.digest, "EiYgJtUfGyad7wf5atL5OG4Fkzohp2qW");
+        assert_eq!(
+            collection.names.as_deref(),
+            Some("g04lKdxiYtG3dOGeUC5AdKEifw65G0Wp")
+        );
+        assert_eq!(collection.sorted_name_length_pairs, None);
+        assert!(parse_collection("x", "[]").is_err());
+
+        let digests = parse_collection_list(
+            r#"{"pagination": {"page": 0, "page_size": 100, "total": 2}, "results": ["a", "b"]}"#,
+        )
+        .expect("Valid list");
+        assert_eq!(digests, ["a", "b"]);
+        assert!(parse_collection_list(r#"{"results": [1]}"#).is_err());
+    }
+
+    #[test]
+    fn test_server() {
+        let client = RefgetClient::new("https://www.ebi.ac.uk/ena/cram/");
+        assert_eq!(client.server(), "https://www.ebi.ac.uk/ena/cram");
+
+        let client = SeqColClient::new("https://seqcolapi.databio.org//");
+        assert_eq!(client.server(), "https://seqcolapi.databio.org");
+    }
+}
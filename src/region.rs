@@ -0,0 +1,799 @@
+//! # Genomic regions
+//!
+//! The module includes [`GenomicRegion`], a contiguous stretch of a [`Contig`],
+//! [`Strand`], the strand of a feature, such as a gene, in a region,
+//! [`RegionSet`], a set of regions, such as an exclusion list,
+//! [`RegionCmp`], the build-order comparator of regions,
+//! and [`RegionKey`], the sort key of a region in the build order.
+//! The regions are parsed from text by [`GenomeBuild::parse_region`],
+//! and split into shards of a similar total length by [`partition_regions`].
+
+use alloc::{collections::BTreeMap, format, string::String, vec, vec::Vec};
+use core::{
+    cmp::{Ordering, Reverse},
+    hash::{Hash, Hasher},
+    ops::Sub,
+    str::FromStr,
+};
+
+use num_traits::{CheckedAdd, CheckedSub, One, ToPrimitive, Zero};
+
+use crate::{Contig, Cytobands, GenomeBuild};
+
+/// A region of a contig.
+///
+/// The region uses 0-based half-open coordinates, e.g. the first 10 bases
+/// of a contig span the region `[0, 10)`.
+///
+/// The region holds a clone of its contig, which is cheap since the contig names are shared.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GenomicRegion<C> {
+    contig: Contig<C>,
+    start: C,
+    end: C,
+}
+
+impl<C> GenomicRegion<C> {
+    /// Create a region on the `contig`.
+    ///
+    /// Returns `None` if `start` is after `end` or if `end` is beyond the contig length.
+    pub fn new(contig: &Contig<C>, start: C, end: C) -> Option<Self>
+    where
+        C: Clone + Zero + PartialOrd,
+    {
+        if C::zero() <= start && start <= end && &end <= contig.length() {
+            Some(GenomicRegion {
+                contig: contig.clone(),
+                start,
+                end,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Get the contig of the region.
+    pub fn contig(&self) -> &Contig<C> {
+        &self.contig
+    }
+
+    /// Get the 0-based start coordinate (included).
+    pub fn start(&self) -> &C {
+        &self.start
+    }
+
+    /// Get the 0-based end coordinate (excluded).
+    pub fn end(&self) -> &C {
+        &self.end
+    }
+}
+
+impl<C> GenomicRegion<C>
+where
+    C: Copy + Zero + PartialOrd + CheckedAdd + CheckedSub,
+{
+    /// Get the `length` bases upstream of the region, e.g. the promoter of a gene,
+    /// with respect to the `strand` of the feature in the region.
+    ///
+    /// The flank is clamped to the contig bounds, hence it is shorter than `length`
+    /// (or even empty) near the contig ends.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{Contig, GenomicRegion, Strand};
+    ///
+    /// let contig = Contig::new("chr1", &["1"], 1_000u32).unwrap();
+    /// let gene = GenomicRegion::new(&contig, 100, 200).unwrap();
+    ///
+    /// let promoter = gene.upstream(150, Strand::Forward);
+    /// assert_eq!((*promoter.start(), *promoter.end()), (0, 100));
+    /// let promoter = gene.upstream(150, Strand::Reverse);
+    /// assert_eq!((*promoter.start(), *promoter.end()), (200, 350));
+    /// ```
+    pub fn upstream(&self, length: C, strand: Strand) -> GenomicRegion<C> {
+        match strand {
+            Strand::Forward => self.before(length),
+            Strand::Reverse => self.after(length),
+        }
+    }
+
+    /// Get the `length` bases downstream of the region,
+    /// with respect to the `strand` of the feature in the region.
+    ///
+    /// The flank is clamped to the contig bounds, as in [`GenomicRegion::upstream`].
+    pub fn downstream(&self, length: C, strand: Strand) -> GenomicRegion<C> {
+        match strand {
+            Strand::Forward => self.after(length),
+            Strand::Reverse => self.before(length),
+        }
+    }
+
+    /// Get up to `length` bases before the start of the region.
+    fn before(&self, length: C) -> GenomicRegion<C> {
+        let start = match self.start.checked_sub(&length) {
+            Some(start) if start > C::zero() => start,
+            _ => C::zero(),
+        };
+        GenomicRegion {
+            contig: self.contig.clone(),
+            start,
+            end: self.start,
+        }
+    }
+
+    /// Get up to `length` bases after the end of the region.
+    fn after(&self, length: C) -> GenomicRegion<C> {
+        let contig_length = *self.contig.length();
+        let end = match self.end.checked_add(&length) {
+            Some(end) if end < contig_length => end,
+            _ => contig_length,
+        };
+        GenomicRegion {
+            contig: self.contig.clone(),
+            start: self.end,
+            end,
+        }
+    }
+}
+
+/// The strand of a feature, such as a gene.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Strand {
+    /// The forward (`+`) strand.
+    #[default]
+    Forward,
+    /// The reverse (`-`) strand.
+    Reverse,
+}
+
+impl Strand {
+    /// Both strands.
+    pub const ALL: [Strand; 2] = [Strand::Forward, Strand::Reverse];
+
+    /// Get the strand symbol, `+` or `-`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Strand::Forward => "+",
+            Strand::Reverse => "-",
+        }
+    }
+}
+
+/// Parse the strand symbol, `+` or `-`, as in the BED or GTF files.
+impl FromStr for Strand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Strand::ALL
+            .into_iter()
+            .find(|strand| strand.as_str() == s)
+            .ok_or_else(|| format!("Unknown strand {s:?}"))
+    }
+}
+
+impl core::fmt::Display for Strand {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A set of regions of the contigs of a genome build, such as an exclusion list.
+///
+/// The overlapping and adjacent regions of a contig are merged,
+/// and the regions are kept sorted by the start coordinate.
+/// The contigs are keyed by the main name.
+///
+/// Use [`crate::formats::parse_exclusion_list`] to load the set from a BED file
+/// or from a list of contig names.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::{Contig, GenomicRegion, RegionSet};
+///
+/// let chr1 = Contig::new("chr1", &["1"], 1_000u32).expect("Valid contig");
+/// let mut set = RegionSet::new();
+/// set.insert(GenomicRegion::new(&chr1, 100, 200).expect("Valid region"));
+/// set.insert(GenomicRegion::new(&chr1, 150, 300).expect("Valid region"));
+///
+/// let query = GenomicRegion::new(&chr1, 0, 400).expect("Valid region");
+/// assert!(set.overlaps(&query));
+/// let unmasked: Vec<_> = set.mask(&query).iter().map(|r| (*r.start(), *r.end())).collect();
+/// assert_eq!(unmasked, [(0, 100), (300, 400)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionSet<C> {
+    regions: BTreeMap<String, Vec<GenomicRegion<C>>>,
+}
+
+impl<C> RegionSet<C> {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        RegionSet {
+            regions: BTreeMap::new(),
+        }
+    }
+
+    /// Get the number of the (merged) regions.
+    pub fn len(&self) -> usize {
+        self.regions.values().map(Vec::len).sum()
+    }
+
+    /// Test if the set includes no regions.
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Iterate over the regions, grouped by contig and sorted by the start coordinate.
+    pub fn regions(&self) -> impl Iterator<Item = &GenomicRegion<C>> {
+        self.regions.values().flatten()
+    }
+
+    /// Get the regions of the `contig`, sorted by the start coordinate.
+    pub fn contig_regions(&self, contig: &Contig<C>) -> &[GenomicRegion<C>] {
+        self.regions
+            .get(contig.name())
+            .map_or(&[], |regions| regions.as_slice())
+    }
+
+    /// Get the regions grouped by contig, one non-empty slice per contig,
+    /// with the contigs in the order of the `build`, e.g. to process the contigs in parallel.
+    ///
+    /// The contigs unknown to the build come last, sorted by the name.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{GenomeBuild, GenomicRegion, RegionSet};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let region = |name, start, end| {
+    ///     GenomicRegion::new(build.contig_by_name(name).unwrap(), start, end).unwrap()
+    /// };
+    /// let set: RegionSet<u32> = [region("chr2", 0, 10), region("chr10", 0, 10), region("chr2", 20, 30)]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let chunks: Vec<_> = set.by_contig(&build).iter().map(|c| (c[0].contig().name(), c.len())).collect();
+    /// assert_eq!(chunks, [("10", 1), ("2", 2)]);
+    /// ```
+    pub fn by_contig(&self, build: &GenomeBuild<C>) -> Vec<&[GenomicRegion<C>]> {
+        let cmp = build.region_cmp();
+        let mut chunks: Vec<_> = self
+            .regions
+            .iter()
+            .filter(|(_, regions)| !regions.is_empty())
+            .collect();
+        chunks.sort_by(|(l, _), (r, _)| cmp.compare_contigs(l, r));
+        chunks
+            .into_iter()
+            .map(|(_, regions)| regions.as_slice())
+            .collect()
+    }
+}
+
+impl<C> RegionSet<C>
+where
+    C: Copy + Zero + PartialOrd,
+{
+    /// Add the `region` into the set, merging it with the overlapping or adjacent regions.
+    pub fn insert(&mut self, region: GenomicRegion<C>) {
+        let regions = self
+            .regions
+            .entry(String::from(region.contig.name()))
+            .or_default();
+
+        let (mut start, mut end) = (region.start, region.end);
+        let first = regions.partition_point(|r| r.end < start);
+        let mut last = first;
+        while last < regions.len() && regions[last].start <= end {
+            if regions[last].start < start {
+                start = regions[last].start;
+            }
+            if regions[last].end > end {
+                end = regions[last].end;
+            }
+            last += 1;
+        }
+
+        let merged = GenomicRegion {
+            contig: region.contig,
+            start,
+            end,
+        };
+        regions.splice(first..last, [merged]);
+    }
+
+    /// Add the whole `contig` into the set.
+    pub fn insert_contig(&mut self, contig: &Contig<C>) {
+        self.insert(GenomicRegion {
+            contig: contig.clone(),
+            start: C::zero(),
+            end: *contig.length(),
+        });
+    }
+
+    /// Test if the whole `contig` is in the set.
+    pub fn is_excluded(&self, contig: &Contig<C>) -> bool {
+        self.contig_regions(contig)
+            .first()
+            .is_some_and(|region| region.start <= C::zero() && &region.end >= contig.length())
+    }
+
+    /// Test if the `region` overlaps any region of the set.
+    pub fn overlaps(&self, region: &GenomicRegion<C>) -> bool {
+        let regions = self.contig_regions(&region.contig);
+        let i = regions.partition_point(|r| r.end <= region.start);
+        regions.get(i).is_some_and(|r| r.start < region.end)
+    }
+
+    /// Get the parts of the `region` that do not overlap the set, sorted by the start coordinate.
+    pub fn mask(&self, region: &GenomicRegion<C>) -> Vec<GenomicRegion<C>> {
+        let regions = self.contig_regions(&region.contig);
+        let mut unmasked = Vec::new();
+        let mut start = region.start;
+        for masked in regions[regions.partition_point(|r| r.end <= region.start)..]
+            .iter()
+            .take_while(|r| r.start < region.end)
+        {
+            if start < masked.start {
+                unmasked.push(GenomicRegion {
+                    contig: region.contig.clone(),
+                    start,
+                    end: masked.start,
+                });
+            }
+            if masked.end > start {
+                start = masked.end;
+            }
+        }
+        if start < region.end {
+            unmasked.push(GenomicRegion {
+                contig: region.contig.clone(),
+                start,
+                end: region.end,
+            });
+        }
+        unmasked
+    }
+}
+
+impl<C> Default for RegionSet<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> FromIterator<GenomicRegion<C>> for RegionSet<C>
+where
+    C: Copy + Zero + PartialOrd,
+{
+    fn from_iter<I: IntoIterator<Item = GenomicRegion<C>>>(iter: I) -> Self {
+        let mut set = RegionSet::new();
+        for region in iter {
+            set.insert(region);
+        }
+        set
+    }
+}
+
+/// The comparator of regions in the build order:
+/// by the contig position in the build, then by the start, and then by the end.
+///
+/// The contigs are looked up by any of their names.
+/// The regions on the contigs unknown to the build come last, sorted by the contig name.
+///
+/// The comparator works with any region type, hence external containers and sorters
+/// can order their regions consistently with the build, see [`GenomeBuild::region_cmp`].
+#[derive(Debug)]
+pub struct RegionCmp<'a, C> {
+    build: &'a GenomeBuild<C>,
+}
+
+impl<C> Clone for RegionCmp<'_, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for RegionCmp<'_, C> {}
+
+impl<C> RegionCmp<'_, C> {
+    /// Get the position of the `contig` in the build order, or `None` if the contig is unknown.
+    ///
+    /// The position can be used as a part of a sort key, e.g. of a [`BTreeMap`].
+    pub fn rank(&self, contig: &str) -> Option<usize> {
+        self.build.contig_index_by_name(contig)
+    }
+
+    /// Compare two contigs by the position in the build order.
+    pub fn compare_contigs(&self, left: &str, right: &str) -> Ordering {
+        match (self.rank(left), self.rank(right)) {
+            (Some(l), Some(r)) => l.cmp(&r),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => left.cmp(right),
+        }
+    }
+
+    /// Compare two regions given as the contig name, the start, and the end.
+    pub fn compare<P>(&self, left: (&str, P, P), right: (&str, P, P)) -> Ordering
+    where
+        P: Ord,
+    {
+        self.compare_contigs(left.0, right.0)
+            .then_with(|| left.1.cmp(&right.1))
+            .then_with(|| left.2.cmp(&right.2))
+    }
+
+    /// Compare two regions of the build.
+    pub fn compare_regions(&self, left: &GenomicRegion<C>, right: &GenomicRegion<C>) -> Ordering
+    where
+        C: Ord,
+    {
+        self.compare(
+            (left.contig.name(), &left.start, &left.end),
+            (right.contig.name(), &right.start, &right.end),
+        )
+    }
+}
+
+impl<C> GenomicRegion<C> {
+    /// Compare the region with the `other` region in the order of the `build`:
+    /// by the contig position in the build, then by the start, and then by the end.
+    ///
+    /// See [`RegionCmp`] for the order of the contigs unknown to the build.
+    pub fn cmp_in(&self, other: &GenomicRegion<C>, build: &GenomeBuild<C>) -> Ordering
+    where
+        C: Ord,
+    {
+        build.region_cmp().compare_regions(self, other)
+    }
+
+    /// Get the key that orders the region in the order of the `build`, as [`GenomicRegion::cmp_in`],
+    /// e.g. to keep the regions in a [`BTreeMap`] or a [`alloc::collections::BinaryHeap`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::collections::BTreeSet;
+    /// use dabuild::{GenomeBuild, GenomicRegion};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let region = |name, start, end| {
+    ///     GenomicRegion::new(build.contig_by_name(name).unwrap(), start, end).unwrap()
+    /// };
+    ///
+    /// let keys: BTreeSet<_> = [region("chr2", 5, 10), region("chr10", 0, 10), region("chr2", 0, 10)]
+    ///     .into_iter()
+    ///     .map(|r| r.key_in(&build))
+    ///     .collect();
+    ///
+    /// let sorted: Vec<_> = keys.iter().map(|k| (k.region().contig().name(), *k.region().start())).collect();
+    /// // The bundled builds are in the lexicographic order of the main names.
+    /// assert_eq!(sorted, [("10", 0), ("2", 0), ("2", 5)]);
+    /// ```
+    pub fn key_in(self, build: &GenomeBuild<C>) -> RegionKey<C> {
+        let rank = build
+            .contig_index_by_name(self.contig.name())
+            .unwrap_or(usize::MAX);
+        RegionKey { rank, region: self }
+    }
+}
+
+/// The key of a region that orders the regions in the order of a genome build,
+/// see [`GenomicRegion::key_in`].
+///
+/// The keys are compared by the contig position in the build, then by the contig name,
+/// which orders the contigs unknown to the build, then by the start, and then by the end.
+#[derive(Debug, Clone)]
+pub struct RegionKey<C> {
+    rank: usize,
+    region: GenomicRegion<C>,
+}
+
+impl<C> RegionKey<C> {
+    /// Get the position of the contig in the build, or `None` if the contig is unknown.
+    pub fn rank(&self) -> Option<usize> {
+        (self.rank != usize::MAX).then_some(self.rank)
+    }
+
+    /// Get the region.
+    pub fn region(&self) -> &GenomicRegion<C> {
+        &self.region
+    }
+
+    /// Get the region, consuming the key.
+    pub fn into_region(self) -> GenomicRegion<C> {
+        self.region
+    }
+}
+
+impl<C: Ord> Ord for RegionKey<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank
+            .cmp(&other.rank)
+            .then_with(|| self.region.contig.name().cmp(other.region.contig.name()))
+            .then_with(|| self.region.start.cmp(&other.region.start))
+            .then_with(|| self.region.end.cmp(&other.region.end))
+    }
+}
+
+impl<C: Ord> PartialOrd for RegionKey<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord> PartialEq for RegionKey<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: Ord> Eq for RegionKey<C> {}
+
+impl<C: Hash> Hash for RegionKey<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank.hash(state);
+        self.region.contig.name().hash(state);
+        self.region.start.hash(state);
+        self.region.end.hash(state);
+    }
+}
+
+impl<C> GenomeBuild<C> {
+    /// Get the comparator for sorting regions in the build order.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let cmp = build.region_cmp();
+    ///
+    /// // The records of an external type.
+    /// struct Record { chrom: &'static str, pos: u64 }
+    /// let mut records = vec![
+    ///     Record { chrom: "chr2", pos: 10 },
+    ///     Record { chrom: "HLA-A*01:01:01:01", pos: 5 },
+    ///     Record { chrom: "chr10", pos: 20 },
+    ///     Record { chrom: "chr2", pos: 5 },
+    /// ];
+    /// records.sort_by(|l, r| cmp.compare((l.chrom, l.pos, l.pos), (r.chrom, r.pos, r.pos)));
+    ///
+    /// let sorted: Vec<_> = records.iter().map(|r| (r.chrom, r.pos)).collect();
+    /// // The bundled builds are in the lexicographic order of the main names.
+    /// assert_eq!(
+    ///     sorted,
+    ///     [("chr10", 20), ("chr2", 5), ("chr2", 10), ("HLA-A*01:01:01:01", 5)],
+    /// );
+    /// ```
+    pub fn region_cmp(&self) -> RegionCmp<'_, C> {
+        RegionCmp { build: self }
+    }
+}
+
+impl<C> GenomeBuild<C>
+where
+    C: Copy + FromStr + Zero + One + PartialOrd + Sub<Output = C>,
+{
+    /// Parse a region in the samtools notation, with 1-based closed coordinates:
+    ///
+    /// * `chr1` - the whole contig
+    /// * `chr1:1,000` - from the 1,000th base to the contig end
+    /// * `chr1:1,000-2,000` - from the 1,000th to the 2,000th base
+    ///
+    /// The contig is looked up by any of its names, and the thousands separators are ignored.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    ///
+    /// let region = build.parse_region("chr1:1,001-2,000").expect("Valid region");
+    /// assert_eq!(region.contig().name(), "1");
+    /// assert_eq!((*region.start(), *region.end()), (1_000, 2_000));
+    ///
+    /// let region = build.parse_region("chrX:100,000,001").expect("Valid region");
+    /// assert_eq!(region.end(), region.contig().length());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// If the contig is not in the build, or if the coordinates are unparsable,
+    /// reversed, or beyond the contig end.
+    pub fn parse_region(&self, text: &str) -> Result<GenomicRegion<C>, String> {
+        let text = text.trim();
+        // The contig names may include a colon, e.g. `HLA-A*01:01:01:01`.
+        if let Some(contig) = self.contig_by_name(text) {
+            return Ok(whole_contig(contig));
+        }
+        let (name, range) = text
+            .rsplit_once(':')
+            .ok_or_else(|| format!("Unknown contig in region {text:?}"))?;
+        let contig = self
+            .contig_by_name(name)
+            .ok_or_else(|| format!("Unknown contig {name:?} in region {text:?}"))?;
+        let coordinate = |value: &str| value.replace(',', "").trim().parse::<C>().ok();
+        let (start, end) = match range.split_once('-') {
+            Some((start, end)) => (coordinate(start), coordinate(end)),
+            None => (coordinate(range), Some(*contig.length())),
+        };
+        match (start, end) {
+            (Some(start), Some(end)) if start > C::zero() => {
+                GenomicRegion::new(contig, start - C::one(), end)
+            }
+            _ => None,
+        }
+        .ok_or_else(|| format!("Invalid coordinates in region {text:?}"))
+    }
+
+    /// Parse a region in the samtools notation (see [`GenomeBuild::parse_region`])
+    /// or in the cytogenetic band notation, such as `1q21.1` or `17p`,
+    /// resolved to the coordinates of the `cytobands`.
+    ///
+    /// A band includes its sub-bands, e.g. `1q21` spans the bands from `1q21.1` to `1q21.3`,
+    /// and an arm, such as `17p`, spans all its bands.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    /// use dabuild::formats::parse_cytobands;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let bands = "chr17\t0\t3400000\tp13.3\tgneg\nchr17\t3400000\t6500000\tp13.2\tgpos50\n\
+    ///     chr17\t6500000\t10800000\tp13.1\tgneg\nchr17\t10800000\t16100000\tp12\tgpos75\n";
+    /// let cytobands = parse_cytobands(&build, bands.as_bytes()).expect("Valid cytobands");
+    ///
+    /// let region = build.parse_region_with_bands("17p13", &cytobands).expect("Valid band");
+    /// assert_eq!((*region.start(), *region.end()), (0, 10_800_000));
+    /// let region = build.parse_region_with_bands("chr17:1-100", &cytobands).expect("Valid region");
+    /// assert_eq!(*region.end(), 100);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// If the text is neither a band nor a valid region in the samtools notation.
+    pub fn parse_region_with_bands(
+        &self,
+        text: &str,
+        cytobands: &Cytobands<C>,
+    ) -> Result<GenomicRegion<C>, String> {
+        let text = text.trim();
+        match cytobands.resolve(self, text) {
+            Some(region) => Ok(region),
+            None => self
+                .parse_region(text)
+                .map_err(|_| format!("Neither a band nor a region {text:?}")),
+        }
+    }
+}
+
+impl<C> GenomeBuild<C>
+where
+    C: Copy + Zero + ToPrimitive,
+{
+    /// Split the contigs into `n` groups of a similar total length,
+    /// e.g. to shard a scatter-gather pipeline.
+    ///
+    /// Each group includes the regions spanning its contigs, in the build order.
+    /// See [`partition_regions`] to split the windows of the contigs instead.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let groups = build.partition(4);
+    ///
+    /// let totals: Vec<u64> = groups
+    ///     .iter()
+    ///     .map(|group| group.iter().map(|r| u64::from(r.end() - r.start())).sum())
+    ///     .collect();
+    /// assert!(totals.iter().all(|&total| total > 700_000_000 && total < 850_000_000));
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// If `n` is zero.
+    pub fn partition(&self, n: usize) -> Vec<Vec<GenomicRegion<C>>> {
+        partition_regions(self.contigs().map(whole_contig), n)
+    }
+}
+
+/// Split the `regions` into `n` groups of a similar total length,
+/// e.g. the windows of [`GenomeBuild::windows`] to shard a scatter-gather pipeline.
+///
+/// The longest regions are placed first, each into the group with the least total length.
+/// The regions of a group keep their order in the `regions`,
+/// and some groups are empty if there are fewer than `n` regions.
+///
+/// ## Panics
+///
+/// If `n` is zero.
+pub fn partition_regions<C, I>(regions: I, n: usize) -> Vec<Vec<GenomicRegion<C>>>
+where
+    C: ToPrimitive,
+    I: IntoIterator<Item = GenomicRegion<C>>,
+{
+    assert!(n > 0, "Number of groups must be positive");
+    let length = |region: &GenomicRegion<C>| match (region.start.to_u64(), region.end.to_u64()) {
+        (Some(start), Some(end)) => end.saturating_sub(start),
+        _ => 0,
+    };
+    let mut regions: Vec<_> = regions
+        .into_iter()
+        .enumerate()
+        .map(|(i, region)| (length(&region), i, region))
+        .collect();
+    regions.sort_by_key(|&(length, i, _)| (Reverse(length), i));
+
+    let mut totals = vec![0; n];
+    let mut groups: Vec<Vec<_>> = (0..n).map(|_| Vec::new()).collect();
+    for (length, i, region) in regions {
+        let (g, _) = totals
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, total)| total)
+            .expect("At least one group");
+        totals[g] += length;
+        groups[g].push((i, region));
+    }
+
+    groups
+        .into_iter()
+        .map(|mut group| {
+            group.sort_by_key(|&(i, _)| i);
+            group.into_iter().map(|(_, region)| region).collect()
+        })
+        .collect()
+}
+
+/// Get the region that spans the whole `contig`.
+pub(crate) fn whole_contig<C: Copy + Zero>(contig: &Contig<C>) -> GenomicRegion<C> {
+    GenomicRegion {
+        contig: contig.clone(),
+        start: C::zero(),
+        end: *contig.length(),
+    }
+}
+
+/// Tile the `contig` with windows of `size` bases, starting each `step` bases.
+///
+/// The last window is truncated at the contig end.
+pub(crate) fn contig_windows<C>(
+    contig: &Contig<C>,
+    size: C,
+    step: C,
+) -> impl Iterator<Item = GenomicRegion<C>> + '_
+where
+    C: Copy + Zero + PartialOrd + CheckedAdd,
+{
+    let length = *contig.length();
+    core::iter::successors(Some(C::zero()), move |start| start.checked_add(&step))
+        .take_while(move |&start| start < length)
+        .map(move |start| {
+            let end = match start.checked_add(&size) {
+                Some(end) if end < length => end,
+                _ => length,
+            };
+            GenomicRegion {
+                contig: contig.clone(),
+                start,
+                end,
+            }
+        })
+}
@@ -0,0 +1,135 @@
+//! # Spike-in and control contigs
+//!
+//! The module includes [`SpikeIn`], the common spike-in and control sequences
+//! added to a reference genome, see [`GenomeBuild::with_spike_ins`].
+
+use alloc::{format, string::String};
+use core::str::FromStr;
+
+use num_traits::{FromPrimitive, Zero};
+
+use crate::{ConflictPolicy, Contig, ContigOrdering, GenomeBuild, IndexKind};
+
+/// A common spike-in or control sequence with a well-known length.
+///
+/// The ERCC and SIRV spike-in mixes are not included, as their sequences depend
+/// on the vendor release. Add them from the FASTA index of the vendor,
+/// e.g. parsed by [`crate::formats::parse_fai`], using [`GenomeBuild::with_spike_ins`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SpikeIn {
+    /// The Illumina sequencing control, the phiX174 bacteriophage genome (`NC_001422.1`).
+    PhiX,
+    /// The Epstein-Barr virus genome (`chrEBV`), a decoy of the GRCh38 analysis sets.
+    Ebv,
+}
+
+impl SpikeIn {
+    /// All spike-ins.
+    pub const ALL: [SpikeIn; 2] = [SpikeIn::PhiX, SpikeIn::Ebv];
+
+    /// Get the spike-in name, e.g. `phix`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpikeIn::PhiX => "phix",
+            SpikeIn::Ebv => "ebv",
+        }
+    }
+
+    /// Get the main contig name, e.g. `phiX174`.
+    pub fn contig_name(&self) -> &'static str {
+        match self {
+            SpikeIn::PhiX => "phiX174",
+            SpikeIn::Ebv => "chrEBV",
+        }
+    }
+
+    /// Get the alternative contig names, the GenBank and the RefSeq accessions.
+    pub fn alt_names(&self) -> &'static [&'static str] {
+        match self {
+            SpikeIn::PhiX => &["J02482.1", "NC_001422.1"],
+            SpikeIn::Ebv => &["AJ507799.2", "NC_007605.1"],
+        }
+    }
+
+    /// Get the sequence length.
+    pub fn length(&self) -> u64 {
+        match self {
+            SpikeIn::PhiX => 5_386,
+            SpikeIn::Ebv => 171_823,
+        }
+    }
+
+    /// Get the contig of the spike-in, or `None` if `C` cannot represent its length.
+    pub fn contig<C>(&self) -> Option<Contig<C>>
+    where
+        C: FromPrimitive + Zero + PartialOrd,
+    {
+        Contig::new(
+            self.contig_name(),
+            self.alt_names(),
+            C::from_u64(self.length())?,
+        )
+    }
+}
+
+/// Parse the spike-in name, e.g. `phix`, ignoring the case.
+impl FromStr for SpikeIn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SpikeIn::ALL
+            .into_iter()
+            .find(|spike_in| spike_in.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown spike-in {s:?}"))
+    }
+}
+
+impl core::fmt::Display for SpikeIn {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<C> GenomeBuild<C>
+where
+    C: Clone,
+{
+    /// Get a new build with the spike-in or control `contigs` appended,
+    /// e.g. the contigs of [`SpikeIn`] or custom contigs.
+    ///
+    /// The new build keeps the identifier of this build. See [`GenomeBuild::merge`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{Contig, GenomeBuild, SpikeIn};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let lambda = Contig::new("lambda", &["NC_001416.1"], 48_502).unwrap();
+    ///
+    /// let merged = build
+    ///     .with_spike_ins([SpikeIn::PhiX.contig().unwrap(), lambda])
+    ///     .expect("No clashes");
+    ///
+    /// assert_eq!(merged.contigs().count(), 642);
+    /// assert_eq!(merged.contig_by_name("NC_001422.1").map(|c| *c.length()), Some(5_386));
+    /// assert!(merged.with_spike_ins(SpikeIn::PhiX.contig()).is_err());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// If a name of the spike-ins is already in the build.
+    pub fn with_spike_ins<I>(&self, contigs: I) -> Result<GenomeBuild<C>, String>
+    where
+        I: IntoIterator<Item = Contig<C>>,
+    {
+        let spike_ins = GenomeBuild::assemble(
+            self.id().clone(),
+            contigs,
+            ContigOrdering::Insertion,
+            IndexKind::default(),
+        );
+        self.merge(&spike_ins, ConflictPolicy::Error)
+    }
+}
@@ -0,0 +1,131 @@
+//! # Length statistics
+//!
+//! The module includes the length distribution of the contigs of a genome build,
+//! see [`GenomeBuild::length_histogram`] and [`GenomeBuild::cumulative_lengths`],
+//! e.g. to compare the assemblies in a plot.
+
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+use num_traits::ToPrimitive;
+
+use crate::GenomeBuild;
+
+/// A bin of a contig length histogram, see [`GenomeBuild::length_histogram`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LengthBin {
+    start: u64,
+    end: u64,
+    count: usize,
+    total: u64,
+}
+
+impl LengthBin {
+    /// Get the least length of the bin.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Get the length past the greatest length of the bin.
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Get the number of the contigs in the bin.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get the total length of the contigs in the bin.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+impl<C> GenomeBuild<C>
+where
+    C: ToPrimitive,
+{
+    /// Get the histogram of the contig lengths with `bins` bins of the same width,
+    /// spanning the lengths from zero to the greatest contig length.
+    ///
+    /// Returns no bins if the build has no contigs.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let histogram = build.length_histogram(5);
+    ///
+    /// assert_eq!(histogram.len(), 5);
+    /// assert_eq!(histogram.iter().map(|bin| bin.count()).sum::<usize>(), build.contigs().count());
+    /// // Chromosomes 1 and 2 are in the last bin.
+    /// assert_eq!(histogram[4].count(), 2);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// If `bins` is zero.
+    pub fn length_histogram(&self, bins: usize) -> Vec<LengthBin> {
+        assert!(bins > 0, "Number of bins must be positive");
+        let lengths: Vec<_> = self.contigs().map(|c| length(c.length())).collect();
+        let Some(&max) = lengths.iter().max() else {
+            return Vec::new();
+        };
+
+        let width = (max / bins as u64 + 1).max(1);
+        let mut histogram: Vec<_> = (0..bins as u64)
+            .map(|i| LengthBin {
+                start: i * width,
+                end: (i + 1) * width,
+                count: 0,
+                total: 0,
+            })
+            .collect();
+        for length in lengths {
+            let bin = &mut histogram[((length / width) as usize).min(bins - 1)];
+            bin.count += 1;
+            bin.total += length;
+        }
+        histogram
+    }
+
+    /// Get the cumulative lengths of the contigs sorted from the longest,
+    /// e.g. to plot the Nx curve of the assembly.
+    ///
+    /// The N50 is the length of the first contig whose cumulative length reaches
+    /// a half of the total length.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::GenomeBuild;
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u32> = get_grch38_p13();
+    /// let cumulative = build.cumulative_lengths();
+    ///
+    /// let total = *cumulative.last().expect("Non-empty build");
+    /// let l50 = cumulative.partition_point(|&sum| 2 * sum < total);
+    /// let n50 = cumulative[l50] - l50.checked_sub(1).map_or(0, |i| cumulative[i]);
+    /// assert_eq!(n50, 145_138_636); // Chromosome 8.
+    /// ```
+    pub fn cumulative_lengths(&self) -> Vec<u64> {
+        let mut lengths: Vec<_> = self.contigs().map(|c| length(c.length())).collect();
+        lengths.sort_unstable_by_key(|&length| Reverse(length));
+        let mut sum = 0;
+        for length in lengths.iter_mut() {
+            sum += *length;
+            *length = sum;
+        }
+        lengths
+    }
+}
+
+/// Get the `length` as `u64`, or zero if it does not fit.
+fn length<C: ToPrimitive>(length: &C) -> u64 {
+    length.to_u64().unwrap_or(0)
+}
@@ -0,0 +1,213 @@
+//! # Property-based testing
+//!
+//! The module provides [proptest](https://docs.rs/proptest) strategies that generate valid
+//! contigs, genome builds, and genomic regions, for property-testing the genome coordinate logic
+//! of the downstream crates. The module is available with the `testing` feature.
+//!
+//! The generated values uphold the invariants of the crate:
+//!
+//! * the contig names are non-empty, and unique within a build
+//! * the contigs are at least 1 base long
+//! * the regions are within the bounds of their contig
+//!
+//! [`Contig`] and [`GenomeBuild`] implement [`Arbitrary`] with the default parameters
+//! of [`contig`] and [`genome_build`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use dabuild::GenomeBuild;
+//! use dabuild::testing::{genome_build, region_in};
+//! use proptest::prelude::*;
+//!
+//! proptest!(|((build, region) in genome_build::<u32>(5, 1_000)
+//!     .prop_flat_map(|build| (Just(build.clone()), region_in(build))))| {
+//!     let contig = build.contig_by_name(region.contig().name()).unwrap();
+//!     prop_assert!(region.end() <= contig.length());
+//! });
+//! ```
+
+use std::fmt::Debug;
+
+use num_traits::{FromPrimitive, ToPrimitive, Zero};
+use proptest::{
+    arbitrary::Arbitrary,
+    collection::vec,
+    sample::select,
+    strategy::{BoxedStrategy, Just, Strategy},
+};
+
+use crate::{Contig, GenomeBuild, GenomeBuildIdentifier, GenomicRegion};
+
+/// The naming styles of the generated names, e.g. `chr3` or `NC_000003.1`.
+const PREFIXES: [&str; 4] = ["", "chr", "NC_", "scaffold_"];
+
+/// Get a strategy for a contig with up to three alternative names
+/// and the length between 1 and `max_length` bases.
+///
+/// ## Panics
+///
+/// If `max_length` is zero or `C` cannot represent it.
+pub fn contig<C>(max_length: u32) -> impl Strategy<Value = Contig<C>>
+where
+    C: FromPrimitive + Zero + PartialOrd + Debug,
+{
+    let max_length = length_of::<C>(max_length);
+    (
+        "[A-Za-z0-9_.]{1,16}",
+        vec("[A-Za-z0-9_.]{1,16}", 0..=3),
+        1..=max_length,
+    )
+        .prop_map(|(name, alt_names, length)| {
+            Contig::new(
+                name,
+                &alt_names,
+                C::from_u32(length).expect("Checked length"),
+            )
+            .expect("Positive length")
+        })
+}
+
+/// Get a strategy for a genome build with 1 to `max_contigs` contigs
+/// that are between 1 and `max_length` bases long.
+///
+/// The contig names follow several naming styles, and they are unique within the build,
+/// hence each name resolves to a single contig.
+///
+/// ## Panics
+///
+/// If `max_contigs` or `max_length` is zero, or if `C` cannot represent `max_length`.
+pub fn genome_build<C>(max_contigs: usize, max_length: u32) -> impl Strategy<Value = GenomeBuild<C>>
+where
+    C: FromPrimitive + Zero + PartialOrd + Debug,
+{
+    assert!(
+        max_contigs > 0,
+        "Genome build must have at least one contig"
+    );
+    let max_length = length_of::<C>(max_length);
+    vec(
+        (select(&PREFIXES[..]), 0..=3usize, 1..=max_length),
+        1..=max_contigs,
+    )
+    .prop_map(|contigs| {
+        let id = GenomeBuildIdentifier::from(("Toy", "p1"));
+        GenomeBuild::new(
+            id,
+            contigs
+                .into_iter()
+                .enumerate()
+                .map(|(i, (prefix, n_alt_names, length))| {
+                    // The position makes the names unique.
+                    let names = unique_names(i + 1);
+                    let name = String::from(prefix) + &names[0];
+                    let alt_names = &names[1..=n_alt_names];
+                    Contig::new(
+                        name,
+                        alt_names,
+                        C::from_u32(length).expect("Checked length"),
+                    )
+                    .expect("Positive length")
+                }),
+        )
+    })
+}
+
+/// Get a strategy for a region on a contig of the `build`.
+///
+/// The regions may be empty, and they may span the entire contig.
+pub fn region_in<C>(build: GenomeBuild<C>) -> impl Strategy<Value = GenomicRegion<C>>
+where
+    C: Clone + FromPrimitive + ToPrimitive + Zero + PartialOrd + Debug + 'static,
+{
+    let contigs: Vec<_> = build.contigs().cloned().collect();
+    select(contigs).prop_flat_map(|contig| {
+        let length = contig
+            .length()
+            .to_u64()
+            .expect("Length should fit into u64");
+        (Just(contig), 0..=length).prop_flat_map(move |(contig, start)| {
+            (Just(contig), Just(start), start..=length).prop_map(|(contig, start, end)| {
+                let (start, end) = (
+                    C::from_u64(start).expect("Start within contig"),
+                    C::from_u64(end).expect("End within contig"),
+                );
+                GenomicRegion::new(&contig, start, end).expect("Region within contig")
+            })
+        })
+    })
+}
+
+/// Get the main name followed by the GenBank-like, RefSeq-like, and UCSC-style names
+/// of the `n`th contig.
+fn unique_names(n: usize) -> [String; 4] {
+    [
+        format!("{n}"),
+        format!("CM{n:06}.1"),
+        format!("NC_{n:06}.1"),
+        format!("chr{n}_toy"),
+    ]
+}
+
+fn length_of<C: FromPrimitive>(max_length: u32) -> u32 {
+    assert!(max_length > 0, "Contig must be at least 1 base long");
+    assert!(
+        C::from_u32(max_length).is_some(),
+        "Maximum length {max_length} should fit into the coordinate type"
+    );
+    max_length
+}
+
+/// Generate contigs that are up to 1 Mb long.
+impl<C> Arbitrary for Contig<C>
+where
+    C: FromPrimitive + Zero + PartialOrd + Debug + 'static,
+{
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        contig(1_000_000).boxed()
+    }
+}
+
+/// Generate builds with up to 25 contigs that are up to 1 Mb long.
+impl<C> Arbitrary for GenomeBuild<C>
+where
+    C: FromPrimitive + Zero + PartialOrd + Debug + 'static,
+{
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        genome_build(25, 1_000_000).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{genome_build, region_in};
+    use crate::GenomeBuild;
+
+    proptest! {
+        #[test]
+        fn test_names_resolve_to_their_contig(build in any::<GenomeBuild<u32>>()) {
+            for (i, contig) in build.contigs().enumerate() {
+                prop_assert!(*contig.length() > 0);
+                for name in contig.names() {
+                    prop_assert_eq!(build.contig_index_by_name(name), Some(i));
+                }
+            }
+        }
+
+        #[test]
+        fn test_regions_within_contig(
+            region in genome_build::<u64>(3, 50).prop_flat_map(region_in),
+        ) {
+            prop_assert!(region.start() <= region.end());
+            prop_assert!(region.end() <= region.contig().length());
+        }
+    }
+}
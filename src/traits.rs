@@ -0,0 +1,72 @@
+//! # Traits
+//!
+//! The traits capture the read API of the contigs and genome builds.
+//! They allow accepting alternative data stores (e.g. caches or database-backed builds)
+//! behind the same interface as the concrete [`Contig`] and [`GenomeBuild`].
+//!
+//! The traits are object-safe.
+
+use alloc::boxed::Box;
+
+use crate::{Contig, GenomeBuild, GenomeBuildIdentifier};
+
+/// A read-only view of a contig.
+///
+/// `C` is the data type to represent the number of contig's base pairs.
+pub trait ContigLike<C> {
+    /// Get the main name of the contig (e.g. `10`, `X`, `MT`).
+    fn name(&self) -> &str;
+
+    /// Get the alternative contig identifiers.
+    fn alt_names(&self) -> Box<dyn Iterator<Item = &str> + '_>;
+
+    /// Get the number of bases of the contig.
+    fn length(&self) -> &C;
+}
+
+/// A read-only view of a genome build.
+///
+/// `C` is the data type to represent the number of contig's base pairs.
+pub trait GenomeBuildLike<C> {
+    /// Get the genome build identifiers.
+    fn id(&self) -> &GenomeBuildIdentifier;
+
+    /// Get an iterator with all contigs.
+    fn contigs(&self) -> Box<dyn Iterator<Item = &dyn ContigLike<C>> + '_>;
+
+    /// Get a contig by its main or alternative name.
+    ///
+    /// The default implementation scans all [`GenomeBuildLike::contigs`].
+    fn contig_by_name(&self, name: &str) -> Option<&dyn ContigLike<C>> {
+        self.contigs()
+            .find(|c| c.name().eq(name) || c.alt_names().any(|alt_name| alt_name.eq(name)))
+    }
+}
+
+impl<C> ContigLike<C> for Contig<C> {
+    fn name(&self) -> &str {
+        Contig::name(self)
+    }
+
+    fn alt_names(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(Contig::alt_names(self))
+    }
+
+    fn length(&self) -> &C {
+        Contig::length(self)
+    }
+}
+
+impl<C> GenomeBuildLike<C> for GenomeBuild<C> {
+    fn id(&self) -> &GenomeBuildIdentifier {
+        GenomeBuild::id(self)
+    }
+
+    fn contigs(&self) -> Box<dyn Iterator<Item = &dyn ContigLike<C>> + '_> {
+        Box::new(GenomeBuild::contigs(self).map(|c| c as &dyn ContigLike<C>))
+    }
+
+    fn contig_by_name(&self, name: &str) -> Option<&dyn ContigLike<C>> {
+        GenomeBuild::contig_by_name(self, name).map(|c| c as &dyn ContigLike<C>)
+    }
+}
@@ -0,0 +1,140 @@
+//! # Length units
+//!
+//! Human-readable formatting of sequence lengths, e.g. `248.96 Mb`.
+
+use alloc::{format, string::String};
+use core::fmt::{self, Display, Write};
+
+use num_traits::ToPrimitive;
+
+use crate::Contig;
+
+/// Format the `length` in base pairs with a decimal unit prefix, e.g. `248.96 Mb`.
+///
+/// The lengths below 1 kb are formatted as integers, e.g. `512 bp`,
+/// and the other lengths are rounded to 2 decimal places.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::format_bp;
+///
+/// assert_eq!(format_bp(248_956_422u32), "248.96 Mb");
+/// assert_eq!(format_bp(16_569u32), "16.57 kb");
+/// assert_eq!(format_bp(512u16), "512 bp");
+/// ```
+pub fn format_bp<C>(length: C) -> String
+where
+    C: ToPrimitive,
+{
+    const UNITS: [(f64, &str); 4] = [(1e12, "Tb"), (1e9, "Gb"), (1e6, "Mb"), (1e3, "kb")];
+
+    let length = length.to_f64().unwrap_or(f64::NAN);
+    UNITS
+        .iter()
+        .find(|(scale, _)| length.abs() >= *scale)
+        .map_or_else(
+            || format!("{length} bp"),
+            |(scale, unit)| format!("{:.2} {unit}", length / scale),
+        )
+}
+
+impl<C> Contig<C>
+where
+    C: ToPrimitive + Copy,
+{
+    /// Get the contig length formatted with a decimal unit prefix, e.g. `248.96 Mb`.
+    ///
+    /// See [`format_bp`].
+    pub fn length_human(&self) -> String {
+        format_bp(*self.length())
+    }
+}
+
+/// Show the main name, the alternative names, and the length with thousands separators,
+/// e.g. `1 (CM000663.2, NC_000001.11, chr1) [248,956,422 bp]`.
+///
+/// The alternate flag (`{:#}`) selects the terse form without the alternative names,
+/// e.g. `1 [248,956,422 bp]`.
+///
+/// ## Example
+///
+/// ```rust
+/// use dabuild::Contig;
+///
+/// let contig = Contig::new("1", &["CM000663.2", "NC_000001.11", "chr1"], 248_956_422u32).unwrap();
+///
+/// assert_eq!(contig.to_string(), "1 (CM000663.2, NC_000001.11, chr1) [248,956,422 bp]");
+/// assert_eq!(format!("{contig:#}"), "1 [248,956,422 bp]");
+/// ```
+impl<C> Display for Contig<C>
+where
+    C: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())?;
+        if !f.alternate() {
+            for (i, alt_name) in self.alt_names().enumerate() {
+                f.write_str(if i == 0 { " (" } else { ", " })?;
+                f.write_str(alt_name)?;
+            }
+            if self.alt_names().next().is_some() {
+                f.write_char(')')?;
+            }
+        }
+        f.write_str(" [")?;
+        write_grouped(f, &format!("{}", self.length()))?;
+        f.write_str(" bp]")
+    }
+}
+
+/// Write the `number` with a comma between each group of three digits, e.g. `248,956,422`.
+///
+/// The `number` is written as is unless it is an optionally negative integer.
+pub(crate) fn write_grouped<W: Write>(write: &mut W, number: &str) -> fmt::Result {
+    let digits = number.strip_prefix('-').unwrap_or(number);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return write.write_str(number);
+    }
+    if digits.len() < number.len() {
+        write.write_char('-')?;
+    }
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            write.write_char(',')?;
+        }
+        write.write_char(digit)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::{format_bp, write_grouped};
+
+    #[test]
+    fn test_format_bp() {
+        assert_eq!(format_bp(0u8), "0 bp");
+        assert_eq!(format_bp(999u32), "999 bp");
+        assert_eq!(format_bp(1_000u32), "1.00 kb");
+        assert_eq!(format_bp(3_272_116_950u64), "3.27 Gb");
+        assert_eq!(format_bp(-1_500i32), "-1.50 kb");
+    }
+
+    #[test]
+    fn test_write_grouped() {
+        let grouped = |number: &str| {
+            let mut out = String::new();
+            write_grouped(&mut out, number).unwrap();
+            out
+        };
+        assert_eq!(grouped("0"), "0");
+        assert_eq!(grouped("999"), "999");
+        assert_eq!(grouped("1000"), "1,000");
+        assert_eq!(grouped("248956422"), "248,956,422");
+        assert_eq!(grouped("-16569"), "-16,569");
+        assert_eq!(grouped("1.5"), "1.5");
+    }
+}
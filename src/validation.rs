@@ -0,0 +1,384 @@
+//! # Genome build validation
+//!
+//! The module includes [`ValidationReport`], the problems of the contigs of a genome build,
+//! such as the names shared by several contigs. See [`GenomeBuild::validate`]
+//! and [`GenomeBuild::validate_lengths`]. The aliases added to a build are checked against
+//! the contig lengths of the bundled builds by [`GenomeBuild::add_alias_checked`]
+//! and [`GenomeBuild::merge_checked`].
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+
+use num_traits::{ToPrimitive, Zero};
+
+use crate::{builds::KnownBuild, ConflictPolicy, Contig, GenomeBuild, NameStyle};
+
+/// A problem of a genome build.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BuildProblem {
+    /// The `name` is the main name or an alias of several `contigs`.
+    DuplicateName { name: String, contigs: Vec<String> },
+    /// The `alias` of the `contig` is the main name of the `other` contig.
+    AliasShadowsName {
+        alias: String,
+        contig: String,
+        other: String,
+    },
+    /// The `contig` has zero length.
+    ZeroLength { contig: String },
+    /// The `contig` has neither a GenBank nor a RefSeq accession,
+    /// while other contigs of the build do.
+    MissingAccession { contig: String },
+    /// The `length` of the `contig` is above the `max` length,
+    /// see [`GenomeBuild::validate_lengths`].
+    ImplausibleLength {
+        contig: String,
+        length: u64,
+        max: u64,
+    },
+    /// The `alias` of the `contig` with the `length` is the accession of a contig
+    /// with the `known_length` in the `known` bundled build, e.g. when mixing patch levels,
+    /// see [`GenomeBuild::add_alias_checked`].
+    AliasLengthMismatch {
+        alias: String,
+        contig: String,
+        length: u64,
+        known: KnownBuild,
+        known_length: u64,
+    },
+}
+
+impl Display for BuildProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildProblem::DuplicateName { name, contigs } => {
+                write!(f, "Name {name:?} is shared by contigs {contigs:?}")
+            }
+            BuildProblem::AliasShadowsName {
+                alias,
+                contig,
+                other,
+            } => write!(
+                f,
+                "Alias {alias:?} of contig {contig:?} is the main name of contig {other:?}"
+            ),
+            BuildProblem::ZeroLength { contig } => write!(f, "Contig {contig:?} has zero length"),
+            BuildProblem::MissingAccession { contig } => {
+                write!(f, "Contig {contig:?} has no GenBank or RefSeq accession")
+            }
+            BuildProblem::ImplausibleLength {
+                contig,
+                length,
+                max,
+            } => write!(
+                f,
+                "Contig {contig:?} has length {length}, more than the maximum {max}"
+            ),
+            BuildProblem::AliasLengthMismatch {
+                alias,
+                contig,
+                length,
+                known,
+                known_length,
+            } => write!(
+                f,
+                "Alias {alias:?} of contig {contig:?} with length {length} has length {known_length} in {known}"
+            ),
+        }
+    }
+}
+
+/// The problems of a genome build, see [`GenomeBuild::validate`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ValidationReport {
+    problems: Vec<BuildProblem>,
+}
+
+impl ValidationReport {
+    /// Test if the build has no problems.
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// Get the problems, grouped by kind.
+    pub fn problems(&self) -> &[BuildProblem] {
+        &self.problems
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for problem in &self.problems {
+            writeln!(f, "{problem}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<C> GenomeBuild<C>
+where
+    C: Zero + PartialEq,
+{
+    /// Check the contigs of the build for:
+    ///
+    /// * names shared by several contigs
+    /// * aliases equal to the main name of another contig
+    /// * zero lengths
+    /// * missing GenBank and RefSeq accessions, if other contigs have them
+    ///
+    /// The checks are useful after merging or editing builds,
+    /// since the builds are not validated on creation.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use dabuild::{BuildProblem, Contig, GenomeBuild, GenomeBuildIdentifier};
+    ///
+    /// let build = GenomeBuild::new(
+    ///     GenomeBuildIdentifier::from_str("toy").expect("Infallible"),
+    ///     [
+    ///         Contig::new("1", &["chr1"], 100u32).unwrap(),
+    ///         Contig::new("2", &["1"], 0u32).unwrap(),
+    ///     ],
+    /// );
+    ///
+    /// let report = build.validate();
+    /// assert_eq!(report.problems(), [
+    ///     BuildProblem::AliasShadowsName {
+    ///         alias: "1".into(), contig: "2".into(), other: "1".into(),
+    ///     },
+    ///     BuildProblem::ZeroLength { contig: "2".into() },
+    /// ]);
+    /// ```
+    pub fn validate(&self) -> ValidationReport {
+        let mut duplicates = Vec::new();
+        let mut shadows = Vec::new();
+        let mut zero_lengths = Vec::new();
+        let mut missing_accessions = Vec::new();
+
+        // The indices of the contigs using each name, as the main name or as an alias.
+        let mut mains: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        let mut aliases: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (i, contig) in self.contigs().enumerate() {
+            mains.entry(contig.name()).or_default().push(i);
+            for alias in contig.alt_names() {
+                aliases.entry(alias).or_default().push(i);
+            }
+        }
+        let contigs: Vec<_> = self.contigs().collect();
+        let names_of = |indices: &[usize]| -> Vec<String> {
+            let mut names: Vec<_> = indices.iter().map(|&i| contigs[i].name()).collect();
+            names.dedup();
+            names.into_iter().map(String::from).collect()
+        };
+
+        for (name, indices) in &mains {
+            if indices.len() > 1 {
+                duplicates.push(BuildProblem::DuplicateName {
+                    name: name.to_string(),
+                    contigs: names_of(indices),
+                });
+            }
+        }
+        for (alias, indices) in &aliases {
+            if let Some(others) = mains.get(alias) {
+                for &i in indices.iter().filter(|i| !others.contains(i)) {
+                    shadows.push(BuildProblem::AliasShadowsName {
+                        alias: alias.to_string(),
+                        contig: contigs[i].name().to_string(),
+                        other: contigs[others[0]].name().to_string(),
+                    });
+                }
+            } else {
+                let mut distinct = indices.clone();
+                distinct.dedup();
+                if distinct.len() > 1 {
+                    duplicates.push(BuildProblem::DuplicateName {
+                        name: alias.to_string(),
+                        contigs: names_of(&distinct),
+                    });
+                }
+            }
+        }
+
+        let has_accession = |i: usize| {
+            contigs[i].name_in(NameStyle::GenBank).is_some()
+                || contigs[i].name_in(NameStyle::RefSeq).is_some()
+        };
+        let any_accession = (0..contigs.len()).any(has_accession);
+        for (i, contig) in contigs.iter().enumerate() {
+            if contig.length().is_zero() {
+                zero_lengths.push(BuildProblem::ZeroLength {
+                    contig: contig.name().to_string(),
+                });
+            }
+            if any_accession && !has_accession(i) {
+                missing_accessions.push(BuildProblem::MissingAccession {
+                    contig: contig.name().to_string(),
+                });
+            }
+        }
+
+        let mut problems = duplicates;
+        problems.append(&mut shadows);
+        problems.append(&mut zero_lengths);
+        problems.append(&mut missing_accessions);
+        ValidationReport { problems }
+    }
+}
+
+impl<C> GenomeBuild<C>
+where
+    C: ToPrimitive,
+{
+    /// Check that no contig is longer than the `max_length`.
+    ///
+    /// The check catches the unit errors in hand-made files, such as a `chrom.sizes`
+    /// with the lengths in other units than base pairs.
+    /// Use [`crate::Karyotype::max_contig_length`] for a plausible maximum of an organism.
+    ///
+    /// The lengths that cannot be represented by `u64` are reported as [`u64::MAX`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use dabuild::{GenomeBuild, GenomeBuildIdentifier, Karyotype};
+    /// use dabuild::builds::get_grch38_p13;
+    ///
+    /// let build: GenomeBuild<u64> = get_grch38_p13();
+    /// let max = Karyotype::Human.max_contig_length();
+    /// assert!(build.validate_lengths(max).is_valid());
+    ///
+    /// // The length of `chr1` in bits rather than in base pairs.
+    /// let build = GenomeBuild::builder()
+    ///     .id(GenomeBuildIdentifier::from_str("hand-made").expect("Infallible"))
+    ///     .contig("chr1", 248_956_422u64 * 2)
+    ///     .build()
+    ///     .expect("Valid build");
+    /// assert_eq!(build.validate_lengths(max).problems().len(), 1);
+    /// ```
+    pub fn validate_lengths(&self, max_length: u64) -> ValidationReport {
+        let problems = self
+            .contigs()
+            .filter_map(|contig| {
+                let length = contig.length().to_u64().unwrap_or(u64::MAX);
+                (length > max_length).then(|| BuildProblem::ImplausibleLength {
+                    contig: contig.name().to_string(),
+                    length,
+                    max: max_length,
+                })
+            })
+            .collect();
+        ValidationReport { problems }
+    }
+}
+
+impl<C> GenomeBuild<C>
+where
+    C: Clone + ToPrimitive,
+{
+    /// Add the `alias` to the contig known under the `name`, as [`GenomeBuild::add_alias`],
+    /// and report if the alias is the accession of a contig of another length in a bundled build,
+    /// a symptom of mixing patch levels.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use dabuild::{BuildProblem, GenomeBuild};
+    /// use dabuild::builds::{get_grch38_p13, KnownBuild};
+    ///
+    /// let mut build: GenomeBuild<u32> = get_grch38_p13();
+    /// // The GRCh37 accession of chromosome 1.
+    /// let report = build.add_alias_checked("chr1", "NC_000001.10").expect("Unique alias");
+    ///
+    /// assert!(matches!(
+    ///     &report.problems()[0],
+    ///     BuildProblem::AliasLengthMismatch { known: KnownBuild::GRCh37p13, known_length: 249_250_621, .. },
+    /// ));
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// The same as [`GenomeBuild::add_alias`].
+    pub fn add_alias_checked(
+        &mut self,
+        name: &str,
+        alias: &str,
+    ) -> Result<ValidationReport, String> {
+        self.add_alias(name, alias)?;
+        let contig = self.contig_by_name(name).expect("Checked contig");
+        Ok(ValidationReport {
+            problems: alias_length_mismatches([(contig, alias)]),
+        })
+    }
+
+    /// Merge the `other` build into this build, as [`GenomeBuild::merge`],
+    /// and report the names of the merged contigs that are the accessions of contigs
+    /// of other lengths in a bundled build.
+    ///
+    /// ## Errors
+    ///
+    /// The same as [`GenomeBuild::merge`].
+    pub fn merge_checked(
+        &self,
+        other: &GenomeBuild<C>,
+        policy: ConflictPolicy,
+    ) -> Result<(GenomeBuild<C>, ValidationReport), String> {
+        let merged = self.merge(other, policy)?;
+        let added = other.contigs().filter(|contig| {
+            merged
+                .contig_by_name(contig.name())
+                .is_some_and(|c| c.names().eq(contig.names()))
+        });
+        let problems = alias_length_mismatches(
+            added.flat_map(|contig| contig.names().map(move |n| (contig, n))),
+        );
+        Ok((merged, ValidationReport { problems }))
+    }
+}
+
+/// Find the aliases that are the accessions of the contigs of other lengths in the bundled builds.
+///
+/// The bundled assembly reports are scanned once.
+fn alias_length_mismatches<'a, C, I>(aliases: I) -> Vec<BuildProblem>
+where
+    C: ToPrimitive + 'a,
+    I: IntoIterator<Item = (&'a Contig<C>, &'a str)>,
+{
+    let accessions: BTreeMap<&str, &Contig<C>> = aliases
+        .into_iter()
+        .filter(|(_, alias)| NameStyle::GenBank.matches(alias) || NameStyle::RefSeq.matches(alias))
+        .map(|(contig, alias)| (alias, contig))
+        .collect();
+    if accessions.is_empty() {
+        return Vec::new();
+    }
+
+    let mut problems = Vec::new();
+    for known in KnownBuild::ALL {
+        for record in known.records() {
+            for accession in [&record.genbank, &record.refseq].into_iter().flatten() {
+                let Some(contig) = accessions.get(accession.as_ref()) else {
+                    continue;
+                };
+                let length = contig.length().to_u64().unwrap_or(u64::MAX);
+                if length != record.length {
+                    problems.push(BuildProblem::AliasLengthMismatch {
+                        alias: accession.to_string(),
+                        contig: contig.name().to_string(),
+                        length,
+                        known,
+                        known_length: record.length,
+                    });
+                }
+            }
+        }
+    }
+    problems
+}
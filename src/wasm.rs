@@ -0,0 +1,106 @@
+//! # WebAssembly bindings
+//!
+//! The [`wasm_bindgen`] wrappers expose the genome build and the contig lookup to JavaScript,
+//! e.g. to share the contig name resolution between browser-based genome viewers
+//! and the backend.
+//!
+//! The contig lengths are represented as `u64` and exposed as JavaScript numbers.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str::FromStr;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    builds::{get_grch37_p13, get_grch38_p13, parse_assembly_report_bytes},
+    Contig, GenomeBuild, GenomeBuildIdentifier,
+};
+
+/// A genome build.
+#[wasm_bindgen(js_name = GenomeBuild)]
+pub struct WasmGenomeBuild(GenomeBuild<u64>);
+
+#[wasm_bindgen(js_class = GenomeBuild)]
+impl WasmGenomeBuild {
+    /// Get the bundled *GRCh37.p13* build.
+    #[wasm_bindgen(js_name = grch37p13)]
+    pub fn grch37_p13() -> WasmGenomeBuild {
+        WasmGenomeBuild(get_grch37_p13())
+    }
+
+    /// Get the bundled *GRCh38.p13* build.
+    #[wasm_bindgen(js_name = grch38p13)]
+    pub fn grch38_p13() -> WasmGenomeBuild {
+        WasmGenomeBuild(get_grch38_p13())
+    }
+
+    /// Parse an assembly report, e.g. downloaded by the browser.
+    #[wasm_bindgen(js_name = fromAssemblyReport)]
+    pub fn from_assembly_report(
+        major_assembly: &str,
+        patch: Option<String>,
+        report: &[u8],
+    ) -> Result<WasmGenomeBuild, JsError> {
+        let id = match patch {
+            Some(patch) => GenomeBuildIdentifier::from((major_assembly, patch.as_str())),
+            None => {
+                GenomeBuildIdentifier::from_str(major_assembly).map_err(|e| JsError::new(&e))?
+            }
+        };
+        parse_assembly_report_bytes(id, report)
+            .map(WasmGenomeBuild)
+            .map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Get the major assembly, e.g. `GRCh38`.
+    #[wasm_bindgen(getter, js_name = majorAssembly)]
+    pub fn major_assembly(&self) -> String {
+        self.0.id().major_assembly().to_string()
+    }
+
+    /// Get the patch, e.g. `p13`, if any.
+    #[wasm_bindgen(getter)]
+    pub fn patch(&self) -> Option<String> {
+        self.0.id().patch().map(ToString::to_string)
+    }
+
+    /// Get the main names of all contigs.
+    #[wasm_bindgen(getter, js_name = contigNames)]
+    pub fn contig_names(&self) -> Vec<String> {
+        self.0.contigs().map(|c| c.name().to_string()).collect()
+    }
+
+    /// Get a contig by its main or alternative name.
+    #[wasm_bindgen(js_name = contigByName)]
+    pub fn contig_by_name(&self, name: &str) -> Option<WasmContig> {
+        self.0.contig_by_name(name).cloned().map(WasmContig)
+    }
+}
+
+/// A contig of a genome build.
+#[wasm_bindgen(js_name = Contig)]
+pub struct WasmContig(Contig<u64>);
+
+#[wasm_bindgen(js_class = Contig)]
+impl WasmContig {
+    /// Get the main name of the contig.
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.0.name().to_string()
+    }
+
+    /// Get the alternative names of the contig.
+    #[wasm_bindgen(getter, js_name = altNames)]
+    pub fn alt_names(&self) -> Vec<String> {
+        self.0.alt_names().map(ToString::to_string).collect()
+    }
+
+    /// Get the number of bases of the contig.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> f64 {
+        *self.0.length() as f64
+    }
+}
@@ -1,6 +1,11 @@
+#![cfg(feature = "std")]
+
 use std::{error::Error, fs::File, io::BufReader, str::FromStr};
 
-use dabuild::{builds::*, GenomeBuild, GenomeBuildIdentifier};
+use dabuild::{
+    builds::*, AssemblyLevel, ContigCategory, ContigOrdering, GenomeBuild, GenomeBuildIdentifier,
+    GenomeBuildLike, GenomeRepresentation, NameStyle, SequenceRole,
+};
 
 #[test]
 fn grch38_p13() {
@@ -23,6 +28,87 @@ fn grch38_p13() {
         .all(|x| alt.contains(x)));
 
     assert_eq!(contig.length(), &248_956_422usize);
+    assert_eq!(contig.role(), Some(SequenceRole::AssembledMolecule));
+    let counts = build.counts_by_role();
+    for role in SequenceRole::ALL {
+        assert_eq!(role.as_str().parse(), Ok(role));
+        assert!(counts.contains_key(&Some(role)), "{role} contigs");
+    }
+
+    assert_eq!(build.chromosomes().count(), 25);
+    assert_eq!(build.autosomes().count(), 22);
+    let sex: Vec<_> = build.sex_chromosomes().map(|c| c.name()).collect();
+    assert_eq!(sex, ["X", "Y"]);
+    let mito: Vec<_> = build.mitochondrial().map(|c| c.name()).collect();
+    assert_eq!(mito, ["MT"]);
+    assert_eq!(build.alt_scaffolds().count(), 261);
+    assert_eq!(build.unplaced().count(), 127);
+
+    let counts = build.counts_by_molecule();
+    assert_eq!(counts[&Some(ContigCategory::Autosome)], 22);
+    assert_eq!(counts[&Some(ContigCategory::Patch)], 185);
+    assert_eq!(counts.values().sum::<usize>(), 640);
+}
+
+#[test]
+fn grch38_patch_releases() {
+    let build = get_grch38_p13::<u32>();
+
+    assert!(build.contigs().all(|c| c.introduced_in().is_some()));
+    let patches: Vec<_> = build
+        .contigs()
+        .filter_map(|c| c.introduced_in().filter(|&patch| patch > 0))
+        .collect();
+    assert_eq!(patches.len(), 185);
+    assert_eq!(patches.iter().max(), Some(&13));
+    assert_eq!(patches.iter().filter(|&&patch| patch == 1).count(), 16);
+
+    // Only the bundled GRCh38 is annotated.
+    let grch37 = get_grch37_p13::<u32>();
+    assert!(grch37.contigs().all(|c| c.introduced_in().is_none()));
+}
+
+#[test]
+fn bundled_build_order_is_stable() {
+    // The digests of the contig names in the build order.
+    // Changing the order of the bundled builds is a breaking change.
+    for (known, names_digest) in [
+        (KnownBuild::GRCh37p13, "7KpM407TGdmmR2-_yy-LYg_rQhIzMwBy"),
+        (KnownBuild::GRCh38p13, "dpiUpLoA_o0aHKBF06V76IjkXV4CWkMU"),
+    ] {
+        let build: GenomeBuild<u32> = known.load();
+        assert_eq!(build.seqcol_digests().names, names_digest);
+
+        let first: Vec<_> = build.order().take(3).collect();
+        assert_eq!(first, ["1", "10", "11"]);
+        assert!(build.order().eq(build.contigs().map(|c| c.name())));
+    }
+}
+
+#[test]
+fn known_builds() {
+    for known in KnownBuild::ALL {
+        let build: GenomeBuild<u32> = known.load();
+        assert_eq!(known.name().parse::<KnownBuild>(), Ok(known));
+        assert!(known.name().starts_with(build.id().major_assembly()));
+
+        let metadata = build.metadata();
+        assert_eq!(
+            metadata.genbank_accession(),
+            Some(known.genbank_accession())
+        );
+        assert_eq!(metadata.refseq_accession(), Some(known.refseq_accession()));
+        assert_eq!(
+            KnownBuild::from_accession(known.refseq_accession()),
+            Some(known)
+        );
+    }
+
+    assert_eq!(
+        "grch37.P13".parse::<KnownBuild>(),
+        Ok(KnownBuild::GRCh37p13)
+    );
+    assert!("GRCh39".parse::<KnownBuild>().is_err());
 }
 
 #[test]
@@ -51,6 +137,441 @@ fn test_parse_assembly_report() -> Result<(), Box<dyn Error>> {
         .all(|x| alt.contains(x)));
 
     assert_eq!(contig.length(), &91_455_967u32);
+    // The 49th line of the report, counting the header lines.
+    assert_eq!(contig.source_index(), Some(48));
 
     Ok(())
 }
+
+#[test]
+fn subset_resolves_alternative_names() {
+    let build = get_grch38_p13::<u32>();
+
+    let subset = build.subset(["chr1", "NC_000001.11", "Y"]);
+
+    assert!(subset.is_ok());
+    let subset = subset.unwrap();
+    assert_eq!(subset.id(), build.id());
+    let names: Vec<_> = subset.contigs().map(|c| c.name()).collect();
+    assert_eq!(names, vec!["1", "Y"]);
+}
+
+#[test]
+fn subset_fails_on_unknown_name() {
+    let build = get_grch38_p13::<u32>();
+
+    assert!(build.subset(["chr1", "chrZ"]).is_err());
+}
+
+#[test]
+fn build_behind_trait_object() {
+    let build: Box<dyn GenomeBuildLike<u32>> = Box::new(get_grch38_p13::<u32>());
+
+    assert_eq!(build.id().major_assembly(), "GRCh38");
+    assert_eq!(build.contigs().count(), 640);
+
+    let contig = build.contig_by_name("NC_000024.10");
+    assert!(contig.is_some());
+
+    let contig = contig.unwrap();
+    assert_eq!(contig.name(), "Y");
+    assert_eq!(contig.length(), &57_227_415);
+}
+
+#[test]
+fn clones_share_contigs() {
+    let build = get_grch38_p13::<u32>();
+    let clone = build.clone();
+
+    assert_eq!(build, clone);
+    assert!(build
+        .contigs()
+        .zip(clone.contigs())
+        .all(|(l, r)| std::ptr::eq(l, r)));
+}
+
+#[test]
+fn parse_assembly_report_from_bytes() -> Result<(), Box<dyn Error>> {
+    let bytes = std::fs::read("data/GCF_000001635.27_GRCm39_assembly_report.txt")?;
+    let build: GenomeBuild<u32> =
+        parse_assembly_report_bytes(GenomeBuildIdentifier::from_str("GRCm39")?, &bytes)?;
+
+    assert_eq!(build.contigs().count(), 61);
+    assert_eq!(
+        build.contig_by_name("NC_000087.8").map(|c| c.name()),
+        Some("Y")
+    );
+
+    assert!(parse_assembly_report_bytes::<u32>(
+        GenomeBuildIdentifier::from_str("GRCm39")?,
+        &[0xff, 0xfe]
+    )
+    .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn borrowed_records_match_parsed_contigs() -> Result<(), Box<dyn Error>> {
+    let report = std::fs::read_to_string("data/GCF_000001635.27_GRCm39_assembly_report.txt")?;
+    let build: GenomeBuild<u32> = parse_assembly_report_bytes(
+        GenomeBuildIdentifier::from_str("GRCm39")?,
+        report.as_bytes(),
+    )?;
+
+    let records = parse_assembly_report_records::<u32>(&report).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(records.len(), 61);
+    assert!(records
+        .iter()
+        .all(|record| matches!(record.name, std::borrow::Cow::Borrowed(_))));
+
+    let owned: Vec<_> = records
+        .into_iter()
+        .map(|record| record.into_owned())
+        .collect();
+    drop(report);
+    for record in owned {
+        let line = record.line;
+        let contig = record.into_contig().expect("Non-negative length");
+        assert_eq!(build.contig_by_name(contig.name()), Some(&contig));
+        assert_eq!(contig.source_index(), Some(line));
+    }
+
+    assert!(
+        parse_assembly_report_records::<u32>("1\tassembled-molecule\n")
+            .next()
+            .is_some_and(|record| record.is_err())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_with_role_filter() -> Result<(), Box<dyn Error>> {
+    let report = std::fs::read("data/GCF_000001635.27_GRCm39_assembly_report.txt")?;
+    let options = ParseOptions::new().roles(&[
+        SequenceRole::AssembledMolecule,
+        SequenceRole::UnlocalizedScaffold,
+    ]);
+
+    let build: GenomeBuild<u32> = parse_assembly_report_bytes_with(
+        GenomeBuildIdentifier::from_str("GRCm39")?,
+        &report,
+        &options,
+    )?;
+
+    assert_eq!(build.contigs().count(), 40);
+    assert!(build.contigs().all(|contig| matches!(
+        contig.role(),
+        Some(SequenceRole::AssembledMolecule | SequenceRole::UnlocalizedScaffold)
+    )));
+    assert!(build.contig_by_name("MSCHRUN_CTG13").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn parse_with_min_length() -> Result<(), Box<dyn Error>> {
+    let report = std::fs::read("data/GCF_000001635.27_GRCm39_assembly_report.txt")?;
+    let id = GenomeBuildIdentifier::from_str("GRCm39")?;
+    let options = ParseOptions::new().min_length(100_000);
+
+    let build: GenomeBuild<u32> = parse_assembly_report_bytes_with(id.clone(), &report, &options)?;
+    let all: GenomeBuild<u32> = parse_assembly_report_bytes(id, &report)?;
+
+    assert!(build.contigs().count() < all.contigs().count());
+    assert!(build.contigs().all(|contig| *contig.length() >= 100_000));
+    assert_eq!(build, all.filter_min_length(100_000));
+
+    Ok(())
+}
+
+#[test]
+fn metadata_from_report_header() -> Result<(), Box<dyn Error>> {
+    let report = std::fs::read("data/GCF_000001635.27_GRCm39_assembly_report.txt")?;
+    let build: GenomeBuild<u32> =
+        parse_assembly_report_bytes(GenomeBuildIdentifier::from_str("GRCm39")?, &report)?;
+
+    let metadata = build.metadata();
+    assert_eq!(metadata.assembly_level(), Some(AssemblyLevel::Chromosome));
+    assert_eq!(
+        metadata.genome_representation(),
+        Some(GenomeRepresentation::Full)
+    );
+
+    let mut written = vec![];
+    write_assembly_report(&build, NameStyle::Main, &mut written)?;
+    let parsed: GenomeBuild<u32> = parse_assembly_report_bytes(build.id().clone(), &written)?;
+    assert_eq!(parsed.metadata(), metadata);
+
+    Ok(())
+}
+
+#[test]
+fn report_length_error_has_line_number() {
+    let report =
+        "# Sequence-Name\tSequence-Role\n1\tassembled-molecule\tna\tna\tna\tna\tna\tna\tten\tna\n";
+    let build = parse_assembly_report_bytes::<u32>(
+        GenomeBuildIdentifier::from_str("broken").unwrap(),
+        report.as_bytes(),
+    );
+    let error = build.unwrap_err().to_string();
+    assert!(
+        error.contains("\"ten\"") && error.contains("line #1"),
+        "{error}"
+    );
+}
+
+#[test]
+fn assembly_report_round_trip_in_any_style() -> Result<(), Box<dyn Error>> {
+    let build = get_grch38_p13::<u32>();
+    for style in NameStyle::ALL {
+        let mut written = vec![];
+        write_assembly_report(&build, style, &mut written)?;
+        let parsed: GenomeBuild<u32> = parse_assembly_report_bytes(build.id().clone(), &written)?;
+        assert_eq!(parsed, build, "{style}");
+        assert!(parsed.contig_by_name("1").is_some(), "{style}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn assembly_report_writes_the_report_names() -> Result<(), Box<dyn Error>> {
+    // The main name looks like a UCSC-style name, hence only the column tells `chrM` apart.
+    let report = "chrMT\tassembled-molecule\tMT\tMitochondrion\tJ01415.2\t=\tNC_012920.1\tnon-nuclear\t16569\tchrM\n";
+    let build: GenomeBuild<u32> =
+        parse_assembly_report(GenomeBuildIdentifier::from_str("toy")?, report.as_bytes())?;
+    let report = dabuild::formats::round_trip_report(
+        &build,
+        dabuild::formats::RoundTripFormat::AssemblyReport,
+    );
+    assert!(report.is_lossless(), "{:?}", report.dropped());
+
+    let mut written = vec![];
+    write_assembly_report(&build, NameStyle::Main, &mut written)?;
+    let written = String::from_utf8(written)?;
+    assert!(written.ends_with("\tJ01415.2\t=\tNC_012920.1\tna\t16569\tchrM\n"));
+
+    let parsed: GenomeBuild<u32> =
+        parse_assembly_report_bytes(build.id().clone(), written.as_bytes())?;
+    let contig = parsed.contig_by_name("chrMT").expect("Known contig");
+    assert_eq!(contig.ucsc_name(), Some("chrM"));
+
+    Ok(())
+}
+
+#[test]
+fn refresh_from_detects_changes() -> Result<(), Box<dyn Error>> {
+    let report = std::fs::read_to_string("data/GCF_000001635.27_GRCm39_assembly_report.txt")?;
+    let mut build: GenomeBuild<u32> = parse_assembly_report(
+        GenomeBuildIdentifier::from_str("GRCm39")?,
+        report.as_bytes(),
+    )?;
+    let fingerprint = build.fingerprint();
+
+    assert!(!build.refresh_from(report.as_bytes())?);
+    assert_eq!(build.fingerprint(), fingerprint);
+
+    // The same names with a different length of chromosome 1.
+    let longer = report.replacen("\t195154279\t", "\t195154280\t", 1);
+    assert_ne!(longer, report);
+    assert!(build.refresh_from(longer.as_bytes())?);
+    assert_eq!(
+        build.contig_by_name("1").map(|c| *c.length()),
+        Some(195_154_280)
+    );
+
+    let fewer = report
+        .lines()
+        .take(report.lines().count() - 1)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let count = build.contigs().count();
+    assert!(build.refresh_from(fewer.as_bytes())?);
+    assert_eq!(build.contigs().count(), count - 1);
+    assert_ne!(build.fingerprint(), fingerprint);
+
+    assert!(build.refresh_from("broken".as_bytes()).is_err());
+    assert_eq!(build.contigs().count(), count - 1);
+
+    // The same contigs in other lines of the report have other source indices.
+    let mut lines: Vec<_> = fewer.lines().collect();
+    let n = lines.len();
+    lines.swap(n - 1, n - 2);
+    assert!(build.refresh_from(lines.join("\n").as_bytes())?);
+    assert_eq!(build.contigs().count(), count - 1);
+
+    Ok(())
+}
+
+#[test]
+fn refresh_from_keeps_ordering_and_patches() -> Result<(), Box<dyn Error>> {
+    let report = std::fs::read_to_string("data/GCF_000001635.27_GRCm39_assembly_report.txt")?;
+    let parsed: GenomeBuild<u32> = parse_assembly_report(
+        GenomeBuildIdentifier::from_str("GRCm39")?,
+        report.as_bytes(),
+    )?;
+    let mut build = GenomeBuild::new_ordered(
+        parsed.id().clone(),
+        parsed.contigs().cloned(),
+        ContigOrdering::Natural,
+    )
+    .with_metadata(parsed.metadata().clone());
+    let key = build.contig_key("MT").expect("Mitochondrion");
+
+    // The unchanged report keeps the build and its keys.
+    assert!(!build.refresh_from(report.as_bytes())?);
+    assert_eq!(build.ordering(), ContigOrdering::Natural);
+    assert_eq!(build.contig_by_key(key).map(|c| c.name()), Some("MT"));
+
+    // The changed build keeps the ordering.
+    let names: Vec<_> = build.contigs().map(|c| c.name().to_string()).collect();
+    let longer = report.replacen("\t195154279\t", "\t195154280\t", 1);
+    assert!(build.refresh_from(longer.as_bytes())?);
+    assert_eq!(build.ordering(), ContigOrdering::Natural);
+    assert!(build
+        .contigs()
+        .map(|c| c.name())
+        .eq(names.iter().map(String::as_str)));
+
+    // The bundled build keeps the patch releases of its contigs.
+    let report =
+        std::fs::read_to_string("src/data/GCF_000001405.39_GRCh38.p13_assembly_report.tsv")?;
+    let patches = |build: &GenomeBuild<u32>| {
+        build
+            .contigs()
+            .filter(|c| c.introduced_in().is_some_and(|patch| patch > 0))
+            .count()
+    };
+    let mut grch38 = get_grch38_p13::<u32>();
+    assert!(!grch38.refresh_from(report.as_bytes())?);
+    let longer = report.replacen("\t248956422\t", "\t248956423\t", 1);
+    assert!(grch38.refresh_from(longer.as_bytes())?);
+    assert_eq!(patches(&grch38), 185);
+
+    // The patches new to a GRCh38 build get their patch releases too.
+    let mut parsed: GenomeBuild<u32> =
+        parse_assembly_report(grch38.id().clone(), report.as_bytes())?;
+    assert_eq!(patches(&parsed), 0);
+    assert!(parsed.refresh_from(longer.as_bytes())?);
+    assert_eq!(patches(&parsed), 185);
+
+    Ok(())
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn parse_async_matches_sync() -> Result<(), Box<dyn Error + Send + Sync>> {
+    use dabuild::builds::{parse_assembly_report_async, parse_assembly_report_async_with};
+
+    let report = std::fs::read("data/GCF_000001635.27_GRCm39_assembly_report.txt")?;
+    let id = GenomeBuildIdentifier::from_str("GRCm39")?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+    let parsed: GenomeBuild<u32> =
+        runtime.block_on(parse_assembly_report_async(id.clone(), report.as_slice()))?;
+
+    let expected: GenomeBuild<u32> =
+        parse_assembly_report_bytes(id, &report).map_err(|e| e.to_string())?;
+    assert_eq!(parsed, expected);
+
+    let options = ParseOptions::new()
+        .roles(&[SequenceRole::AssembledMolecule])
+        .chr_aliases();
+    let parsed: GenomeBuild<u32> = runtime.block_on(parse_assembly_report_async_with(
+        GenomeBuildIdentifier::from_str("GRCm39")?,
+        report.as_slice(),
+        &options,
+    ))?;
+    assert_eq!(parsed.contigs().count(), 22);
+    let max = ParseOptions::new().max_contigs(10);
+    assert!(runtime
+        .block_on(parse_assembly_report_async_with::<u32, _>(
+            GenomeBuildIdentifier::from_str("GRCm39")?,
+            report.as_slice(),
+            &max,
+        ))
+        .is_err());
+
+    Ok(())
+}
+
+#[cfg(all(feature = "tokio", feature = "bgzf"))]
+#[test]
+fn parse_async_decompresses() -> Result<(), Box<dyn Error + Send + Sync>> {
+    use std::io::Write;
+
+    use dabuild::builds::parse_assembly_report_async;
+    use flate2::{write::GzEncoder, Compression};
+
+    let report = std::fs::read("data/GCF_000001635.27_GRCm39_assembly_report.txt")?;
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder.write_all(&report)?;
+    let compressed = encoder.finish()?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+    let parsed: GenomeBuild<u32> = runtime.block_on(parse_assembly_report_async(
+        GenomeBuildIdentifier::from_str("GRCm39")?,
+        compressed.as_slice(),
+    ))?;
+    assert_eq!(parsed.contigs().count(), 61);
+
+    Ok(())
+}
+
+#[test]
+fn cached_builds_are_loaded_once() {
+    use std::sync::Arc;
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| std::thread::spawn(|| cached::<u32>(KnownBuild::GRCh37p13)))
+        .collect();
+    let builds: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+    assert!(builds.iter().all(|build| Arc::ptr_eq(build, &builds[0])));
+    assert_eq!(builds[0].id().major_assembly(), "GRCh37");
+
+    let wide = cached::<u64>(KnownBuild::GRCh37p13);
+    assert_eq!(wide.contigs().count(), builds[0].contigs().count());
+}
+
+#[test]
+fn toy_build_covers_roles_and_styles() {
+    let build = toy::<u32>();
+
+    assert_eq!(build.id().major_assembly(), "Toy");
+    assert_eq!(
+        build.metadata().assembly_level(),
+        Some(AssemblyLevel::Chromosome)
+    );
+    let counts = build.counts_by_role();
+    assert_eq!(counts.len(), 6);
+    assert!(!counts.contains_key(&None));
+    for style in NameStyle::ALL {
+        assert!(build.contigs().all(|c| c.name_in(style).is_some()));
+    }
+    assert!(build.validate().is_valid());
+}
+
+#[test]
+fn report_accessions() {
+    let build = get_grch38_p13::<u32>();
+
+    let mt = build.contig_by_name("chrM").unwrap();
+    assert_eq!(mt.genbank_accession(), Some("J01415.2"));
+    assert_eq!(mt.refseq_accession(), Some("NC_012920.1"));
+    assert_eq!(mt.ucsc_name(), Some("chrM"));
+
+    let report = "# Sequence-Name\tSequence-Role\tAssigned-Molecule\tAssigned-Molecule-Location/Type\tGenBank-Accn\tRelationship\tRefSeq-Accn\tAssembly-Unit\tSequence-Length\tUCSC-style-name\n\
+        chr1\tassembled-molecule\t1\tChromosome\tCM000663.2\t=\tna\tPrimary Assembly\t10\tchr1\n";
+    let build: GenomeBuild<u32> = parse_assembly_report(
+        GenomeBuildIdentifier::from_str("toy").unwrap(),
+        report.as_bytes(),
+    )
+    .unwrap();
+    let chr1 = build.contig_by_name("chr1").unwrap();
+    assert_eq!(chr1.genbank_accession(), Some("CM000663.2"));
+    assert_eq!(chr1.refseq_accession(), None);
+    assert_eq!(chr1.ucsc_name(), Some("chr1"));
+}
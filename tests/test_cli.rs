@@ -0,0 +1,343 @@
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+fn dabuild(args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_dabuild"))
+        .args(args)
+        .output()
+        .expect("Run dabuild");
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).expect("UTF-8 output"),
+    )
+}
+
+#[test]
+fn info_of_bundled_build() {
+    let (success, stdout) = dabuild(&["info", "--build", "GRCh38.p13"]);
+
+    assert!(success);
+    assert!(stdout.contains("GRCh38.p13"));
+    assert!(stdout.contains("640"));
+    assert!(stdout.contains("3272116950 bp (3.27 Gb)"));
+    assert!(stdout
+        .lines()
+        .any(|line| line.trim() == "assembled-molecule      25"));
+}
+
+#[test]
+fn info_of_assembly_report() {
+    let (success, stdout) = dabuild(&[
+        "info",
+        "--build",
+        "data/GCF_000001635.27_GRCm39_assembly_report.txt",
+    ]);
+
+    assert!(success);
+    assert!(stdout.contains("61"));
+}
+
+#[test]
+fn info_of_missing_file() {
+    let (success, _) = dabuild(&["info", "--build", "no/such/file.fai"]);
+
+    assert!(!success);
+}
+
+#[test]
+fn lookup_names_of_any_style() {
+    let (success, stdout) = dabuild(&["lookup", "--build", "GRCh38.p13", "chrY", "NC_000001.11"]);
+
+    assert!(success);
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "chrY\tY\tCM000686.2,NC_000024.10,chrY\t57227415",
+            "NC_000001.11\t1\tCM000663.2,NC_000001.11,chr1\t248956422",
+        ]
+    );
+}
+
+#[test]
+fn lookup_unknown_name() {
+    let (success, stdout) = dabuild(&["lookup", "--build", "GRCh38.p13", "chr1", "chrZ"]);
+
+    assert!(!success);
+    assert_eq!(stdout.lines().count(), 1);
+}
+
+#[test]
+fn export_chrom_sizes_in_ucsc_style() {
+    let (success, stdout) = dabuild(&[
+        "export",
+        "--build",
+        "GRCh38.p13",
+        "--to",
+        "chrom.sizes",
+        "--name-style",
+        "ucsc",
+    ]);
+
+    assert!(success);
+    assert_eq!(stdout.lines().count(), 640);
+    assert!(stdout.lines().any(|line| line == "chrY\t57227415"));
+}
+
+#[test]
+fn export_vcf_header() {
+    let (success, stdout) = dabuild(&["export", "--build", "GRCh37.p13", "--to", "vcf-header"]);
+
+    assert!(success);
+    assert!(stdout.starts_with("##fileformat=VCFv4.2\n"));
+    assert!(stdout.contains("##contig=<ID=MT,length=16569,assembly=GRCh37.p13>\n"));
+    assert!(stdout.ends_with("#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n"));
+}
+
+#[test]
+fn sniff_vcf_header() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("sniff.vcf");
+    std::fs::write(
+        &path,
+        "##fileformat=VCFv4.2\n\
+        ##contig=<ID=chr1,length=249250621>\n\
+        ##contig=<ID=chrM,length=16571>\n\
+        ##contig=<ID=chrEBV,length=171823>\n\
+        #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n",
+    )
+    .expect("Write VCF");
+
+    let (success, stdout) = dabuild(&["sniff", path.to_str().unwrap()]);
+
+    assert!(success);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines[3].split_whitespace().collect::<Vec<_>>(),
+        vec!["GRCh37.p13", "1", "1", "1", "33.3%"]
+    );
+    assert_eq!(lines.last(), Some(&"Best match      GRCh37.p13"));
+}
+
+#[test]
+fn rename_vcf_to_ucsc_style() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("rename.vcf");
+    std::fs::write(
+        &path,
+        "##fileformat=VCFv4.2\n\
+        ##contig=<ID=NC_000001.11,length=248956422>\n\
+        #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n\
+        1\t10\t.\tA\tC\t.\t.\t.\n\
+        MT\t20\t.\tG\tT\t.\t.\t.\n",
+    )
+    .expect("Write VCF");
+
+    let (success, stdout) = dabuild(&[
+        "rename",
+        "--build",
+        "GRCh38.p13",
+        "--to",
+        "ucsc",
+        path.to_str().unwrap(),
+    ]);
+
+    assert!(success);
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "##fileformat=VCFv4.2",
+            "##contig=<ID=chr1,length=248956422>",
+            "#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO",
+            "chr1\t10\t.\tA\tC\t.\t.\t.",
+            "chrM\t20\t.\tG\tT\t.\t.\t.",
+        ]
+    );
+}
+
+#[test]
+fn rename_refuses_unknown_contigs() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("rename.bed");
+    std::fs::write(&path, "chr1\t0\t10\nchrEBV\t0\t10\n").expect("Write BED");
+    let path = path.to_str().unwrap();
+
+    let (success, _) = dabuild(&["rename", "-b", "GRCh38.p13", "--to", "main", path]);
+    assert!(!success);
+
+    let (success, stdout) = dabuild(&[
+        "rename",
+        "-b",
+        "GRCh38.p13",
+        "--to",
+        "main",
+        "--on-unknown",
+        "warn",
+        path,
+    ]);
+    assert!(success);
+    assert_eq!(stdout, "1\t0\t10\nchrEBV\t0\t10\n");
+}
+
+#[test]
+fn validate_bed_records() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("validate.bed");
+    std::fs::write(
+        &path,
+        "track name=test\n\
+        chr1\t0\t10\n\
+        chr2\t0\t242193530\n\
+        chr1\t100\t200\n\
+        chrEBV\t0\t10\n\
+        chr2\t10\n",
+    )
+    .expect("Write BED");
+
+    let (success, stdout) = dabuild(&["validate", "-b", "GRCh38.p13", path.to_str().unwrap()]);
+
+    assert!(!success);
+    let violations: Vec<_> = stdout
+        .lines()
+        .skip(1)
+        .map(|line| line.split('\t').take(3).collect::<Vec<_>>().join(" "))
+        .collect();
+    assert_eq!(
+        violations,
+        vec![
+            "3 out-of-bounds chr2",
+            "4 unsorted chr1",
+            "5 unknown-contig chrEBV",
+            "6 invalid-record .",
+        ]
+    );
+}
+
+#[test]
+fn validate_sorted_vcf() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("validate.vcf");
+    std::fs::write(
+        &path,
+        "##fileformat=VCFv4.2\n\
+        #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n\
+        1\t10\t.\tA\tC\t.\t.\t.\n\
+        chr1\t20\t.\tA\tC\t.\t.\t.\n\
+        MT\t16569\t.\tG\tT\t.\t.\t.\n",
+    )
+    .expect("Write VCF");
+
+    let (success, stdout) = dabuild(&["validate", "-b", "GRCh38.p13", path.to_str().unwrap()]);
+
+    assert!(success);
+    assert_eq!(stdout, "#line\tviolation\tcontig\tdetail\n");
+}
+
+#[test]
+fn diff_of_fai_and_dict() {
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let (fai, dict) = (dir.join("diff.fai"), dir.join("diff.dict"));
+    std::fs::write(
+        &fai,
+        "chr1\t100\t0\t0\t0\nchr2\t200\t0\t0\t0\nchr3\t300\t0\t0\t0\n",
+    )
+    .expect("Write FASTA index");
+    std::fs::write(
+        &dict,
+        "@SQ\tSN:chr1\tLN:100\n@SQ\tSN:chr2\tLN:250\n@SQ\tSN:chrM\tLN:16569\n",
+    )
+    .expect("Write sequence dictionary");
+
+    let (success, stdout) = dabuild(&["diff", fai.to_str().unwrap(), dict.to_str().unwrap()]);
+
+    assert!(!success);
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec![
+            "changed\tchr2\tlength 200 -> 250",
+            "removed\tchr3\tlength 300",
+            "added\tchrM\tlength 16569",
+        ]
+    );
+
+    let (success, stdout) = dabuild(&["diff", "GRCh38.p13", "GRCh38.p13"]);
+    assert!(success);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn fetch_offline_from_cache() {
+    let cache_dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("cache");
+    let cache_dir = cache_dir.to_str().unwrap();
+
+    let (success, _) = dabuild(&[
+        "fetch",
+        "--cache-dir",
+        cache_dir,
+        "--offline",
+        "GCF_000001635.26",
+    ]);
+    assert!(!success);
+
+    let cache = dabuild::fetch::BuildCache::new(cache_dir);
+    let report = std::fs::read_to_string("data/GCF_000001635.27_GRCm39_assembly_report.txt")
+        .expect("Read report");
+    let cached = cache
+        .store("GCF_000001635.27", &report)
+        .expect("Store report");
+
+    let (success, stdout) = dabuild(&[
+        "fetch",
+        "--cache-dir",
+        cache_dir,
+        "--offline",
+        "GCF_000001635.27",
+    ]);
+    assert!(success);
+    assert_eq!(stdout.trim_end(), cached.to_str().unwrap());
+}
+
+#[test]
+fn fetch_invalid_accession() {
+    let (success, _) = dabuild(&["fetch", "GRCh38"]);
+
+    assert!(!success);
+}
+
+#[test]
+fn windows_of_primary_contigs() {
+    let (success, stdout) = dabuild(&[
+        "windows",
+        "--build",
+        "GRCh38.p13",
+        "--size",
+        "10000000",
+        "--step",
+        "5000000",
+        "--primary-only",
+        "--name-style",
+        "ucsc",
+    ]);
+
+    assert!(success);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines[0], "chr1\t0\t10000000");
+    assert_eq!(lines[1], "chr1\t5000000\t15000000");
+    assert_eq!(lines.last(), Some(&"chrY\t55000000\t57227415"));
+    assert!(lines.contains(&"chrM\t0\t16569"));
+    assert!(!lines.iter().any(|line| line.contains("_random")));
+}
+
+#[test]
+fn digest_of_dict() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("digest.dict");
+    std::fs::write(
+        &path,
+        "@SQ\tSN:chr1\tLN:248956422\tM5:6aef897c3d6ff0c78aff06ac189178dd\n\
+        @SQ\tSN:chrM\tLN:16569\n",
+    )
+    .expect("Write sequence dictionary");
+
+    let (success, stdout) = dabuild(&["digest", "-b", path.to_str().unwrap()]);
+
+    assert!(success);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("fingerprint\t"));
+    assert_eq!(lines[4], "md5\tchr1\t6aef897c3d6ff0c78aff06ac189178dd");
+}
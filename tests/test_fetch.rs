@@ -0,0 +1,35 @@
+#![cfg(feature = "fetch")]
+
+use std::{error::Error, fs};
+
+use dabuild::{fetch::BuildCache, GenomeBuild};
+
+const REPORT: &str = "data/GCF_000001635.27_GRCm39_assembly_report.txt";
+
+#[test]
+fn cache_detects_corrupted_report() -> Result<(), Box<dyn Error>> {
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("corrupted-cache");
+    let cache = BuildCache::new(&dir).offline(true);
+    let accession = "GCF_000001635.27";
+
+    let path = cache.store(accession, &fs::read_to_string(REPORT)?)?;
+    assert!(cache.contains(accession));
+    let build: GenomeBuild<u32> = cache.load(accession)?;
+    assert_eq!(build.id().major_assembly(), "GRCm39");
+    assert_eq!(build.contigs().count(), 61);
+
+    fs::write(&path, "# Truncated")?;
+    assert!(!cache.contains(accession));
+    assert!(cache.load::<u32>(accession).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn offline_cache_does_not_download() {
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("empty-cache");
+    let cache = BuildCache::new(dir).offline(true);
+
+    let error = cache.fetch("GCF_000001405.40").unwrap_err();
+    assert!(error.to_string().contains("not cached"));
+}
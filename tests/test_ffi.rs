@@ -0,0 +1,56 @@
+#![cfg(feature = "ffi")]
+
+use std::{ffi::CStr, ptr};
+
+use dabuild::ffi::*;
+
+#[test]
+fn lookup_through_ffi() {
+    unsafe {
+        let build = dabuild_get_grch38_p13();
+        assert!(!build.is_null());
+        assert_eq!(dabuild_genome_build_contig_count(build), 640);
+
+        let mut length = 0u64;
+        assert_eq!(
+            dabuild_contig_length(build, c"chrY".as_ptr(), &mut length),
+            0
+        );
+        assert_eq!(length, 57_227_415);
+        assert_eq!(
+            dabuild_contig_length(build, c"chrZ".as_ptr(), &mut length),
+            -1
+        );
+
+        let mut buf = [0; 8];
+        let n = dabuild_contig_name(build, c"NC_000024.10".as_ptr(), buf.as_mut_ptr(), buf.len());
+        assert_eq!(n, 1);
+        assert_eq!(CStr::from_ptr(buf.as_ptr()), c"Y");
+
+        let n = dabuild_contig_name(build, c"chr1".as_ptr(), ptr::null_mut(), 0);
+        assert_eq!(n, 1);
+
+        dabuild_genome_build_free(build);
+    }
+}
+
+#[test]
+fn parse_assembly_report_through_ffi() {
+    unsafe {
+        let build = dabuild_parse_assembly_report(
+            c"data/GCF_000001635.27_GRCm39_assembly_report.txt".as_ptr(),
+            c"GRCm39".as_ptr(),
+            ptr::null(),
+        );
+        assert!(!build.is_null());
+        assert_eq!(dabuild_genome_build_contig_count(build), 61);
+        dabuild_genome_build_free(build);
+
+        let build = dabuild_parse_assembly_report(
+            c"no/such/file".as_ptr(),
+            c"GRCm39".as_ptr(),
+            ptr::null(),
+        );
+        assert!(build.is_null());
+    }
+}
@@ -0,0 +1,641 @@
+#![cfg(feature = "std")]
+
+use std::{error::Error, str::FromStr};
+
+use dabuild::{
+    annotation::audit_annotation,
+    builds::{get_grch38_p13, parse_assembly_report, toy, write_assembly_report},
+    formats::{
+        check_fai, check_fasta, parse_chrom_sizes, parse_cytobands, parse_dict,
+        parse_exclusion_list, parse_fai, parse_interval_list, parse_vcf_header, round_trip_report,
+        write_chrom_sizes, write_dict, write_identity_chain, write_interval_list, write_json,
+        write_ndjson, Dropped, RoundTripFormat,
+    },
+    Contig, ContigOrdering, GenomeBuild, GenomeBuildIdentifier, GenomicRegion, Karyotype,
+    NameStyle,
+};
+
+#[test]
+fn fai_keeps_file_order() -> Result<(), Box<dyn Error>> {
+    let fai = "chr2\t242193529\t0\t60\t61\nchr1\t248956422\t0\t60\t61\n";
+    let build: GenomeBuild<u32> =
+        parse_fai(GenomeBuildIdentifier::from_str("hg38")?, fai.as_bytes())?;
+
+    let contigs: Vec<_> = build.contigs().map(|c| (c.name(), *c.length())).collect();
+    assert_eq!(contigs, vec![("chr2", 242_193_529), ("chr1", 248_956_422)]);
+    assert!(build.contigs().all(|c| c.role().is_none()));
+
+    Ok(())
+}
+
+#[test]
+fn fai_with_invalid_length() {
+    let fai = "chr1\tmany\t0\t60\t61\n";
+    let build = parse_fai::<u32, _>(
+        GenomeBuildIdentifier::from_str("hg38").unwrap(),
+        fai.as_bytes(),
+    );
+
+    assert!(build.is_err());
+}
+
+#[test]
+fn dict_with_alternative_names() -> Result<(), Box<dyn Error>> {
+    let dict = "@HD\tVN:1.6\n\
+        @SQ\tSN:chr1\tLN:248956422\tM5:6aef897c3d6ff0c78aff06ac189178dd\tAN:1,NC_000001.11\n\
+        @SQ\tSN:chrM\tLN:16569\n";
+    let build: GenomeBuild<u32> =
+        parse_dict(GenomeBuildIdentifier::from_str("hg38")?, dict.as_bytes())?;
+
+    assert_eq!(build.contigs().count(), 2);
+    let contig = build.contig_by_name("NC_000001.11").unwrap();
+    assert_eq!(contig.name(), "chr1");
+    assert_eq!(
+        contig.alt_names().collect::<Vec<_>>(),
+        vec!["1", "NC_000001.11"]
+    );
+    assert_eq!(contig.length(), &248_956_422);
+    assert_eq!(contig.md5(), Some("6aef897c3d6ff0c78aff06ac189178dd"));
+    assert_eq!(build.contig_by_name("chrM").unwrap().md5(), None);
+    assert_eq!(contig.source_index(), Some(1));
+    assert_eq!(
+        build.contig_by_name("chrM").unwrap().source_index(),
+        Some(2)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dict_round_trip_in_refseq_style() -> Result<(), Box<dyn Error>> {
+    let build: GenomeBuild<u32> = get_grch38_p13();
+    let mut dict = vec![];
+    write_dict(&build, NameStyle::RefSeq, &mut dict)?;
+
+    let parsed: GenomeBuild<u32> = parse_dict(
+        GenomeBuildIdentifier::from(("GRCh38", "p13")),
+        dict.as_slice(),
+    )?;
+
+    assert_eq!(parsed.contigs().count(), 640);
+    let contig = parsed.contig_by_name("chrY").unwrap();
+    assert_eq!(contig.name(), "NC_000024.10");
+    assert_eq!(
+        contig.alt_names().collect::<Vec<_>>(),
+        vec!["Y", "CM000686.2", "chrY"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn json_escapes_names() -> Result<(), Box<dyn Error>> {
+    let build: GenomeBuild<u32> = GenomeBuild::builder()
+        .id(GenomeBuildIdentifier::from_str("toy")?)
+        .contig("a\"b", 10)
+        .build()?;
+    let mut json = vec![];
+    write_json(&build, NameStyle::Main, &mut json)?;
+
+    assert_eq!(
+        String::from_utf8(json)?,
+        "{\"schema_version\":4,\"id\":{\"major_assembly\":\"toy\",\"patch\":null},\
+        \"metadata\":{\"organism\":null,\"taxid\":null,\"submitter\":null,\
+        \"date\":null,\"assembly_level\":null,\"genome_representation\":null,\"bioproject\":null,\
+        \"biosample\":null,\"genbank_accession\":null,\"refseq_accession\":null},\
+        \"contigs\":[{\"name\":\"a\\\"b\",\"alt_names\":[],\"length\":10,\"role\":null,\"md5\":null,\
+        \"genbank\":null,\"refseq\":null,\"ucsc\":null}]}\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ndjson_writes_a_line_per_contig() -> Result<(), Box<dyn Error>> {
+    let build: GenomeBuild<u32> = GenomeBuild::builder()
+        .id(GenomeBuildIdentifier::from(("toy", "p1")))
+        .contig("chr1", 10)
+        .alias("chr1", "1")
+        .contig("chr2", 5)
+        .ordering(ContigOrdering::Insertion)
+        .build()?;
+    let mut ndjson = vec![];
+    write_ndjson(&build, NameStyle::Main, &mut ndjson)?;
+
+    assert_eq!(
+        String::from_utf8(ndjson)?,
+        "{\"major_assembly\":\"toy\",\"patch\":\"p1\",\"name\":\"chr1\",\"alt_names\":[\"1\"],\
+        \"length\":10,\"role\":null,\"md5\":null}\n\
+        {\"major_assembly\":\"toy\",\"patch\":\"p1\",\"name\":\"chr2\",\"alt_names\":[],\
+        \"length\":5,\"role\":null,\"md5\":null}\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn chrom_sizes() -> Result<(), Box<dyn Error>> {
+    let sizes = "chr1\t248956422\nchrM\t16569\n";
+    let build: GenomeBuild<u32> =
+        parse_chrom_sizes(GenomeBuildIdentifier::from_str("hg38")?, sizes.as_bytes())?;
+
+    let contigs: Vec<_> = build.contigs().map(|c| (c.name(), *c.length())).collect();
+    assert_eq!(contigs, vec![("chr1", 248_956_422), ("chrM", 16_569)]);
+
+    Ok(())
+}
+
+#[test]
+fn vcf_header_contigs() -> Result<(), Box<dyn Error>> {
+    let vcf = "##fileformat=VCFv4.2\n\
+        ##contig=<ID=chr1,length=248956422,assembly=GRCh38>\n\
+        ##INFO=<ID=DP,Number=1,Type=Integer,Description=\"Depth, total\">\n\
+        ##contig=<ID=chrM,Description=\"Mitochondria, rCRS\",length=16569>\n\
+        #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n\
+        chr1\t1\t.\tA\tC\t.\t.\t.\n";
+    let build: GenomeBuild<u32> =
+        parse_vcf_header(GenomeBuildIdentifier::from_str("hg38")?, vcf.as_bytes())?;
+
+    let contigs: Vec<_> = build.contigs().map(|c| (c.name(), *c.length())).collect();
+    assert_eq!(contigs, vec![("chr1", 248_956_422), ("chrM", 16_569)]);
+
+    Ok(())
+}
+
+#[test]
+fn vcf_header_without_contig_length() {
+    let vcf = "##fileformat=VCFv4.2\n##contig=<ID=chr1>\n";
+    let build = parse_vcf_header::<u32, _>(
+        GenomeBuildIdentifier::from_str("hg38").unwrap(),
+        vcf.as_bytes(),
+    );
+
+    assert!(build.is_err());
+}
+
+#[test]
+fn parsers_share_parse_options() {
+    use dabuild::{
+        builds::ParseOptions,
+        formats::{parse_dict_with, parse_vcf_header_with},
+    };
+
+    let vcf = "##contig=<ID=chr1,length=248956422>\n##contig=<ID=chrEBV>\n##contig=<ID=chr2,length=242193529>\n";
+    let id = GenomeBuildIdentifier::from_str("hg38").unwrap();
+
+    let build = parse_vcf_header_with::<u32, _>(
+        id.clone(),
+        vcf.as_bytes(),
+        &ParseOptions::default().lenient().chr_aliases(),
+    )
+    .unwrap();
+    assert_eq!(
+        build.contigs().map(|c| c.name()).collect::<Vec<_>>(),
+        ["chr1", "chr2"]
+    );
+    assert!(build.contig_by_name("1").is_some());
+
+    let dict = "@SQ\tSN:1\tLN:248956422\n@SQ\tSN:2\tLN:242193529\n";
+    let options = ParseOptions::default().max_contigs(1);
+    assert!(parse_dict_with::<u32, _>(id, dict.as_bytes(), &options).is_err());
+}
+
+#[cfg(feature = "bgzf")]
+#[test]
+fn bam_header() -> Result<(), Box<dyn Error>> {
+    use std::io::Write;
+
+    use dabuild::formats::parse_bam_header;
+    use flate2::{write::GzEncoder, Compression};
+
+    let text = b"@HD\tVN:1.6\n";
+    let mut bam = b"BAM\x01".to_vec();
+    bam.extend((text.len() as u32).to_le_bytes());
+    bam.extend(text);
+    bam.extend(2u32.to_le_bytes());
+    for (name, length) in [("chr1", 248_956_422u32), ("chrM", 16_569)] {
+        bam.extend((name.len() as u32 + 1).to_le_bytes());
+        bam.extend(name.as_bytes());
+        bam.push(0);
+        bam.extend(length.to_le_bytes());
+    }
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder.write_all(&bam)?;
+    let compressed = encoder.finish()?;
+
+    let build: GenomeBuild<u32> = parse_bam_header(
+        GenomeBuildIdentifier::from_str("sample")?,
+        compressed.as_slice(),
+    )?;
+
+    let contigs: Vec<_> = build.contigs().map(|c| (c.name(), *c.length())).collect();
+    assert_eq!(contigs, vec![("chr1", 248_956_422), ("chrM", 16_569)]);
+
+    // A huge name length of a truncated header, an empty name, and a name without NUL.
+    for (l_name, name) in [(u32::MAX, &b"chr1\0"[..]), (0, b""), (4, b"chr1")] {
+        let mut bam = b"BAM\x01".to_vec();
+        bam.extend(0u32.to_le_bytes());
+        bam.extend(1u32.to_le_bytes());
+        bam.extend(l_name.to_le_bytes());
+        bam.extend(name);
+        bam.extend(10u32.to_le_bytes());
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(&bam)?;
+        let compressed = encoder.finish()?;
+        let build = parse_bam_header::<u32, _>(
+            GenomeBuildIdentifier::from_str("sample")?,
+            compressed.as_slice(),
+        );
+        assert!(build.is_err(), "{l_name}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn exclusion_list_by_any_name() -> Result<(), Box<dyn Error>> {
+    let build: GenomeBuild<u32> = get_grch38_p13();
+    let list = "track name=blacklist\nchr1\t100\t200\nNC_000001.11\t150\t300\nY\n";
+    let excluded = parse_exclusion_list(&build, list.as_bytes())?;
+
+    assert_eq!(excluded.len(), 2);
+    let chr1 = build.contig_by_name("1").ok_or("Missing chr1")?;
+    let regions: Vec<_> = excluded
+        .contig_regions(chr1)
+        .iter()
+        .map(|r| (*r.start(), *r.end()))
+        .collect();
+    assert_eq!(regions, [(100, 300)]);
+    assert!(excluded.is_excluded(build.contig_by_name("chrY").ok_or("Missing chrY")?));
+
+    assert!(parse_exclusion_list(&build, "chrZ\n".as_bytes()).is_err());
+    assert!(parse_exclusion_list(&build, "chr1\t100\n".as_bytes()).is_err());
+    assert!(parse_exclusion_list(&build, "chrM\t0\t20000\n".as_bytes()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn gff3_audit_stops_at_fasta() -> Result<(), Box<dyn Error>> {
+    let build: GenomeBuild<u32> = get_grch38_p13();
+    let gff = "##gff-version 3\n\
+        ##sequence-region NC_000024.10 1 57227415\n\
+        NC_000024.10\tRefSeq\tregion\t1\t57227415\t.\t+\t.\tID=NC_000024.10:1..57227415\n\
+        NC_000024.10\tBestRefSeq\tgene\t0\t100\t.\t+\t.\tID=gene-X\n\
+        \n\
+        chrY\tBestRefSeq\tgene\t57227000\t57227416\t.\t-\t.\tID=gene-Y\n\
+        ##FASTA\n\
+        >unknown\n\
+        ACGT\n";
+
+    let audit = audit_annotation(&build, gff.as_bytes())?;
+
+    assert_eq!(audit.features(), 3);
+    assert_eq!(audit.referenced(), ["Y"]);
+    assert!(audit.missing().is_empty());
+    let lines: Vec<_> = audit.out_of_bounds().iter().map(|f| f.line).collect();
+    assert_eq!(lines, [3, 5]);
+
+    let gtf = "chr1\tHAVANA\tgene\t11869\n";
+    assert!(audit_annotation(&build, gtf.as_bytes()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn fasta_check_suggests_renames() -> Result<(), Box<dyn Error>> {
+    let build: GenomeBuild<u32> = get_grch38_p13();
+    let fasta = ">NC_012920.1 Homo sapiens mitochondrion\n\
+        GATCACAGGTCTATCACCC\n\
+        TATTAACCAC\n\
+        >chrY\n\
+        NNNN\n\
+        >phiX174\n\
+        GAGT\n";
+
+    let check = check_fasta(&build, fasta.as_bytes())?;
+
+    assert!(!check.is_consistent());
+    assert_eq!(check.unknown(), ["phiX174"]);
+    assert!(check.styles().is_empty());
+    assert_eq!(check.length_mismatches().len(), 2);
+    assert_eq!(check.length_mismatches()[0].found, 29);
+    assert_eq!(check.missing().len(), 638);
+    assert_eq!(
+        check.rename_map(NameStyle::Ucsc),
+        [("NC_012920.1".to_string(), "chrM".to_string())]
+    );
+    assert_eq!(
+        check.query_map(),
+        [
+            ("MT".to_string(), "NC_012920.1".to_string()),
+            ("Y".to_string(), "chrY".to_string())
+        ]
+    );
+
+    let fai = "NC_012920.1\t16569\t40\t60\t61\nNC_000024.10\t57227415\t16900\t60\t61\n";
+    let check = check_fai(&build, fai.as_bytes())?;
+    assert!(check.is_consistent());
+    assert_eq!(check.styles(), [NameStyle::RefSeq]);
+
+    assert!(check_fasta(&build, "ACGT\n>chr1\n".as_bytes()).is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "bgzf")]
+#[test]
+fn tabix_and_csi_names() -> Result<(), Box<dyn Error>> {
+    use std::io::Write;
+
+    use dabuild::formats::read_index_names;
+    use flate2::{write::GzEncoder, Compression};
+
+    let compress = |bytes: &[u8]| -> std::io::Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(bytes)?;
+        encoder.finish()
+    };
+    let names = b"1\0MT\0NC_001422.1\0";
+    let mut aux = vec![];
+    // `format`, `col_seq`, `col_beg`, `col_end`, `meta`, and `skip` of a VCF.
+    for value in [2u32, 1, 2, 0, u32::from(b'#'), 0] {
+        aux.extend(value.to_le_bytes());
+    }
+    aux.extend((names.len() as u32).to_le_bytes());
+    aux.extend(names);
+
+    let mut tbi = b"TBI\x01".to_vec();
+    tbi.extend(3u32.to_le_bytes());
+    tbi.extend(&aux);
+    let mut csi = b"CSI\x01".to_vec();
+    csi.extend(14u32.to_le_bytes());
+    csi.extend(5u32.to_le_bytes());
+    csi.extend((aux.len() as u32).to_le_bytes());
+    csi.extend(&aux);
+
+    let build: GenomeBuild<u32> = get_grch38_p13();
+    for index in [tbi, csi] {
+        let names = read_index_names(compress(&index)?.as_slice())?;
+        assert_eq!(names, ["1", "MT", "NC_001422.1"]);
+
+        let check = build.check_names(&names);
+        assert_eq!(check.unknown(), ["NC_001422.1"]);
+        assert_eq!(
+            check.rename_map(NameStyle::Ucsc),
+            [
+                ("1".to_string(), "chr1".to_string()),
+                ("MT".to_string(), "chrM".to_string())
+            ]
+        );
+    }
+
+    let bai = b"CSI\x01\x0e\0\0\0\x05\0\0\0\0\0\0\0";
+    assert!(read_index_names(compress(bai)?.as_slice()).is_err());
+
+    // A huge length of the names of a truncated index.
+    let mut truncated = b"TBI\x01".to_vec();
+    truncated.extend(3u32.to_le_bytes());
+    truncated.extend(&aux[..24]);
+    truncated.extend(u32::MAX.to_le_bytes());
+    truncated.extend(names);
+    assert!(read_index_names(compress(&truncated)?.as_slice()).is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "bgzf")]
+#[test]
+fn compressed_text_inputs() -> Result<(), Box<dyn Error>> {
+    use std::io::Write;
+
+    use dabuild::builds::parse_assembly_report;
+    use flate2::{write::GzEncoder, Compression};
+
+    let compress = |text: &str| -> std::io::Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(text.as_bytes())?;
+        encoder.finish()
+    };
+
+    let sizes = compress("chr1\t248956422\nchrM\t16569\n")?;
+    let build: GenomeBuild<u32> =
+        parse_chrom_sizes(GenomeBuildIdentifier::from_str("hg38")?, sizes.as_slice())?;
+    assert_eq!(build.contigs().count(), 2);
+
+    let report = std::fs::read_to_string("data/GCF_000001635.27_GRCm39_assembly_report.txt")?;
+    let build: GenomeBuild<u32> = parse_assembly_report(
+        GenomeBuildIdentifier::from_str("GRCm39")?,
+        compress(&report)?.as_slice(),
+    )?;
+    assert_eq!(build.contigs().count(), 61);
+
+    Ok(())
+}
+
+fn round_trip(
+    build: &GenomeBuild<u32>,
+    format: RoundTripFormat,
+) -> Result<GenomeBuild<u32>, Box<dyn Error>> {
+    let mut out = vec![];
+    let id = build.id().clone();
+    Ok(match format {
+        RoundTripFormat::AssemblyReport => {
+            write_assembly_report(build, NameStyle::Main, &mut out)?;
+            parse_assembly_report(id, out.as_slice())?
+        }
+        RoundTripFormat::Dict => {
+            write_dict(build, NameStyle::Main, &mut out)?;
+            parse_dict(id, out.as_slice())?
+        }
+        RoundTripFormat::ChromSizes => {
+            write_chrom_sizes(build, NameStyle::Main, &mut out)?;
+            parse_chrom_sizes(id, out.as_slice())?
+        }
+    })
+}
+
+#[test]
+fn round_trip_reports_match_parsed_builds() -> Result<(), Box<dyn Error>> {
+    let dict = "@SQ\tSN:chr1\tLN:100\tM5:ce3e31103314a704255f3cd90369ecce\tAN:1,NC_000001.11\n\
+        @SQ\tSN:chrM\tLN:50\n";
+    let from_dict: GenomeBuild<u32> =
+        parse_dict(GenomeBuildIdentifier::from_str("hg38")?, dict.as_bytes())?;
+    let toy: GenomeBuild<u32> = toy();
+    let sorted = toy.sorted(ContigOrdering::Karyotype(Karyotype::Human));
+
+    for build in [&toy, &sorted, &from_dict] {
+        for format in RoundTripFormat::ALL {
+            let report = round_trip_report(build, format);
+            let parsed = round_trip(build, format)?;
+            assert_eq!(report.is_lossless(), parsed == *build, "{format}");
+            if format == RoundTripFormat::AssemblyReport {
+                assert_eq!(parsed.metadata(), build.metadata());
+            }
+        }
+    }
+
+    let report = round_trip_report(&from_dict, RoundTripFormat::AssemblyReport);
+    assert_eq!(
+        report.dropped(),
+        [
+            Dropped::AltNames {
+                contig: "chr1".into(),
+                names: vec!["1".into()],
+            },
+            Dropped::Md5 {
+                contig: "chr1".into()
+            },
+        ]
+    );
+    assert!(round_trip_report(&from_dict, RoundTripFormat::Dict).is_lossless());
+    assert_eq!(
+        round_trip_report(&sorted, RoundTripFormat::AssemblyReport).dropped(),
+        [Dropped::Order]
+    );
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_schema_versions() -> Result<(), Box<dyn Error>> {
+    use dabuild::formats::{from_json_any_version, JSON_SCHEMA_VERSION};
+
+    let build = get_grch38_p13::<u32>();
+    let mut json = vec![];
+    write_json(&build, NameStyle::Main, &mut json)?;
+    let read: GenomeBuild<u32> = from_json_any_version(json.as_slice())?;
+    assert_eq!(read, build);
+    assert_eq!(read.metadata(), build.metadata());
+    let chr1 = read.contig_by_name("chr1").unwrap();
+    assert_eq!(chr1.genbank_accession(), Some("CM000663.2"));
+    assert_eq!(chr1.refseq_accession(), Some("NC_000001.11"));
+    assert_eq!(chr1.ucsc_name(), Some("chr1"));
+    for (read, contig) in read.contigs().zip(build.contigs()) {
+        assert_eq!(read.genbank_accession(), contig.genbank_accession());
+        assert_eq!(read.refseq_accession(), contig.refseq_accession());
+        assert_eq!(read.ucsc_name(), contig.ucsc_name());
+    }
+
+    // Version 3 had no report names, hence they are looked up among the names of the contigs with a role.
+    let v3 = r#"{"schema_version":3,"id":{"major_assembly":"toy","patch":null},"metadata":{},"contigs":[
+        {"name":"1","alt_names":["CM000663.2","NC_000001.11","chr1"],"length":10,"role":"assembled-molecule","md5":null},
+        {"name":"chr2","alt_names":["2"],"length":20,"role":null,"md5":null}]}"#;
+    let read: GenomeBuild<u32> = from_json_any_version(v3.as_bytes())?;
+    let chr1 = read.contig_by_name("1").unwrap();
+    assert_eq!(chr1.genbank_accession(), Some("CM000663.2"));
+    assert_eq!(chr1.refseq_accession(), Some("NC_000001.11"));
+    assert_eq!(chr1.ucsc_name(), Some("chr1"));
+    assert_eq!(read.contig_by_name("chr2").unwrap().ucsc_name(), None);
+
+    let v1 = r#"{"id":{"major_assembly":"toy","patch":"p1"},"contigs":[
+        {"name":"2","alt_names":["chr2"],"length":20,"role":"assembled-molecule","md5":null},
+        {"name":"1","alt_names":[],"length":10,"role":null,"md5":"ce3e31103314a704255f3cd90369ecce"}]}"#;
+    let read: GenomeBuild<u32> = from_json_any_version(v1.as_bytes())?;
+    let names: Vec<_> = read.contigs().map(|c| c.name()).collect();
+    assert_eq!(names, ["2", "1"]);
+    assert_eq!(read.id().patch(), Some("p1"));
+    assert!(read.contig_by_name("chr2").unwrap().role().is_some());
+    assert!(read.contig_by_name("1").unwrap().md5().is_some());
+
+    let future = format!(
+        r#"{{"schema_version":{},"id":{{"major_assembly":"toy"}},"contigs":[]}}"#,
+        JSON_SCHEMA_VERSION + 1
+    );
+    assert!(from_json_any_version::<u32, _>(future.as_bytes()).is_err());
+    Ok(())
+}
+
+#[test]
+fn identity_chain_of_shared_contigs() -> Result<(), Box<dyn Error>> {
+    let source: GenomeBuild<u32> = toy();
+    let target = GenomeBuild::new(
+        GenomeBuildIdentifier::from_str("target")?,
+        [
+            Contig::new("chr1", &["1"], 1000u32).unwrap(),
+            Contig::new("chr2", &["2"], 900).unwrap(),
+            Contig::new("mito", &["NC_900005.1"], 165).unwrap(),
+        ],
+    );
+
+    let mut out = vec![];
+    write_identity_chain(&source, &target, NameStyle::Main, &mut out)?;
+    assert_eq!(
+        String::from_utf8(out)?,
+        "chain 1000 1 1000 + 0 1000 chr1 1000 + 0 1000 1\n1000\n\n\
+         chain 165 MT 165 + 0 165 mito 165 + 0 165 2\n165\n\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn cytoband_regions() -> Result<(), Box<dyn Error>> {
+    let build: GenomeBuild<u32> = toy();
+    let bands = "\
+chr1\t0\t100\tp12\tgneg
+chr1\t100\t200\tp11\tacen
+chr1\t200\t300\tq11\tacen
+chr1\t300\t500\tq21.1\tgpos50
+chr1\t500\t600\tq21.2\tgneg
+chr1\t600\t1000\tq3\tgpos100
+";
+    let cytobands = parse_cytobands(&build, bands.as_bytes())?;
+    assert_eq!(cytobands.len(), 6);
+    assert_eq!(cytobands.contig_bands("1")[2].name(), "q11");
+
+    let band = |text| {
+        build
+            .parse_region_with_bands(text, &cytobands)
+            .map(|r| (*r.start(), *r.end()))
+    };
+    assert_eq!(band("1p"), Ok((0, 200)));
+    assert_eq!(band("chr1q21"), Ok((300, 600)));
+    assert_eq!(band("1q21.2"), Ok((500, 600)));
+    assert_eq!(band("1q2"), Ok((300, 600)));
+    assert_eq!(band("1:1-10"), Ok((0, 10)));
+    assert!(band("1q4").is_err());
+    assert!(band("2p").is_err());
+
+    let chr1 = build.contig_by_name("chr1").unwrap();
+    let region = |start, end| GenomicRegion::new(chr1, start, end).unwrap();
+    assert_eq!(
+        cytobands.band_at(chr1, &199).map(|b| b.stain()),
+        Some("acen")
+    );
+    assert_eq!(cytobands.iscn_position(chr1, 0).as_deref(), Some("1p12(1)"));
+    assert_eq!(
+        cytobands.iscn(&region(300, 500)).as_deref(),
+        Some("1q21.1(301_500)")
+    );
+    assert_eq!(
+        cytobands.iscn(&region(150, 1000)).as_deref(),
+        Some("1p11q3(151_1,000)")
+    );
+    let chr2 = build.contig_by_name("2").unwrap();
+    assert_eq!(cytobands.iscn_position(chr2, 10), None);
+
+    assert!(parse_cytobands(&build, "chr9\t0\t10\tp1\tgneg\n".as_bytes()).is_err());
+    assert!(parse_cytobands(&build, "chr1\t0\t2000\tp1\tgneg\n".as_bytes()).is_err());
+    Ok(())
+}
+
+#[test]
+fn interval_list_round_trip() -> Result<(), Box<dyn Error>> {
+    let build: GenomeBuild<u32> = toy();
+    let shards = build.partition(3);
+    for shard in &shards {
+        let mut out = vec![];
+        write_interval_list(&build, NameStyle::Ucsc, shard, &mut out)?;
+        assert_eq!(&parse_interval_list(&build, out.as_slice())?, shard);
+    }
+
+    let header = "@HD\tVN:1.6\n@SQ\tSN:chr1\tLN:1000\n";
+    let parse = |list: &str| parse_interval_list(&build, list.as_bytes());
+    assert!(parse(&format!("{header}chr1\t1\t1000\t+\t.\n")).is_ok());
+    assert!(parse("@SQ\tSN:chr1\tLN:999\n").is_err());
+    assert!(parse("@SQ\tSN:chr9\tLN:1000\n").is_err());
+    assert!(parse(&format!("{header}chr1\t0\t10\t+\t.\n")).is_err());
+    assert!(parse(&format!("{header}chr1\t1\t1001\t+\t.\n")).is_err());
+    assert!(parse(&format!("{header}chr1\t1\t10\n")).is_err());
+    Ok(())
+}
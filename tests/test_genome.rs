@@ -1,4 +1,11 @@
-use dabuild::Contig;
+use std::str::FromStr;
+
+use dabuild::{
+    builds::KnownBuild, partition_regions, AltPlacement, AltPlacements, Browser, BuildProblem,
+    ConflictPolicy, Contig, ContigKey, ContigMap, ContigOrdering, GapHandling, GenomeBuild,
+    GenomeBuildIdentifier, GenomicRegion, IndexKind, IterationOrder, Karyotype, Liftability,
+    NameStyle, RegionSet, SequenceRole, Sex, SharedGenomeBuild, SpikeIn, Strand,
+};
 
 #[test]
 fn contig_basics() {
@@ -11,4 +18,1085 @@ fn contig_basics() {
         vec!["CM000663.2", "NC_000001.11", "chr1"]
     );
     assert_eq!(contig.length(), &10u8);
+    assert_eq!(contig.length_copied() - 1, 9);
+}
+
+fn build_of(id: &str, contigs: &[(&str, &[&str], u32)]) -> GenomeBuild<u32> {
+    GenomeBuild::new(
+        GenomeBuildIdentifier::from_str(id).unwrap(),
+        contigs
+            .iter()
+            .map(|&(name, alt_names, length)| Contig::new(name, alt_names, length).unwrap()),
+    )
+}
+
+#[test]
+fn merge_with_conflict_policy() {
+    let ours = build_of("ours", &[("1", &["chr1"], 100), ("2", &["chr2"], 50)]);
+    let theirs = build_of("theirs", &[("chr2", &[], 60), ("phiX", &[], 10)]);
+
+    assert!(ours.merge(&theirs, ConflictPolicy::Error).is_err());
+
+    let merged = ours.merge(&theirs, ConflictPolicy::PreferSelf).unwrap();
+    assert_eq!(merged.id().major_assembly(), "ours");
+    let contigs: Vec<_> = merged.contigs().map(|c| (c.name(), *c.length())).collect();
+    assert_eq!(contigs, vec![("1", 100), ("2", 50), ("phiX", 10)]);
+
+    let merged = ours.merge(&theirs, ConflictPolicy::PreferOther).unwrap();
+    let contigs: Vec<_> = merged.contigs().map(|c| (c.name(), *c.length())).collect();
+    assert_eq!(contigs, vec![("1", 100), ("chr2", 60), ("phiX", 10)]);
+}
+
+#[test]
+fn builder_reports_duplicates() {
+    let build = GenomeBuild::builder()
+        .id(GenomeBuildIdentifier::from_str("toy").unwrap())
+        .contig("1", 100u32)
+        .contig("2", 50)
+        .alias("2", "1")
+        .build();
+
+    assert!(build.is_err());
+    assert!(build.unwrap_err().contains("\"1\""));
+}
+
+#[test]
+fn builder_ignores_repeated_aliases() {
+    let build = GenomeBuild::builder()
+        .id(GenomeBuildIdentifier::from_str("toy").unwrap())
+        .contig("1", 100u32)
+        .alias("1", "chr1")
+        .alias("chr1", "chr1")
+        .alias("1", "1")
+        .build()
+        .unwrap();
+
+    let contig = build.contig_by_name("chr1").unwrap();
+    assert_eq!(contig.alt_names().count(), 1);
+    assert!(build.validate().problems().is_empty());
+}
+
+#[test]
+fn builder_reports_empty_build() {
+    let build = GenomeBuild::<u32>::builder()
+        .id(GenomeBuildIdentifier::from_str("toy").unwrap())
+        .build();
+
+    assert!(build.is_err());
+}
+
+#[test]
+fn builder_reports_alias_of_unknown_contig() {
+    let build = GenomeBuild::builder()
+        .id(GenomeBuildIdentifier::from_str("toy").unwrap())
+        .contig("1", 100u32)
+        .alias("2", "chr2")
+        .build();
+
+    assert!(build.is_err());
+}
+
+#[test]
+fn overlapping_windows() {
+    let build = build_of("toy", &[("1", &[], 25), ("2", &[], 5)]);
+
+    let windows: Vec<_> = build
+        .windows(10, 5)
+        .map(|w| (w.contig().name().to_string(), *w.start(), *w.end()))
+        .collect();
+
+    assert_eq!(
+        windows,
+        [
+            ("1", 0, 10),
+            ("1", 5, 15),
+            ("1", 10, 20),
+            ("1", 15, 25),
+            ("1", 20, 25),
+            ("2", 0, 5),
+        ]
+        .map(|(name, start, end)| (name.to_string(), start, end))
+    );
+}
+
+#[test]
+fn relabel_build_keeps_contigs() {
+    let build = build_of("unknown", &[("1", &["chr1"], 100)]);
+    let contigs_before: Vec<_> = build.contigs().map(|c| c as *const _).collect();
+
+    let mut labeled = build.with_id(GenomeBuildIdentifier::from(("GRCh38", "p13")));
+    assert_eq!(labeled.id().major_assembly(), "GRCh38");
+    assert_eq!(labeled.id().patch(), Some("p13"));
+
+    labeled.set_id(GenomeBuildIdentifier::from_str("hg38").unwrap());
+    assert_eq!(labeled.id().major_assembly(), "hg38");
+    assert_eq!(labeled.id().patch(), None);
+
+    let contigs_after: Vec<_> = labeled.contigs().map(|c| c as *const _).collect();
+    assert_eq!(contigs_before, contigs_after);
+    assert!(labeled.contig_by_name("chr1").is_some());
+}
+
+#[test]
+fn validate_reports_problems() {
+    let build = build_of(
+        "toy",
+        &[
+            ("1", &["CM000663.2"], 100),
+            ("2", &["chr2", "CM000663.2"], 100),
+            ("3", &["chr2"], 0),
+        ],
+    );
+
+    let report = build.validate();
+    assert!(!report.is_valid());
+    assert_eq!(
+        report.problems(),
+        [
+            BuildProblem::DuplicateName {
+                name: "CM000663.2".into(),
+                contigs: vec!["1".into(), "2".into()],
+            },
+            BuildProblem::DuplicateName {
+                name: "chr2".into(),
+                contigs: vec!["2".into(), "3".into()],
+            },
+            BuildProblem::ZeroLength { contig: "3".into() },
+            BuildProblem::MissingAccession { contig: "3".into() },
+        ]
+    );
+
+    let grch38: GenomeBuild<u32> = dabuild::builds::get_grch38_p13();
+    assert!(grch38.validate().is_valid());
+}
+
+#[test]
+fn validate_lengths_flags_implausible_contigs() {
+    // The lengths of a hand-made chrom.sizes, with chr2 in bits.
+    let build = build_of(
+        "hand-made",
+        &[("chr1", &[], 248_956_422), ("chr2", &[], 242_193_529 * 2)],
+    );
+
+    let report = build.validate_lengths(Karyotype::Human.max_contig_length());
+    assert_eq!(
+        report.problems(),
+        [BuildProblem::ImplausibleLength {
+            contig: "chr2".into(),
+            length: 484_387_058,
+            max: 300_000_000,
+        }]
+    );
+    assert!(build.validate_lengths(u64::MAX).is_valid());
+}
+
+#[test]
+fn karyotype_ordering() {
+    let build = build_of(
+        "GRCm39",
+        &[
+            ("chrUn_JH584304", &[], 5),
+            ("chrX", &[], 10),
+            ("chr10", &[], 10),
+            ("chrM", &[], 2),
+            ("chr2", &[], 10),
+        ],
+    );
+    let karyotype = build.karyotype().unwrap();
+    assert_eq!(karyotype, Karyotype::Mouse);
+
+    let names: Vec<_> = build
+        .sorted(ContigOrdering::Karyotype(karyotype))
+        .contigs()
+        .map(|c| c.name().to_string())
+        .collect();
+    assert_eq!(names, ["chr2", "chr10", "chrX", "chrM", "chrUn_JH584304"]);
+}
+
+#[test]
+fn explicit_iteration_order() {
+    let build = build_of(
+        "GRCm39",
+        &[
+            ("chrUn_JH584304", &[], 5),
+            ("chrX", &[], 10),
+            ("chr10", &[], 10),
+            ("chrM", &[], 2),
+            ("chr2", &[], 12),
+        ],
+    );
+    assert_eq!(build.ordering(), ContigOrdering::Lexicographic);
+
+    let names = |order| -> Vec<_> { build.contigs_in(order).map(|c| c.name()).collect() };
+    assert_eq!(
+        names(IterationOrder::Build),
+        ["chr10", "chr2", "chrM", "chrUn_JH584304", "chrX"]
+    );
+    assert_eq!(
+        names(IterationOrder::Karyotype(Karyotype::Mouse)),
+        ["chr2", "chr10", "chrX", "chrM", "chrUn_JH584304"]
+    );
+    // The ties keep the build order.
+    assert_eq!(
+        names(IterationOrder::Length),
+        ["chr2", "chr10", "chrX", "chrUn_JH584304", "chrM"]
+    );
+
+    let sorted = build.sorted(ContigOrdering::Karyotype(Karyotype::Mouse));
+    assert_eq!(
+        sorted.ordering(),
+        ContigOrdering::Karyotype(Karyotype::Mouse)
+    );
+    let subset = sorted.subset(["chrX", "chr2"]).unwrap();
+    assert_eq!(
+        subset.ordering(),
+        ContigOrdering::Karyotype(Karyotype::Mouse)
+    );
+    let merged = subset.merge(&build, ConflictPolicy::PreferSelf).unwrap();
+    assert_eq!(merged.ordering(), ContigOrdering::Insertion);
+}
+
+#[test]
+fn region_set_merges_and_masks() {
+    let build = build_of("toy", &[("1", &[], 100), ("2", &[], 50)]);
+    let (one, two) = (
+        build.contig_by_name("1").unwrap(),
+        build.contig_by_name("2").unwrap(),
+    );
+    let region = |contig, start, end| GenomicRegion::new(contig, start, end).unwrap();
+
+    let mut set: RegionSet<u32> = [
+        region(one, 40, 50),
+        region(one, 10, 20),
+        region(one, 20, 30),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(set.len(), 2);
+
+    set.insert(region(one, 25, 45));
+    let regions: Vec<_> = set.regions().map(|r| (*r.start(), *r.end())).collect();
+    assert_eq!(regions, [(10, 50)]);
+
+    assert!(set.overlaps(&region(one, 0, 11)));
+    assert!(!set.overlaps(&region(one, 0, 10)));
+    assert!(!set.overlaps(&region(two, 0, 50)));
+
+    let unmasked: Vec<_> = set
+        .mask(&region(one, 0, 100))
+        .iter()
+        .map(|r| (*r.start(), *r.end()))
+        .collect();
+    assert_eq!(unmasked, [(0, 10), (50, 100)]);
+    assert!(set.mask(&region(one, 15, 45)).is_empty());
+
+    assert!(!set.is_excluded(one));
+    set.insert_contig(two);
+    assert!(set.is_excluded(two));
+    assert_eq!(set.contig_regions(two).len(), 1);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_windows_match_sequential() {
+    use rayon::prelude::*;
+
+    let build = build_of("toy", &[("1", &[], 25), ("2", &[], 5), ("3", &[], 17)]);
+
+    let windows: Vec<_> = build.windows(4, 3).collect();
+    let par_windows: Vec<_> = build.par_windows(4, 3).collect();
+
+    assert_eq!(windows, par_windows);
+    assert_eq!(build.par_contigs().count(), 3);
+}
+
+#[test]
+fn builder_with_sorted_index() {
+    let build = GenomeBuild::builder()
+        .id(GenomeBuildIdentifier::from_str("toy").unwrap())
+        .contig("2", 50u32)
+        .alias("2", "chr2")
+        .contig("1", 100)
+        .alias("1", "chr1")
+        .index(IndexKind::Sorted)
+        .build()
+        .unwrap();
+
+    assert_eq!(build.contig_by_name("chr1").map(|c| c.name()), Some("1"));
+    assert_eq!(build.contig_by_name("2").map(|c| c.name()), Some("2"));
+    assert!(build.contig_by_name("chr3").is_none());
+
+    let idx = build.contig_index_by_name("chr1").unwrap();
+    assert_eq!(build.contig_at(idx).map(|c| c.name()), Some("1"));
+    assert_eq!(build.contig_index_by_name("chr3"), None);
+    assert!(build.contig_at(build.contigs().count()).is_none());
+}
+
+#[test]
+fn region_cmp_follows_build_order() {
+    let build = build_of("toy", &[("1", &["chr1"], 100), ("2", &["chr2"], 50)])
+        .sorted(ContigOrdering::Karyotype(Karyotype::Human));
+    let cmp = build.region_cmp();
+
+    assert_eq!(cmp.rank("chr2"), Some(1));
+    assert_eq!(cmp.rank("chrUn"), None);
+
+    let mut regions = vec![
+        ("chrUn", 0, 1),
+        ("2", 10, 20),
+        ("chr1", 5, 6),
+        ("chr2", 10, 15),
+    ];
+    regions.sort_by(|&l, &r| cmp.compare(l, r));
+    assert_eq!(
+        regions,
+        [
+            ("chr1", 5, 6),
+            ("chr2", 10, 15),
+            ("2", 10, 20),
+            ("chrUn", 0, 1)
+        ]
+    );
+
+    let (one, two) = (
+        build.contig_by_name("1").unwrap(),
+        build.contig_by_name("2").unwrap(),
+    );
+    let region = |contig, start, end| GenomicRegion::new(contig, start, end).unwrap();
+    let keys: std::collections::BTreeMap<_, _> = [region(two, 0, 5), region(one, 10, 20)]
+        .into_iter()
+        .map(|r| ((cmp.rank(r.contig().name()), *r.start()), r))
+        .collect();
+    assert_eq!(
+        keys.values().map(|r| r.contig().name()).collect::<Vec<_>>(),
+        ["1", "2"]
+    );
+    assert!(cmp
+        .compare_regions(&region(one, 10, 20), &region(two, 0, 5))
+        .is_lt());
+}
+
+#[test]
+fn approx_memory_usage_grows_with_names() {
+    let build = build_of("toy", &[("1", &[], 100), ("2", &[], 50)]);
+    let aliased = build_of(
+        "toy",
+        &[
+            ("1", &["chr1", "NC_000001.11"], 100),
+            ("2", &["chr2", "NC_000002.12"], 50),
+        ],
+    );
+    assert!(build.approx_memory_usage() > 0);
+    assert!(build.approx_memory_usage() < aliased.approx_memory_usage());
+
+    let grch38: GenomeBuild<u32> = dabuild::builds::get_grch38_p13();
+    let names: usize = grch38
+        .contigs()
+        .flat_map(|c| std::iter::once(c.name()).chain(c.alt_names()))
+        .map(str::len)
+        .sum();
+    assert!(grch38.approx_memory_usage() > names);
+}
+
+#[test]
+fn shared_build_across_threads() {
+    let build: SharedGenomeBuild<u32> = KnownBuild::GRCh38p13.load_shared();
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let build = SharedGenomeBuild::clone(&build);
+            std::thread::spawn(move || build.contig_by_name("chrY").map(|c| c.name().to_string()))
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap().as_deref(), Some("Y"));
+    }
+    assert_eq!(std::sync::Arc::strong_count(&build), 1);
+}
+
+#[test]
+fn add_alias_updates_index() {
+    for kind in [IndexKind::default(), IndexKind::Sorted] {
+        let mut build = GenomeBuild::builder()
+            .id(GenomeBuildIdentifier::from_str("toy").unwrap())
+            .contig("1", 100u32)
+            .contig("2", 50)
+            .md5("2", "ce3e31103314a704255f3cd90369ecce")
+            .index(kind)
+            .build()
+            .unwrap();
+        let snapshot = build.clone();
+
+        build.add_alias("2", "chr2").unwrap();
+        build.add_alias("chr2", "NC_000002.12").unwrap();
+        assert!(build.add_alias("3", "chr3").is_err());
+        assert!(build.add_alias("1", "chr2").is_err());
+
+        let two = build.contig_by_name("NC_000002.12").unwrap();
+        assert_eq!(
+            two.alt_names().collect::<Vec<_>>(),
+            ["chr2", "NC_000002.12"]
+        );
+        assert_eq!(two.md5(), Some("ce3e31103314a704255f3cd90369ecce"));
+        assert_eq!(build.contig_by_name("1").map(|c| c.name()), Some("1"));
+        // The clones keep their contigs.
+        assert!(snapshot.contig_by_name("chr2").is_none());
+
+        let other = build_of(
+            "other",
+            &[("chr2", &[], 60), ("phiX", &["NC_001422.1"], 10)],
+        );
+        let merged = build.merge(&other, ConflictPolicy::PreferSelf).unwrap();
+        assert_eq!(merged.contigs().count(), 3);
+        assert_eq!(merged.contig_by_name("chr2").map(|c| c.name()), Some("2"));
+        assert_eq!(
+            merged.contig_by_name("NC_001422.1").map(|c| c.name()),
+            Some("phiX")
+        );
+    }
+
+    let mut grch38: GenomeBuild<u32> = dabuild::builds::get_grch38_p13();
+    grch38.add_alias("MT", "chrMT").unwrap();
+    assert_eq!(grch38.contig_by_name("chrMT").map(|c| c.name()), Some("MT"));
+    assert_eq!(grch38.contig_by_name("chrX").map(|c| c.name()), Some("X"));
+}
+
+#[test]
+fn contigs_by_length() {
+    let build = build_of(
+        "toy",
+        &[
+            ("1", &[], 100),
+            ("2", &[], 50),
+            ("3", &[], 50),
+            ("MT", &[], 16),
+        ],
+    );
+
+    let names: Vec<_> = build.contigs_with_length(&50).map(|c| c.name()).collect();
+    assert_eq!(names, ["2", "3"]);
+    assert!(build.contig_by_unique_length(&50).is_none());
+    assert_eq!(
+        build.contig_by_unique_length(&16).map(|c| c.name()),
+        Some("MT")
+    );
+    assert!(build.contig_by_unique_length(&17).is_none());
+}
+
+#[test]
+fn detect_name_style_of_user_names() {
+    let build = build_of(
+        "toy",
+        &[
+            ("chr1", &["1", "NC_000001.11"], 100),
+            ("chr2", &["2", "NC_000002.12"], 50),
+        ],
+    );
+
+    // The main names are UCSC-style, hence the tie goes to the UCSC style.
+    let detection = build.detect_name_style(["chr1", "chr2"]);
+    assert_eq!(detection.style(), Some(NameStyle::Ucsc));
+    assert_eq!(detection.count(NameStyle::Main), 2);
+    assert_eq!(detection.confidence(), 1.);
+    assert_eq!(detection.common_prefix(), "chr");
+
+    let detection = build.detect_name_style(["NC_000001.11", "NC_000003.12", "1"]);
+    assert_eq!(detection.style(), Some(NameStyle::RefSeq));
+    assert_eq!(detection.total(), 3);
+    assert_eq!(detection.common_prefix(), "");
+
+    let detection = build.detect_name_style(["contig_1", "contig_2"]);
+    assert_eq!(detection.style(), None);
+    assert_eq!(detection.confidence(), 0.);
+    assert_eq!(detection.common_prefix(), "contig_");
+    assert_eq!(build.detect_name_style(Vec::<&str>::new()).total(), 0);
+}
+
+#[test]
+fn plan_rename_reports_collisions() {
+    let build = build_of(
+        "toy",
+        &[
+            ("1", &["chr1"], 100),
+            ("M", &["chrM"], 16),
+            ("MT", &["chrM_alt"], 16),
+            ("chrUn", &[], 10),
+            ("Un", &[], 10),
+        ],
+    );
+    let plan = build.plan_rename(NameStyle::Main, NameStyle::Ucsc).unwrap();
+    assert_eq!(plan.get("1"), Some("chr1"));
+    assert_eq!(plan.get("Un"), Some("Un"));
+    assert_eq!(plan.kept(), ["Un"]);
+    let renames: Vec<_> = plan.renames().collect();
+    assert_eq!(renames, [("1", "chr1"), ("M", "chrM"), ("MT", "chrM_alt")]);
+
+    let build = build_of(
+        "toy",
+        &[("M", &["chrM"], 16), ("MT", &[], 16), ("chrM", &[], 16)],
+    );
+    let error = build
+        .plan_rename(NameStyle::Main, NameStyle::Ucsc)
+        .unwrap_err();
+    assert!(error.contains(r#""M" and "chrM" would both be renamed to "chrM""#));
+}
+
+#[test]
+fn liftability_between_builds() {
+    let md5 = "0123456789abcdef0123456789abcdef";
+    let source = build_of(
+        "source",
+        &[
+            ("1", &["chr1"], 100),
+            ("2", &[], 50),
+            ("3", &[], 30),
+            ("4", &[], 40),
+        ],
+    );
+    let mut contigs: Vec<_> = source.contigs().cloned().collect();
+    contigs[3] = contigs[3].clone().with_md5(md5).unwrap();
+    let source = GenomeBuild::new(source.id().clone(), contigs);
+    let target = GenomeBuild::new(
+        GenomeBuildIdentifier::from_str("target").unwrap(),
+        [
+            Contig::new("chr1", &["1"], 100u32).unwrap(),
+            Contig::new("chr2", &["2"], 60).unwrap(),
+            Contig::new("four", &[] as &[&str], 40)
+                .unwrap()
+                .with_md5(md5)
+                .unwrap(),
+        ],
+    );
+
+    assert_eq!(
+        source.liftability("chr1", &target),
+        Some(Liftability::Identical)
+    );
+    assert_eq!(
+        source.liftability("2", &target),
+        Some(Liftability::RequiresChain)
+    );
+    assert_eq!(
+        source.liftability("3", &target),
+        Some(Liftability::NotPresent)
+    );
+    assert_eq!(
+        source.liftability("4", &target),
+        Some(Liftability::RenamedOnly)
+    );
+    assert_eq!(source.liftability("5", &target), None);
+}
+
+#[test]
+fn project_alt_regions_to_primary() {
+    let build = build_of(
+        "toy",
+        &[("1", &[], 1000), ("1_alt", &[], 120), ("2", &[], 10)],
+    );
+    let contig = |name| build.contig_by_name(name).unwrap();
+    let region = |name, start, end| GenomicRegion::new(contig(name), start, end).unwrap();
+
+    let mut placements = AltPlacements::new();
+    placements.insert(AltPlacement::new(
+        region("1_alt", 10, 110),
+        region("1", 100, 300),
+        true,
+    ));
+    assert_eq!(placements.len(), 1);
+
+    // The reverse strand placement flips the region.
+    let projected = placements
+        .project_to_primary(&region("1_alt", 10, 20))
+        .unwrap();
+    assert_eq!((*projected.start(), *projected.end()), (280, 300));
+    // The region is clipped to the aligned part.
+    let projected = placements
+        .project_to_primary(&region("1_alt", 0, 60))
+        .unwrap();
+    assert_eq!((*projected.start(), *projected.end()), (200, 300));
+
+    assert!(placements
+        .project_to_primary(&region("1_alt", 112, 120))
+        .is_none());
+    assert!(placements.project_to_primary(&region("2", 0, 5)).is_none());
+}
+
+#[test]
+fn windows_around_gaps() {
+    let build = build_of("toy", &[("1", &[], 30), ("2", &[], 10)]);
+    let contig = |name| build.contig_by_name(name).unwrap();
+    let gaps: RegionSet<u32> = [
+        GenomicRegion::new(contig("1"), 12, 14).unwrap(),
+        GenomicRegion::new(contig("1"), 16, 18).unwrap(),
+        GenomicRegion::new(contig("2"), 0, 10).unwrap(),
+    ]
+    .into_iter()
+    .collect();
+
+    let windows = |handling| {
+        build
+            .windows_around_gaps(10, 5, &gaps, handling)
+            .map(|w| (w.contig().name().to_string(), *w.start(), *w.end()))
+            .collect::<Vec<_>>()
+    };
+    let skipped = windows(GapHandling::Skip);
+    assert_eq!(
+        skipped,
+        [
+            ("1".to_string(), 0, 10),
+            ("1".to_string(), 20, 30),
+            ("1".to_string(), 25, 30)
+        ]
+    );
+    let trimmed: Vec<_> = build
+        .windows_around_gaps(10, 10, &gaps, GapHandling::Trim)
+        .map(|w| (*w.start(), *w.end()))
+        .collect();
+    assert_eq!(trimmed, [(0, 10), (10, 12), (14, 16), (18, 20), (20, 30)]);
+}
+
+#[test]
+fn parse_regions() {
+    let build = build_of("toy", &[("1", &["chr1"], 1000), ("HLA:1", &[], 10)]);
+    let parsed = |text| {
+        build
+            .parse_region(text)
+            .map(|r| (r.contig().name().to_string(), *r.start(), *r.end()))
+    };
+
+    assert_eq!(parsed("chr1"), Ok(("1".to_string(), 0, 1000)));
+    assert_eq!(parsed("chr1:101"), Ok(("1".to_string(), 100, 1000)));
+    assert_eq!(parsed(" 1:1-1,000 "), Ok(("1".to_string(), 0, 1000)));
+    assert_eq!(parsed("HLA:1"), Ok(("HLA:1".to_string(), 0, 10)));
+    assert_eq!(parsed("HLA:1:2-3"), Ok(("HLA:1".to_string(), 1, 3)));
+
+    assert!(parsed("chr2:1-10").unwrap_err().contains("Unknown contig"));
+    assert!(parsed("chr1:0-10").is_err());
+    assert!(parsed("chr1:20-10").is_err());
+    assert!(parsed("chr1:1-1001").is_err());
+}
+
+#[test]
+fn browser_urls() {
+    let build = build_of("hg19", &[("chrM", &[], 100), ("HLA-A*01:01", &[], 10)]);
+    let region = |name, start, end| {
+        GenomicRegion::new(build.contig_by_name(name).unwrap(), start, end).unwrap()
+    };
+
+    assert_eq!(
+        region("chrM", 10, 10)
+            .browser_url(Browser::Ensembl, build.id())
+            .as_deref(),
+        Some("https://grch37.ensembl.org/Homo_sapiens/Location/View?r=MT:11-11")
+    );
+    assert_eq!(
+        region("HLA-A*01:01", 0, 10)
+            .browser_url(Browser::Ucsc, build.id())
+            .as_deref(),
+        Some("https://genome.ucsc.edu/cgi-bin/hgTracks?db=hg19&position=HLA-A%2A01%3A01:1-10")
+    );
+
+    let id = GenomeBuildIdentifier::from_str("T2T-CHM13").unwrap();
+    assert_eq!(region("chrM", 0, 10).browser_url(Browser::Igv, &id), None);
+    assert_eq!("IGV".parse(), Ok(Browser::Igv));
+}
+
+#[test]
+fn flanks_are_clamped_to_contig() {
+    let build = build_of("toy", &[("1", &[], 100)]);
+    let contig = build.contig_by_name("1").unwrap();
+    let span = |region: GenomicRegion<u32>| (*region.start(), *region.end());
+    let gene = GenomicRegion::new(contig, 20, 90).unwrap();
+
+    assert_eq!(span(gene.upstream(5, Strand::Forward)), (15, 20));
+    assert_eq!(span(gene.upstream(50, Strand::Forward)), (0, 20));
+    assert_eq!(span(gene.upstream(50, Strand::Reverse)), (90, 100));
+    assert_eq!(span(gene.downstream(5, Strand::Forward)), (90, 95));
+    assert_eq!(span(gene.downstream(u32::MAX, Strand::Forward)), (90, 100));
+    assert_eq!(span(gene.downstream(5, Strand::Reverse)), (15, 20));
+
+    let end = GenomicRegion::new(contig, 100, 100).unwrap();
+    assert_eq!(span(end.downstream(5, Strand::Forward)), (100, 100));
+    assert_eq!("-".parse(), Ok(Strand::Reverse));
+}
+
+#[test]
+fn region_set_by_contig() {
+    let build: GenomeBuild<u32> = GenomeBuild::builder()
+        .id(GenomeBuildIdentifier::from_str("toy").unwrap())
+        .contig("b", 100)
+        .contig("a", 100)
+        .ordering(ContigOrdering::Insertion)
+        .build()
+        .unwrap();
+    let other = build_of("other", &[("z", &[], 10)]);
+    let region = |contig: &Contig<u32>, start, end| GenomicRegion::new(contig, start, end).unwrap();
+    let (a, b, z) = (
+        build.contig_by_name("a").unwrap(),
+        build.contig_by_name("b").unwrap(),
+        other.contig_by_name("z").unwrap(),
+    );
+    let set: RegionSet<u32> = [
+        region(z, 0, 5),
+        region(a, 10, 20),
+        region(b, 50, 60),
+        region(a, 0, 5),
+    ]
+    .into_iter()
+    .collect();
+
+    let chunks: Vec<Vec<_>> = set
+        .by_contig(&build)
+        .iter()
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|r| (r.contig().name(), *r.start()))
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        chunks,
+        [vec![("b", 50)], vec![("a", 0), ("a", 10)], vec![("z", 0)]]
+    );
+}
+
+#[test]
+fn partition_into_balanced_groups() {
+    let build = build_of(
+        "toy",
+        &[
+            ("1", &[], 100),
+            ("2", &[], 60),
+            ("3", &[], 50),
+            ("4", &[], 40),
+            ("5", &[], 10),
+        ],
+    );
+    let names = |groups: &[Vec<GenomicRegion<u32>>]| -> Vec<Vec<String>> {
+        groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|r| r.contig().name().to_string())
+                    .collect()
+            })
+            .collect()
+    };
+
+    // 100 | 60 + 10 | 50 + 40
+    assert_eq!(
+        names(&build.partition(3)),
+        [vec!["1"], vec!["2", "5"], vec!["3", "4"]]
+    );
+    assert_eq!(
+        build.partition(7).iter().filter(|g| g.is_empty()).count(),
+        2
+    );
+
+    let windows = partition_regions(build.windows(30, 30), 4);
+    let totals: Vec<u32> = windows
+        .iter()
+        .map(|group| group.iter().map(|r| r.end() - r.start()).sum())
+        .collect();
+    assert_eq!(totals.iter().sum::<u32>(), 260);
+    assert!(totals.iter().all(|&total| (60..=70).contains(&total)));
+}
+
+#[test]
+fn aliases_checked_against_bundled_builds() {
+    let mut build = build_of("toy", &[("1", &[], 248_956_422), ("2", &[], 10)]);
+    // The GRCh38 accession of chromosome 1, with the GRCh38 length.
+    let report = build.add_alias_checked("1", "NC_000001.11").unwrap();
+    assert!(report.is_valid());
+    assert!(build.add_alias_checked("2", "chr2").unwrap().is_valid());
+    assert!(build.add_alias_checked("3", "chr3").is_err());
+
+    let other = build_of("other", &[("mito", &["NC_012920.1"], 100)]);
+    let (merged, report) = build.merge_checked(&other, ConflictPolicy::Error).unwrap();
+    assert!(merged.contig_by_name("mito").is_some());
+    assert_eq!(
+        report.problems(),
+        KnownBuild::ALL.map(|known| BuildProblem::AliasLengthMismatch {
+            alias: "NC_012920.1".to_string(),
+            contig: "mito".to_string(),
+            length: 100,
+            known,
+            known_length: 16_569,
+        })
+    );
+}
+
+#[test]
+fn regions_ordered_in_build() {
+    let build: GenomeBuild<u32> = GenomeBuild::builder()
+        .id(GenomeBuildIdentifier::from_str("toy").unwrap())
+        .contig("b", 100)
+        .contig("a", 100)
+        .ordering(ContigOrdering::Insertion)
+        .build()
+        .unwrap();
+    let other = build_of("other", &[("z", &[], 10), ("y", &[], 10)]);
+    let region = |build: &GenomeBuild<u32>, name, start, end| {
+        GenomicRegion::new(build.contig_by_name(name).unwrap(), start, end).unwrap()
+    };
+
+    let (a, b) = (region(&build, "a", 0, 10), region(&build, "b", 50, 60));
+    assert_eq!(b.cmp_in(&a, &build), std::cmp::Ordering::Less);
+    assert_eq!(a.cmp_in(&a, &build), std::cmp::Ordering::Equal);
+
+    let keys: std::collections::BTreeSet<_> = [
+        region(&other, "z", 0, 5),
+        region(&build, "a", 10, 20),
+        region(&other, "y", 0, 5),
+        region(&build, "b", 50, 60),
+        region(&build, "a", 0, 20),
+        region(&build, "a", 0, 10),
+    ]
+    .into_iter()
+    .map(|r| r.key_in(&build))
+    .collect();
+    let sorted: Vec<_> = keys
+        .iter()
+        .map(|k| {
+            (
+                k.rank(),
+                k.region().contig().name(),
+                *k.region().start(),
+                *k.region().end(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        sorted,
+        [
+            (Some(0), "b", 50, 60),
+            (Some(1), "a", 0, 10),
+            (Some(1), "a", 0, 20),
+            (Some(1), "a", 10, 20),
+            (None, "y", 0, 5),
+            (None, "z", 0, 5),
+        ]
+    );
+}
+
+#[test]
+fn contig_keys_are_tied_to_build() {
+    let build = build_of("toy", &[("chr1", &["1"], 100), ("chr2", &["2"], 50)]);
+    let renamed = build
+        .clone()
+        .with_id(GenomeBuildIdentifier::from_str("other").unwrap());
+    let subset = build.retain(|c| c.name() == "chr2");
+
+    let key: ContigKey = build.contig_key("2").unwrap();
+    assert_eq!(build.contig_key("chr2"), Some(key));
+    assert_eq!(key.build(), build.fingerprint());
+    assert_eq!(build.contig_by_key(key).map(Contig::name), Some("chr2"));
+    assert_eq!(build.contig_key("chrZ"), None);
+
+    // The clones share the fingerprint, the builds with other ids or contigs do not.
+    assert_eq!(build.clone().contig_key("2"), Some(key));
+    assert_ne!(renamed.fingerprint(), build.fingerprint());
+    assert_ne!(subset.fingerprint(), build.fingerprint());
+    assert_eq!(subset.contig_by_key(key), None);
+
+    let keys: Vec<_> = build
+        .contig_keys()
+        .map(|(k, c)| (k.index(), c.name()))
+        .collect();
+    assert_eq!(keys, [(0, "chr1"), (1, "chr2")]);
+}
+
+#[test]
+fn fingerprint_covers_lengths_alt_names_and_roles() {
+    let build = build_of("x.p1", &[("1", &["chr1"], 10)]);
+    let longer = build_of("x.p1", &[("1", &["chr1"], 99)]);
+    let renamed = build_of("x.p1", &[("1", &["NC_1"], 10)]);
+    let with_role: GenomeBuild<u32> = GenomeBuild::builder()
+        .id(GenomeBuildIdentifier::from_str("x.p1").unwrap())
+        .contig("1", 10)
+        .alias("1", "chr1")
+        .role("1", SequenceRole::AssembledMolecule)
+        .build()
+        .unwrap();
+
+    for other in [&longer, &renamed, &with_role] {
+        assert_ne!(other.fingerprint(), build.fingerprint());
+    }
+    // The stale key of the same-named build is not resolved.
+    let key = build.contig_key("1").unwrap();
+    assert_eq!(longer.contig_by_key(key), None);
+    assert_eq!(
+        build.fingerprint(),
+        build_of("x.p1", &[("1", &["chr1"], 10)]).fingerprint()
+    );
+}
+
+#[test]
+fn contig_map_accumulates_per_contig() {
+    let build = build_of("toy", &[("chr1", &["1"], 100), ("chr2", &["2"], 50)]);
+    let other = build_of("other", &[("chr1", &["1"], 100)]);
+
+    let mut coverage: ContigMap<u32> = build.contig_map();
+    assert_eq!(coverage.len(), 2);
+    for (name, depth) in [("1", 3), ("chr2", 1), ("chr1", 2)] {
+        coverage[build.contig_key(name).unwrap()] += depth;
+    }
+    assert_eq!(coverage.values(), [5, 1]);
+
+    let foreign = other.contig_key("chr1").unwrap();
+    assert_eq!(coverage.get(foreign), None);
+    assert_eq!(coverage.get_mut(foreign), None);
+
+    for (_, depth) in coverage.iter_mut() {
+        *depth *= 2;
+    }
+    let named: Vec<_> = coverage
+        .iter()
+        .map(|(k, d)| (build.contig_by_key(k).unwrap().name(), *d))
+        .collect();
+    assert_eq!(named, [("chr1", 10), ("chr2", 2)]);
+    assert_eq!(build.make_vec::<Option<u8>>(), [None, None]);
+}
+
+#[test]
+fn length_histogram_and_cumulative_lengths() {
+    let build = build_of(
+        "toy",
+        &[
+            ("a", &[], 10),
+            ("b", &[], 35),
+            ("c", &[], 99),
+            ("d", &[], 100),
+        ],
+    );
+
+    let histogram: Vec<_> = build
+        .length_histogram(4)
+        .iter()
+        .map(|bin| (bin.start(), bin.end(), bin.count(), bin.total()))
+        .collect();
+    assert_eq!(
+        histogram,
+        [
+            (0, 26, 1, 10),
+            (26, 52, 1, 35),
+            (52, 78, 0, 0),
+            (78, 104, 2, 199)
+        ]
+    );
+    assert_eq!(build_of("empty", &[]).length_histogram(3), []);
+
+    assert_eq!(build.cumulative_lengths(), [100, 199, 234, 244]);
+}
+
+#[test]
+fn expected_contigs_by_sex() {
+    let build: GenomeBuild<u32> = GenomeBuild::builder()
+        .id(GenomeBuildIdentifier::from_str("toy").unwrap())
+        .contig("chr1", 100)
+        .role("chr1", SequenceRole::AssembledMolecule)
+        .contig("chrX", 80)
+        .role("chrX", SequenceRole::AssembledMolecule)
+        .contig("chrY", 20)
+        .role("chrY", SequenceRole::AssembledMolecule)
+        .contig("chrM", 5)
+        .role("chrM", SequenceRole::AssembledMolecule)
+        .contig("chrUn_1", 5)
+        .role("chrUn_1", SequenceRole::UnplacedScaffold)
+        .ordering(ContigOrdering::Insertion)
+        .build()
+        .unwrap();
+    let names = |sex| {
+        build
+            .expected_contigs(sex)
+            .map(Contig::name)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(Sex::Female), ["chr1", "chrX", "chrM"]);
+    assert_eq!(names(Sex::Male), ["chr1", "chrX", "chrY", "chrM"]);
+
+    // The chromosomes without a role are recognized by the karyotype of the assembly.
+    let fai = build_of(
+        "GRCh38",
+        &[("chr1", &[], 100), ("chrY", &[], 20), ("chrUn_1", &[], 5)],
+    );
+    let female: Vec<_> = fai
+        .expected_contigs(Sex::Female)
+        .map(Contig::name)
+        .collect();
+    assert_eq!(female, ["chr1"]);
+
+    assert_eq!("XY".parse(), Ok(Sex::Male));
+    assert_eq!("Female".parse(), Ok(Sex::Female));
+    assert!("XXY".parse::<Sex>().is_err());
+}
+
+#[test]
+fn spike_ins_are_appended() {
+    let build = build_of("toy", &[("chr1", &["1"], 100)]);
+
+    let merged = build
+        .with_spike_ins(SpikeIn::ALL.iter().map(|s| s.contig().unwrap()))
+        .unwrap();
+    let names: Vec<_> = merged.contigs().map(Contig::name).collect();
+    assert_eq!(names, ["chr1", "phiX174", "chrEBV"]);
+    assert_eq!(merged.id(), build.id());
+    assert_eq!(
+        merged.contig_by_name("NC_007605.1").map(Contig::length),
+        Some(&171_823)
+    );
+
+    let clash = Contig::new("custom", &["1"], 10).unwrap();
+    assert!(build.with_spike_ins([clash]).is_err());
+    assert_eq!(SpikeIn::PhiX.contig::<u8>(), None);
+    assert_eq!("PhiX".parse(), Ok(SpikeIn::PhiX));
+}
+
+#[test]
+fn natural_ordering_by_role_and_number() {
+    let build: GenomeBuild<u32> = GenomeBuild::builder()
+        .id(GenomeBuildIdentifier::from_str("toy").unwrap())
+        .contig("HSCHR1_ALT", 10)
+        .role("HSCHR1_ALT", SequenceRole::AltScaffold)
+        .contig("MT", 10)
+        .role("MT", SequenceRole::AssembledMolecule)
+        .contig("22", 10)
+        .role("22", SequenceRole::AssembledMolecule)
+        .contig("GL000195.1", 10)
+        .role("GL000195.1", SequenceRole::UnplacedScaffold)
+        .contig("Y", 10)
+        .role("Y", SequenceRole::AssembledMolecule)
+        .contig("3", 10)
+        .role("3", SequenceRole::AssembledMolecule)
+        .contig("X", 10)
+        .role("X", SequenceRole::AssembledMolecule)
+        .ordering(ContigOrdering::Natural)
+        .build()
+        .unwrap();
+
+    let names: Vec<_> = build.contigs().map(Contig::name).collect();
+    assert_eq!(
+        names,
+        ["3", "22", "X", "Y", "MT", "GL000195.1", "HSCHR1_ALT"]
+    );
+    assert_eq!(build.ordering(), ContigOrdering::Natural);
+}
+
+#[test]
+fn build_identifier_round_trip() {
+    for name in [
+        "GRCh38.p13",
+        "GRCh38",
+        "hg38",
+        "T2T-CHM13v2.0",
+        "Toy.p",
+        ".p1",
+    ] {
+        let id = GenomeBuildIdentifier::from_str(name).unwrap();
+        assert_eq!(id.to_string(), name);
+    }
+    let id = GenomeBuildIdentifier::from_str("GRCm39.p6").unwrap();
+    assert_eq!(id, GenomeBuildIdentifier::from(("GRCm39", "p6")));
 }
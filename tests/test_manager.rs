@@ -0,0 +1,142 @@
+#![cfg(feature = "watch")]
+
+use std::{
+    error::Error,
+    fs,
+    path::PathBuf,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use dabuild::manager::{BuildEvent, BuildManager};
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dabuild-test-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("Writable temporary directory");
+    dir
+}
+
+#[test]
+fn manager_reloads_changed_files() -> Result<(), Box<dyn Error>> {
+    let dir = scratch("manager");
+    fs::copy(
+        "data/GCF_000001635.27_GRCm39_assembly_report.txt",
+        dir.join("GRCm39_assembly_report.txt"),
+    )?;
+    fs::write(dir.join("toy.chrom.sizes"), "chr1\t1000\nchr2\t500\n")?;
+    fs::write(dir.join("README.md"), "Not a reference file")?;
+
+    let manager = BuildManager::<u32>::new(&dir);
+    assert_eq!(
+        manager.poll()?,
+        [
+            BuildEvent::Added("GRCm39_assembly_report.txt".to_string()),
+            BuildEvent::Added("toy.chrom.sizes".to_string()),
+        ]
+    );
+    assert_eq!(
+        manager.names(),
+        ["GRCm39_assembly_report.txt", "toy.chrom.sizes"]
+    );
+    let mouse = manager
+        .get("GRCm39_assembly_report.txt")
+        .expect("Loaded build");
+    assert_eq!(mouse.id().major_assembly(), "GRCm39");
+    let toy = manager.get("toy.chrom.sizes").expect("Loaded build");
+    assert_eq!(toy.id().major_assembly(), "toy");
+
+    // Nothing changed.
+    assert!(manager.poll()?.is_empty());
+
+    fs::write(
+        dir.join("toy.chrom.sizes"),
+        "chr1\t1000\nchr2\t500\nchr3\t250\n",
+    )?;
+    fs::remove_file(dir.join("GRCm39_assembly_report.txt"))?;
+    assert_eq!(
+        manager.poll()?,
+        [
+            BuildEvent::Removed("GRCm39_assembly_report.txt".to_string()),
+            BuildEvent::Updated("toy.chrom.sizes".to_string()),
+        ]
+    );
+    assert!(manager.get("GRCm39_assembly_report.txt").is_none());
+    let updated = manager.get("toy.chrom.sizes").expect("Reloaded build");
+    assert_eq!(updated.contigs().count(), 3);
+    // The old handle stays valid.
+    assert_eq!(toy.contigs().count(), 2);
+
+    // A broken file keeps the previous build.
+    fs::write(dir.join("toy.chrom.sizes"), "chr1\tnot a length\n")?;
+    let events = manager.poll()?;
+    assert!(
+        matches!(events.as_slice(), [BuildEvent::Failed(name, _)] if name == "toy.chrom.sizes")
+    );
+    assert!(Arc::ptr_eq(
+        &manager.get("toy.chrom.sizes").unwrap(),
+        &updated
+    ));
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn concurrent_polls_report_each_change_once() -> Result<(), Box<dyn Error>> {
+    let dir = scratch("concurrent");
+    for i in 0..8 {
+        fs::write(dir.join(format!("toy{i}.sizes")), "chr1\t1000\n")?;
+    }
+    // The SAM files are not sequence dictionaries.
+    fs::write(
+        dir.join("reads.sam"),
+        "@HD\tVN:1.6\nread\t4\t*\t0\t0\t*\t*\t0\t0\tA\t*\n",
+    )?;
+
+    let manager = BuildManager::<u32>::new(&dir);
+    let events: Vec<_> = thread::scope(|scope| {
+        let polls: Vec<_> = (0..4).map(|_| scope.spawn(|| manager.poll())).collect();
+        polls
+            .into_iter()
+            .map(|poll| poll.join().expect("No panic"))
+            .collect::<Result<Vec<_>, _>>()
+    })?
+    .into_iter()
+    .flatten()
+    .collect();
+    assert_eq!(events.len(), 8);
+    assert!(events
+        .iter()
+        .all(|event| matches!(event, BuildEvent::Added(_))));
+    assert_eq!(manager.names().len(), 8);
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn watch_polls_in_background() -> Result<(), Box<dyn Error>> {
+    let dir = scratch("watch");
+    fs::write(dir.join("toy.fa.fai"), "chr1\t1000\t6\t60\t61\n")?;
+
+    let manager = Arc::new(BuildManager::<u32>::new(&dir));
+    let handle = manager.watch(Duration::from_millis(10));
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while manager.get("toy.fa.fai").is_none() {
+        assert!(
+            Instant::now() < deadline,
+            "The build was not loaded in time"
+        );
+        thread::sleep(Duration::from_millis(10));
+    }
+    handle.stop();
+    assert_eq!(
+        manager.get("toy.fa.fai").unwrap().id().major_assembly(),
+        "toy"
+    );
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
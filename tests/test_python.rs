@@ -0,0 +1,33 @@
+#![cfg(feature = "python")]
+
+use dabuild::python::module as dabuild_module;
+use pyo3::prelude::*;
+
+#[test]
+fn lookup_from_python() {
+    pyo3::append_to_inittab!(dabuild_module);
+    Python::initialize();
+
+    Python::attach(|py| {
+        py.run(
+            cr#"
+import dabuild
+
+build = dabuild.get_grch38_p13()
+assert build.major_assembly == "GRCh38"
+assert build.patch == "p13"
+assert len(build) == 640
+
+y = build.contig_by_name("NC_000024.10")
+assert y.name == "Y"
+assert "chrY" in y.alt_names
+assert y.length == 57_227_415
+assert "chrY" in build
+assert build.contig_by_name("chrZ") is None
+"#,
+            None,
+            None,
+        )
+    })
+    .unwrap();
+}